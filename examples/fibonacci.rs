@@ -10,7 +10,7 @@ fn main() {
 				.name("fibonacci")
 				.with_visibility(Visibility::Public)
 				.with_return_type(types::Integer)
-				.with_param(Parameter(Box::new("num"), Some(Box::new(types::Integer))))
+				.with_param(Parameter(Ident::from("num"), Some(types::Integer.into())))
 				.with_scope(
 					Scope::default()
 						.with(
@@ -25,11 +25,11 @@ fn main() {
 						.with(statement::Return(Some(operation::Add(
 							expression::FunctionCall(
 								"fibonacci",
-								vec![Box::new(operation::Subtract("num", 1))],
+								vec![operation::Subtract("num", 1).into()],
 							),
 							expression::FunctionCall(
 								"fibonacci",
-								vec![Box::new(operation::Subtract("num", 2))],
+								vec![operation::Subtract("num", 2).into()],
 							),
 						)))),
 				)
@@ -37,30 +37,40 @@ fn main() {
 				.unwrap(),
 		)
 		.with(RunScope(Scope::default().with(print::Println(
-			FunctionCall("fibonacci", vec![Box::new(5)]),
+			FunctionCall("fibonacci", vec![5.into()]),
 		))));
-	for language in vec![
+	for language in [
 		Language::Rust,
 		Language::TypeScript,
+		Language::JavaScript {
+			module_system: ModuleSystem::ESM,
+		},
+		Language::C,
 		Language::CPP,
+		Language::CSharp {
+			top_level_statements: false,
+		},
+		Language::Go,
+		Language::Java {
+			class_name: "__fibonacci",
+		},
+		Language::Kotlin,
+		Language::Lua,
+		Language::Swift,
+		Language::Ruby,
+		Language::PHP,
+		Language::Zig,
+		Language::Haskell,
+		Language::WAT,
 		Language::Python {
 			include_types: false,
-			indent_level: None,
-			indent_type: IndentType::Tab,
+			version: (3, 10),
 		},
 	]
 	.iter()
 	{
 		std::fs::write(
-			format!(
-				"./examples/__fibonacci.{}",
-				match language {
-					Language::Rust => "rs",
-					Language::TypeScript => "ts",
-					Language::CPP => "cpp",
-					Language::Python { .. } => "py",
-				}
-			),
+			format!("./examples/__fibonacci.{}", language.backend().extension()),
 			program.to_code(*language),
 		)
 		.unwrap()
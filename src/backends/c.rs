@@ -0,0 +1,341 @@
+use itertools::Itertools;
+
+use crate::{
+	backends::{escape, Context, Counter},
+	block::{Conditional, ForEach, ForRange, While},
+	ir::{Expr, Type},
+	statement::VariableInit,
+	types::Primitive,
+	Backend, Case, Comment, Field, Function, GenError, Ident, Import, Literal, NameKind, Parameter,
+	Scope, Struct, ToCode, Value, Visibility,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct C;
+
+const KEYWORDS: &[&str] = &[
+	"auto", "bool", "break", "case", "char", "const", "continue", "default", "do", "double",
+	"else", "enum", "extern", "false", "float", "for", "goto", "if", "inline", "int", "long",
+	"register", "restrict", "return", "short", "signed", "sizeof", "static", "struct", "switch",
+	"true", "typedef", "union", "unsigned", "void", "volatile", "while",
+];
+
+impl Backend for C {
+	fn name(&self) -> &'static str {
+		"C"
+	}
+
+	fn extension(&self) -> &'static str {
+		"c"
+	}
+
+	fn keywords(&self) -> &'static [&'static str] {
+		KEYWORDS
+	}
+
+	fn naming_convention(&self, _kind: NameKind) -> Option<Case> {
+		Some(Case::Snake)
+	}
+
+	fn escape_keyword(&self, name: &str) -> String {
+		format!("_{}", name)
+	}
+
+	/// Includes every header that was required.
+	fn finish(&self, code: String, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}{}",
+			cx.requirements()
+				.iter()
+				.map(|itm| format!("#include <{}>\n", itm))
+				.join(""),
+			code
+		))
+	}
+
+	fn scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		Ok(cx.children(scope)?.join(""))
+	}
+
+	fn run_scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("int main(void){{{}return 0;}}", scope.render(cx)?))
+	}
+
+	fn function(&self, function: &Function, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}{} {}({}){{{}}}",
+			function.visibility.render(cx)?,
+			match function.return_type {
+				Some(ref typ) => typ.render(cx)?,
+				None => String::from("void"),
+			},
+			function.name.render(cx)?,
+			cx.join(&function.params, ",")?,
+			function.scope.render(cx)?
+		))
+	}
+
+	fn parameter(&self, parameter: &Parameter, cx: &mut Context) -> Result<String, GenError> {
+		let Parameter(name, typ) = parameter;
+		match typ {
+			Some(typ) => Ok(format!("{} {}", typ.render(cx)?, name.render(cx)?)),
+			None => Err(cx.missing_type("parameter")),
+		}
+	}
+
+	/// Private functions have internal linkage.
+	fn visibility(&self, visibility: Visibility, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match visibility {
+			Visibility::Public => "",
+			Visibility::Private => "static ",
+		}))
+	}
+
+	/// A `typedef` of an anonymous struct, so that it is named without
+	/// `struct`. C has no private fields.
+	fn structure(&self, structure: &Struct, cx: &mut Context) -> Result<String, GenError> {
+		let mut fields = String::new();
+		for field in &structure.fields {
+			fields.push_str(&format!("{};", field.render(cx)?));
+		}
+		Ok(format!(
+			"typedef struct{{{}}}{};",
+			fields,
+			cx.type_name(structure.name.as_str())?
+		))
+	}
+
+	fn field(&self, field: &Field, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{} {}",
+			field.typ.render(cx)?,
+			cx.field_name(field.name.as_str())?
+		))
+	}
+
+	fn conditional(&self, conditional: &Conditional, cx: &mut Context) -> Result<String, GenError> {
+		let mut branches = Vec::new();
+		for (condition, scope) in &conditional.branches {
+			branches.push(format!(
+				"if({}){{{}}}",
+				condition.render(cx)?,
+				scope.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}{}",
+			branches.iter().join("else "),
+			match conditional.otherwise {
+				Some(ref last) => format!("else{{{}}}", last.render(cx)?),
+				None => String::new(),
+			}
+		))
+	}
+
+	fn while_loop(&self, while_loop: &While, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"while({}){{{}}}",
+			while_loop.condition.render(cx)?,
+			while_loop.scope.render(cx)?
+		))
+	}
+
+	fn for_range(&self, for_range: &ForRange, cx: &mut Context) -> Result<String, GenError> {
+		let typ = Type::Primitive(Primitive::Integer).render(cx)?;
+		let counter = Counter::new(for_range, cx)?;
+		Ok(format!(
+			"for({} {}={};{};{}){{{}}}",
+			typ,
+			counter.var,
+			counter.start,
+			counter.condition,
+			counter.increment(),
+			for_range.scope.render(cx)?
+		))
+	}
+
+	/// C has no collections to iterate over.
+	fn for_each(&self, _for_each: &ForEach, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("block::ForEach"))
+	}
+
+	fn break_statement(&self, label: Option<&str>, cx: &mut Context) -> Result<String, GenError> {
+		cx.innermost_loop(label, "statement::Break")?;
+		Ok(String::from("break;"))
+	}
+
+	fn continue_statement(
+		&self,
+		label: Option<&str>,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		cx.innermost_loop(label, "statement::Continue")?;
+		Ok(String::from("continue;"))
+	}
+
+	/// Variables without a type are declared with the type of their value.
+	fn variable_init(&self, init: &VariableInit, cx: &mut Context) -> Result<String, GenError> {
+		let VariableInit {
+			name, typ, value, ..
+		} = init;
+		let typ = match typ.clone().or_else(|| cx.type_of(value)) {
+			Some(typ) => typ,
+			None => return Err(cx.missing_type("variable")),
+		};
+		Ok(format!(
+			"{} {}={};",
+			typ.render(cx)?,
+			name.render(cx)?,
+			value.render(cx)?
+		))
+	}
+
+	fn assign_variable(
+		&self,
+		name: &Expr,
+		value: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}={};", name.render(cx)?, value.render(cx)?))
+	}
+
+	fn return_statement(&self, value: Option<&Expr>, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match value {
+			Some(expr) => format!("return {};", expr.render(cx)?),
+			None => String::from("return;"),
+		})
+	}
+
+	fn comment(&self, comment: &Comment, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match comment {
+			Comment::Regular(content) | Comment::Doc(content) => format!("//{}\n", content),
+			Comment::MultiLine(content) => format!("/*{}*/", content),
+		})
+	}
+
+	fn import(&self, import: &Import, cx: &mut Context) -> Result<String, GenError> {
+		match import {
+			Import::Module(location) => Ok(format!("#include \"{}\"\n", location)),
+			Import::Members(..) => Err(cx.unsupported("importing members")),
+		}
+	}
+
+	/// The format specifier is chosen from the type of `value`, and booleans
+	/// are printed as `true` or `false`.
+	fn println(&self, value: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		let specifier = match value {
+			Expr::Literal(Literal::Str(_)) => Some("%s"),
+			Expr::Literal(Literal::Char(_)) => Some("%c"),
+			_ => None,
+		};
+		if let Some(specifier) = specifier {
+			cx.require("stdio.h");
+			return Ok(format!(
+				"printf(\"{}\\n\",{});",
+				specifier,
+				value.render(cx)?
+			));
+		}
+		let primitive = match cx.type_of(value) {
+			Some(Type::Primitive(primitive)) => primitive,
+			Some(_) => return Err(cx.unsupported("printing non-primitive values")),
+			None => return Err(cx.missing_type("printed value")),
+		};
+		cx.require("stdio.h");
+		let value = value.render(cx)?;
+		Ok(match primitive {
+			Primitive::Integer => format!("printf(\"%d\\n\",{});", value),
+			Primitive::Number | Primitive::Float => format!("printf(\"%f\\n\",{});", value),
+			Primitive::Boolean => format!("printf(\"%s\\n\",{}?\"true\":\"false\");", value),
+		})
+	}
+
+	fn expression_statement(&self, expr: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("{};", expr.render(cx)?))
+	}
+
+	fn ternary(
+		&self,
+		condition: &Expr,
+		if_true: &Expr,
+		if_false: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}?{}:{}",
+			condition.render(cx)?,
+			if_true.render(cx)?,
+			if_false.render(cx)?
+		))
+	}
+
+	fn primitive_type(&self, primitive: Primitive, cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match primitive {
+			Primitive::Integer => "int",
+			Primitive::Number | Primitive::Float => "double",
+			Primitive::Boolean => {
+				cx.require("stdbool.h");
+				"bool"
+			}
+		}))
+	}
+
+	fn generic_type(
+		&self,
+		_name: &str,
+		_args: &[Type],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Err(cx.unsupported("types::Generic"))
+	}
+
+	fn future_type(&self, _value: &Type, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("types::Future"))
+	}
+
+	fn value(&self, value: &Value, cx: &mut Context) -> Result<String, GenError> {
+		cx.require("stdbool.h");
+		Ok(String::from(match value {
+			Value::True => "true",
+			Value::False => "false",
+		}))
+	}
+
+	fn string(&self, text: &str, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(escape::quote(text, '"', "", escape::octal))
+	}
+
+	fn character(&self, c: char, cx: &mut Context) -> Result<String, GenError> {
+		if !c.is_ascii() {
+			return Err(cx.unsupported("non-ASCII characters"));
+		}
+		Ok(escape::quote(&c.to_string(), '\'', "", escape::octal))
+	}
+
+	fn null(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.require("stddef.h");
+		Ok(String::from("NULL"))
+	}
+
+	/// A compound literal with designated initializers.
+	fn struct_literal(
+		&self,
+		name: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let mut values = Vec::new();
+		for (field, value) in fields {
+			values.push(format!(
+				".{}={}",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!(
+			"({}){{{}}}",
+			cx.type_name(name.as_str())?,
+			cx.list(&values)?
+		))
+	}
+}
@@ -0,0 +1,732 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+	backends::layout::{self, DEDENT, INDENT, NEWLINE},
+	ir::{Associativity, Expr, Item, Operator, Stmt, Type},
+	types::Primitive,
+	Backend, BraceStyle, Class, Enum, GenError, Ident, Interface, Language, Literal, NameKind,
+	NamingPolicy, NodePath, Parameter, Receiver, RenderOptions, Scope, Struct, ToCode,
+};
+
+/// The names declared by a function or scope, which are forgotten when it
+/// ends.
+#[derive(Debug, Default)]
+struct Frame {
+	/// The name that each rendered name was written for.
+	names: HashMap<String, String>,
+	variables: HashSet<String>,
+	functions: HashSet<String>,
+	/// The parameters of each function declared in this scope.
+	parameters: HashMap<String, Vec<Parameter>>,
+	/// The type of each variable and function declared in this scope, or
+	/// `None` if it is not known.
+	types: HashMap<String, Option<Type>>,
+}
+
+/// The state of a single render, passed to every [`ToCode::render`] and
+/// [`Backend`] method.
+#[derive(Debug)]
+pub struct Context {
+	language: Language,
+	options: RenderOptions,
+	path: Vec<String>,
+	requirements: Vec<String>,
+	renames: HashMap<String, String>,
+	/// The innermost function or scope is last.
+	frames: Vec<Frame>,
+	entry_point: bool,
+	depth: usize,
+	/// The label of every loop around the node being rendered.
+	loops: Vec<Option<String>>,
+	structs: HashMap<String, Struct>,
+	classes: HashMap<String, Class>,
+	enums: HashMap<String, Enum>,
+	interfaces: HashMap<String, Interface>,
+	/// The class whose members are being rendered.
+	class: Option<String>,
+	receiver: Receiver,
+	/// The return type of the function being rendered.
+	result: Option<Type>,
+}
+
+impl Context {
+	pub fn new(language: Language) -> Self {
+		Self::with_options(language, RenderOptions::default())
+	}
+
+	pub fn with_options(language: Language, options: RenderOptions) -> Self {
+		Self {
+			language,
+			options,
+			path: Vec::new(),
+			requirements: Vec::new(),
+			renames: HashMap::new(),
+			frames: vec![Frame::default()],
+			entry_point: false,
+			depth: 0,
+			loops: Vec::new(),
+			structs: HashMap::new(),
+			classes: HashMap::new(),
+			enums: HashMap::new(),
+			interfaces: HashMap::new(),
+			class: None,
+			receiver: Receiver::Static,
+			result: None,
+		}
+	}
+
+	#[inline]
+	pub fn language(&self) -> Language {
+		self.language
+	}
+
+	#[inline]
+	pub fn backend(&self) -> &'static dyn Backend {
+		self.language.backend()
+	}
+
+	#[inline]
+	pub fn options(&self) -> &RenderOptions {
+		&self.options
+	}
+
+	/// The path to the node currently being rendered.
+	pub fn path(&self) -> NodePath {
+		NodePath(self.path.clone())
+	}
+
+	/// Runs `f` with `segment` appended to the current path.
+	pub fn within<T>(
+		&mut self,
+		segment: impl Into<String>,
+		f: impl FnOnce(&mut Self) -> Result<T, GenError>,
+	) -> Result<T, GenError> {
+		self.path.push(segment.into());
+		let result = f(self);
+		self.path.pop();
+		result
+	}
+
+	/// How deeply the node being rendered is nested: `1` for the children of
+	/// the outermost [`Scope`], plus one for every scope, function and
+	/// [`crate::RunScope`] around it.
+	#[inline]
+	pub fn depth(&self) -> usize {
+		self.depth
+	}
+
+	/// Runs `f` one [`Context::depth`] deeper. The names that are declared
+	/// inside of it are forgotten afterwards.
+	pub fn nested<T>(
+		&mut self,
+		f: impl FnOnce(&mut Self) -> Result<T, GenError>,
+	) -> Result<T, GenError> {
+		self.depth += 1;
+		self.frames.push(Frame::default());
+		let result = f(self);
+		self.frames.pop();
+		self.depth -= 1;
+		result
+	}
+
+	/// Runs `f` inside of a loop, which `break` and `continue` can refer to by
+	/// `label`.
+	pub fn looping<T>(
+		&mut self,
+		label: Option<&str>,
+		f: impl FnOnce(&mut Self) -> Result<T, GenError>,
+	) -> Result<T, GenError> {
+		self.loops.push(label.map(str::to_owned));
+		let result = f(self);
+		self.loops.pop();
+		result
+	}
+
+	/// Runs `f` inside of the class `name`.
+	pub fn inside_class<T>(
+		&mut self,
+		name: &str,
+		f: impl FnOnce(&mut Self) -> Result<T, GenError>,
+	) -> Result<T, GenError> {
+		let outer = self.class.replace(name.to_owned());
+		let result = f(self);
+		self.class = outer;
+		result
+	}
+
+	/// Runs `f` inside of a method that receives its instance as `receiver`.
+	pub fn receiving<T>(
+		&mut self,
+		receiver: Receiver,
+		f: impl FnOnce(&mut Self) -> Result<T, GenError>,
+	) -> Result<T, GenError> {
+		let outer = std::mem::replace(&mut self.receiver, receiver);
+		let result = f(self);
+		self.receiver = outer;
+		result
+	}
+
+	/// Runs `f` inside of a function that returns `result`.
+	pub fn returning<T>(
+		&mut self,
+		result: Option<Type>,
+		f: impl FnOnce(&mut Self) -> Result<T, GenError>,
+	) -> Result<T, GenError> {
+		let outer = std::mem::replace(&mut self.result, result);
+		let value = f(self);
+		self.result = outer;
+		value
+	}
+
+	/// The return type of the function being rendered, if it has one.
+	#[inline]
+	pub fn return_type(&self) -> Option<&Type> {
+		self.result.as_ref()
+	}
+
+	/// How the method being rendered receives its instance, which is
+	/// [`Receiver::Static`] outside of methods.
+	#[inline]
+	pub fn receiver(&self) -> Receiver {
+		self.receiver
+	}
+
+	/// The labels of the loops around the node being rendered, from the
+	/// outermost inwards.
+	#[inline]
+	pub fn loops(&self) -> &[Option<String>] {
+		&self.loops
+	}
+
+	/// The index into [`Context::loops`] of the loop that a `break` or
+	/// `continue` with `label` refers to: the innermost loop with that label, or
+	/// the innermost loop if there is no label.
+	pub fn loop_target(&self, label: Option<&str>, construct: &str) -> Result<usize, GenError> {
+		let target = match label {
+			Some(label) => self
+				.loops
+				.iter()
+				.rposition(|itm| itm.as_deref() == Some(label)),
+			None => self.loops.len().checked_sub(1),
+		};
+		target.ok_or_else(|| GenError::NotInLoop {
+			construct: construct.to_owned(),
+			target: match label {
+				Some(label) => format!("a loop labeled `{}`", label),
+				None => String::from("a loop"),
+			},
+			path: self.path(),
+		})
+	}
+
+	/// Checks that a `break` or `continue` with `label` refers to the innermost
+	/// loop, for languages without labels.
+	pub fn innermost_loop(&self, label: Option<&str>, construct: &str) -> Result<(), GenError> {
+		if self.loop_target(label, construct)? + 1 == self.loops.len() {
+			Ok(())
+		} else {
+			Err(self.unsupported(format!("{} out of an outer loop", construct)))
+		}
+	}
+
+	/// Records that the generated code depends on `requirement`, e.g. a module
+	/// that has to be imported. What a requirement means is up to the backend,
+	/// which usually emits them in [`Backend::finish`].
+	pub fn require(&mut self, requirement: impl Into<String>) {
+		let requirement = requirement.into();
+		if !self.requirements.contains(&requirement) {
+			self.requirements.push(requirement);
+		}
+	}
+
+	/// Every requirement recorded so far, in the order they were first
+	/// required.
+	#[inline]
+	pub fn requirements(&self) -> &[String] {
+		&self.requirements
+	}
+
+	/// Renders every reference to `name` as `to` from now on.
+	pub fn rename(&mut self, name: impl Into<String>, to: impl Into<String>) {
+		self.renames.insert(name.into(), to.into());
+	}
+
+	/// The name that `name` is rendered as, see [`Context::rename`] and
+	/// [`Context::escape`].
+	pub fn resolve(&mut self, name: &str) -> Result<String, GenError> {
+		match self.renames.get(name) {
+			Some(to) => Ok(to.clone()),
+			None => self.escape(name),
+		}
+	}
+
+	/// `name` in the convention of the target if [`RenderOptions::naming`]
+	/// asks for it. Only declared variables and functions are converted, as
+	/// other names may belong to the target, e.g. `HashMap::new`.
+	pub fn convention(&self, name: &str) -> String {
+		if self.options.naming == NamingPolicy::Preserve {
+			return name.to_owned();
+		}
+		let kind = if self.is_variable(name) {
+			NameKind::Variable
+		} else if self.is_function(name) {
+			NameKind::Function
+		} else {
+			return name.to_owned();
+		};
+		match self.backend().naming_convention(kind) {
+			Some(case) => case.apply(name),
+			None => name.to_owned(),
+		}
+	}
+
+	/// `name` in the convention of the target and escaped, see
+	/// [`Context::reserve`].
+	pub fn escape(&mut self, name: &str) -> Result<String, GenError> {
+		let converted = self.convention(name);
+		self.reserve(name, converted)
+	}
+
+	/// The field `name` in the convention of the target and escaped.
+	pub fn field_name(&mut self, name: &str) -> Result<String, GenError> {
+		let converted = match self.options.naming {
+			NamingPolicy::Preserve => None,
+			NamingPolicy::Conventional => self.backend().naming_convention(NameKind::Field),
+		};
+		let converted = match converted {
+			Some(case) => case.apply(name),
+			None => name.to_owned(),
+		};
+		self.reserve(name, converted)
+	}
+
+	/// The name that the type `name` is rendered as, which is escaped but
+	/// never converted to a naming convention.
+	pub fn type_name(&mut self, name: &str) -> Result<String, GenError> {
+		match self.renames.get(name) {
+			Some(to) => Ok(to.clone()),
+			None => self.reserve(name, name.to_owned()),
+		}
+	}
+
+	/// Escapes `name` if it is one of the [`Backend::keywords`] and records
+	/// that it is written for `original`, which fails if it is already written
+	/// for another name that is in scope. The record lasts as long as the
+	/// declaration of `original`.
+	pub fn reserve(&mut self, original: &str, name: String) -> Result<String, GenError> {
+		let backend = self.backend();
+		let name = if backend.keywords().contains(&name.as_str()) {
+			backend.escape_keyword(&name)
+		} else {
+			name
+		};
+		match self
+			.frames
+			.iter()
+			.rev()
+			.find_map(|itm| itm.names.get(&name))
+		{
+			Some(first) if first != original => Err(GenError::NameCollision {
+				first: first.clone(),
+				second: original.to_owned(),
+				name,
+				language: backend.name(),
+				path: self.path(),
+			}),
+			Some(_) => Ok(name),
+			None => {
+				let frame = self.frames.iter_mut().rev().find(|itm| {
+					itm.variables.contains(original) || itm.functions.contains(original)
+				});
+				let frame = match frame {
+					Some(frame) => frame,
+					None => self.frames.last_mut().unwrap(),
+				};
+				frame.names.insert(name.clone(), original.to_owned());
+				Ok(name)
+			}
+		}
+	}
+
+	/// Records the type of a variable, or the return type of a function, so
+	/// that [`Context::type_of`] can find it.
+	pub fn declare(&mut self, name: impl Into<String>, typ: Type) {
+		let frame = self.frames.last_mut().unwrap();
+		frame.types.insert(name.into(), Some(typ));
+	}
+
+	/// Records the fields of `structure`, which [`Context::type_of`] and
+	/// [`Context::declared_order`] look up.
+	pub fn declare_struct(&mut self, structure: Struct) {
+		self.structs
+			.insert(structure.name.as_str().to_owned(), structure);
+	}
+
+	/// The struct called `name`, if it has been rendered.
+	pub fn structure(&self, name: &str) -> Option<&Struct> {
+		self.structs.get(name)
+	}
+
+	/// Records the fields and methods of `class`.
+	pub fn declare_class(&mut self, class: Class) {
+		self.classes.insert(class.name.as_str().to_owned(), class);
+	}
+
+	/// The class called `name`, if it has been rendered.
+	pub fn class(&self, name: &str) -> Option<&Class> {
+		self.classes.get(name)
+	}
+
+	/// Records the variants of `enumeration`, which [`crate::block::Match`]
+	/// checks its arms against.
+	pub fn declare_enum(&mut self, enumeration: Enum) {
+		self.enums
+			.insert(enumeration.name.as_str().to_owned(), enumeration);
+	}
+
+	/// The enum called `name`, if it has been rendered.
+	pub fn enumeration(&self, name: &str) -> Option<&Enum> {
+		self.enums.get(name)
+	}
+
+	/// Records the methods of `interface`, which classes that implement it
+	/// are checked against.
+	pub fn declare_interface(&mut self, interface: Interface) {
+		self.interfaces
+			.insert(interface.name.as_str().to_owned(), interface);
+	}
+
+	/// The interface called `name`, if it has been rendered.
+	pub fn interface(&self, name: &str) -> Option<&Interface> {
+		self.interfaces.get(name)
+	}
+
+	/// The interface of the class being rendered that declares the method
+	/// `name`, if any.
+	pub fn implementing(&self, name: &str) -> Option<&Interface> {
+		let class = self.classes.get(self.class.as_ref()?)?;
+		class
+			.interfaces
+			.iter()
+			.filter_map(|itm| self.interfaces.get(itm.as_str()))
+			.find(|itm| itm.method(name).is_some())
+	}
+
+	/// The fields of a literal of the struct `name` in the order they were
+	/// declared in, for languages that construct structs with positional
+	/// arguments. Fields of structs that have not been rendered stay in the
+	/// order they were given in.
+	pub fn declared_order<'a>(
+		&self,
+		name: &str,
+		fields: &'a [(Ident, Expr)],
+	) -> Vec<&'a (Ident, Expr)> {
+		match self.structure(name) {
+			Some(structure) => super::ordered(&structure.fields, fields),
+			None => fields.iter().collect(),
+		}
+	}
+
+	/// Records that `name` is a variable or parameter of the innermost
+	/// function or scope, whose type is unknown until it is given to
+	/// [`Context::declare`].
+	pub fn declare_variable(&mut self, name: impl Into<String>) {
+		let name = name.into();
+		let frame = self.frames.last_mut().unwrap();
+		frame.types.insert(name.clone(), None);
+		frame.variables.insert(name);
+	}
+
+	/// Whether `name` was declared with [`Context::declare_variable`] in a
+	/// function or scope around the node being rendered.
+	pub fn is_variable(&self, name: &str) -> bool {
+		self.frames.iter().any(|itm| itm.variables.contains(name))
+	}
+
+	/// Records that `name` is a function or method of the innermost scope.
+	pub fn declare_function(&mut self, name: impl Into<String>) {
+		let frame = self.frames.last_mut().unwrap();
+		frame.functions.insert(name.into());
+	}
+
+	/// Records the parameters of the function `name`, so that the arguments
+	/// of a call can be checked against them before the function appears.
+	pub fn declare_parameters(&mut self, name: impl Into<String>, parameters: Vec<Parameter>) {
+		let frame = self.frames.last_mut().unwrap();
+		frame.parameters.insert(name.into(), parameters);
+	}
+
+	/// The parameters of the function `name` that is in scope, if any.
+	pub fn parameters(&self, name: &str) -> Option<&[Parameter]> {
+		self.frames
+			.iter()
+			.rev()
+			.find_map(|itm| itm.parameters.get(name))
+			.map(Vec::as_slice)
+	}
+
+	/// Whether `name` was declared with [`Context::declare_function`] and is
+	/// in scope.
+	pub fn is_function(&self, name: &str) -> bool {
+		self.frames.iter().any(|itm| itm.functions.contains(name))
+	}
+
+	/// The type of `expr` as far as it can be inferred from its literals,
+	/// operators and the names declared so far.
+	pub fn type_of(&self, expr: &Expr) -> Option<Type> {
+		match expr {
+			Expr::Ident(name) => self
+				.frames
+				.iter()
+				.rev()
+				.find_map(|itm| itm.types.get(name.as_str()))?
+				.clone(),
+			Expr::Int(_) => Some(Type::Primitive(Primitive::Integer)),
+			Expr::Literal(Literal::Int { suffix, .. }) => {
+				Some(Type::Primitive(suffix.unwrap_or(Primitive::Integer)))
+			}
+			Expr::Literal(Literal::Float(_)) => Some(Type::Primitive(Primitive::Float)),
+			Expr::Literal(_) => None,
+			Expr::Value(_) => Some(Type::Primitive(Primitive::Boolean)),
+			Expr::Binary(
+				Operator::Add
+				| Operator::Subtract
+				| Operator::Multiply
+				| Operator::Divide
+				| Operator::Mod,
+				lhs,
+				rhs,
+			) => match (self.type_of(lhs), self.type_of(rhs)) {
+				(Some(Type::Primitive(Primitive::Integer)), Some(rhs)) => Some(rhs),
+				(lhs, rhs) => lhs.or(rhs),
+			},
+			Expr::Binary(..) => Some(Type::Primitive(Primitive::Boolean)),
+			Expr::Call(name, _) => self.type_of(name),
+			Expr::Ternary(_, if_true, if_false) => {
+				self.type_of(if_true).or_else(|| self.type_of(if_false))
+			}
+			Expr::StructLiteral(name, _) => Some(Type::Named(name.as_str().to_owned())),
+			Expr::Field(object, field) => match self.type_of(object)? {
+				Type::Named(name) => {
+					let field = match self.structure(&name) {
+						Some(structure) => structure.field(field.as_str()),
+						None => self.class(&name)?.field(field.as_str()),
+					};
+					Some(field?.typ.clone())
+				}
+				_ => None,
+			},
+			Expr::This => match self.receiver {
+				Receiver::Static => None,
+				_ => self.class.clone().map(Type::Named),
+			},
+			Expr::New(name, _) | Expr::Variant(name, ..) => {
+				Some(Type::Named(name.as_str().to_owned()))
+			}
+			Expr::MethodCall(object, name, _) => match self.type_of(object)? {
+				Type::Named(class) => self.method_type(&class, name),
+				_ => None,
+			},
+			Expr::StaticCall(class, name, _) => self.method_type(class.as_str(), name),
+		}
+	}
+
+	/// The return type of the method `name` of the class `class`.
+	fn method_type(&self, class: &str, name: &Ident) -> Option<Type> {
+		let method = self.class(class)?.method(name.as_str())?;
+		method.function.return_type.clone()
+	}
+
+	/// Whether a [`crate::RunScope`] has been rendered.
+	#[inline]
+	pub fn has_entry_point(&self) -> bool {
+		self.entry_point
+	}
+
+	#[inline]
+	pub(crate) fn mark_entry_point(&mut self) {
+		self.entry_point = true;
+	}
+
+	/// Renders every child of `scope`, recording its index in the path. The
+	/// functions and methods among them are declared first, so that they can
+	/// be called before they appear.
+	pub fn children(&mut self, scope: &Scope) -> Result<Vec<String>, GenError> {
+		for child in scope.children() {
+			match child {
+				Stmt::Item(Item::Function(function)) => {
+					self.declare_function(function.name.as_str());
+					self.declare_parameters(function.name.as_str(), function.params.clone());
+				}
+				Stmt::Item(Item::Class(class)) => {
+					for method in &class.methods {
+						self.declare_function(method.function.name.as_str());
+					}
+				}
+				Stmt::Item(Item::Interface(interface)) => {
+					for method in &interface.methods {
+						self.declare_function(method.name.as_str());
+					}
+				}
+				_ => {}
+			}
+		}
+		scope
+			.children()
+			.iter()
+			.enumerate()
+			.map(|(idx, child)| self.within(format!("[{}]", idx), |cx| child.render(cx)))
+			.collect()
+	}
+
+	/// Renders each of `items` and joins the results with `separator`.
+	pub fn join<'a, T: ToCode + ?Sized + 'a>(
+		&mut self,
+		items: impl IntoIterator<Item = &'a T>,
+		separator: &str,
+	) -> Result<String, GenError> {
+		Ok(items
+			.into_iter()
+			.map(|itm| itm.render(self))
+			.collect::<Result<Vec<_>, _>>()?
+			.join(separator))
+	}
+
+	/// Joins rendered statements, putting each on a line of its own if
+	/// [`RenderOptions::newlines`] is set.
+	pub fn statements(&self, statements: Vec<String>) -> String {
+		if self.options.newlines {
+			layout::lines(statements)
+		} else {
+			statements.join("")
+		}
+	}
+
+	/// Wraps the result of [`Context::statements`] in braces.
+	pub fn braces(&self, body: &str) -> String {
+		if !self.options.newlines {
+			return format!("{{{}}}", body);
+		}
+		let open = match self.options.brace_style {
+			BraceStyle::KAndR => String::from(" {"),
+			BraceStyle::Allman => format!("{}{{", NEWLINE),
+		};
+		if body.is_empty() {
+			return format!("{}}}", open);
+		}
+		format!("{}{}{}{}{}}}", open, INDENT, body, DEDENT, NEWLINE)
+	}
+
+	/// What goes between the closing brace of a block and `keyword`, e.g.
+	/// `else`.
+	pub fn continuation(&self, keyword: &str) -> String {
+		match (self.options.newlines, self.options.brace_style) {
+			(false, _) => keyword.to_owned(),
+			(true, BraceStyle::KAndR) => format!(" {}", keyword),
+			(true, BraceStyle::Allman) => format!("{}{}", NEWLINE, keyword),
+		}
+	}
+
+	/// `lhs` and `rhs` joined by `operator`, which is surrounded by spaces if
+	/// [`RenderOptions::spaces`] is set.
+	pub fn infix(&self, lhs: &str, operator: &str, rhs: &str) -> String {
+		if self.options.spaces {
+			format!("{} {} {}", lhs, operator.trim(), rhs)
+		} else {
+			format!("{}{}{}", lhs, operator, rhs)
+		}
+	}
+
+	/// Renders the left or right operand of `operator`, parenthesized only if
+	/// it would otherwise be grouped differently according to
+	/// [`Backend::precedence`].
+	pub fn operand(
+		&mut self,
+		operator: Operator,
+		operand: &Expr,
+		right: bool,
+	) -> Result<String, GenError> {
+		let code = operand.render(self)?;
+		let parenthesize = match operand {
+			Expr::Binary(inner, ..) => {
+				let (level, associativity) = self.backend().precedence(operator);
+				let (inner_level, _) = self.backend().precedence(*inner);
+				inner_level < level
+					|| inner_level == level
+						&& match associativity {
+							Associativity::Left => right,
+							Associativity::Right => !right,
+							Associativity::None => true,
+						}
+			}
+			Expr::Ternary(..) => true,
+			Expr::Int(value) | Expr::Literal(Literal::Int { value, .. }) => *value < 0,
+			Expr::Literal(Literal::Float(value)) => value.is_sign_negative(),
+			_ => false,
+		};
+		Ok(if parenthesize {
+			format!("({})", code)
+		} else {
+			code
+		})
+	}
+
+	/// Renders the items of an argument or parameter list, which is put on a
+	/// line per item if it is longer than [`RenderOptions::max_width`]. Each
+	/// of those lines ends in a comma if [`Backend::trailing_commas`] allows
+	/// it.
+	pub fn list<'a, T: ToCode + ?Sized + 'a>(
+		&mut self,
+		items: impl IntoIterator<Item = &'a T>,
+	) -> Result<String, GenError> {
+		let items = items
+			.into_iter()
+			.map(|itm| itm.render(self))
+			.collect::<Result<Vec<_>, _>>()?;
+		let separator = if self.options.spaces { ", " } else { "," };
+		let list = items.join(separator);
+		match self.options.max_width {
+			Some(width) if self.options.newlines && list.chars().count() > width => {
+				let last = items.len() - 1;
+				let trailing = self.backend().trailing_commas();
+				let lines = items.into_iter().enumerate().map(|(idx, itm)| {
+					if idx < last || trailing {
+						format!("{},", itm)
+					} else {
+						itm
+					}
+				});
+				Ok(format!("{}{}", layout::block(lines), NEWLINE))
+			}
+			_ => Ok(list),
+		}
+	}
+
+	/// Runs `f` while rendering for `language` instead of the current language.
+	pub(crate) fn with_language<T>(
+		&mut self,
+		language: Language,
+		f: impl FnOnce(&mut Self) -> Result<T, GenError>,
+	) -> Result<T, GenError> {
+		let previous = std::mem::replace(&mut self.language, language);
+		let result = f(self);
+		self.language = previous;
+		result
+	}
+
+	pub fn missing_type(&self, construct: impl Into<String>) -> GenError {
+		GenError::MissingType {
+			construct: construct.into(),
+			language: self.backend().name(),
+			path: self.path(),
+		}
+	}
+
+	pub fn unsupported(&self, construct: impl Into<String>) -> GenError {
+		GenError::UnsupportedConstruct {
+			construct: construct.into(),
+			language: self.backend().name(),
+			path: self.path(),
+		}
+	}
+}
@@ -0,0 +1,761 @@
+use itertools::Itertools;
+
+use crate::{
+	backends::{c_like, escape, ordered, prepend, receiver, variant_type, Context, Counter},
+	block::{Conditional, ForEach, ForRange, Match, While},
+	ir::{Expr, Type},
+	statement::Return,
+	statement::VariableInit,
+	types::Integer,
+	types::Primitive,
+	Backend, Case, Class, Comment, Constructor, Enum, Field, Function, FunctionBuilder, GenError,
+	Ident, Import, Interface, Method, NameKind, Parameter, Receiver, Scope, Signature, Struct,
+	ToCode, Visibility,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct CPP;
+
+/// C++ adds its own keywords to those of C.
+const KEYWORDS: &[&str] = &[
+	"auto",
+	"bool",
+	"break",
+	"case",
+	"char",
+	"const",
+	"continue",
+	"default",
+	"do",
+	"double",
+	"else",
+	"enum",
+	"extern",
+	"false",
+	"float",
+	"for",
+	"goto",
+	"if",
+	"inline",
+	"int",
+	"long",
+	"register",
+	"restrict",
+	"return",
+	"short",
+	"signed",
+	"sizeof",
+	"static",
+	"struct",
+	"switch",
+	"true",
+	"typedef",
+	"union",
+	"unsigned",
+	"void",
+	"volatile",
+	"while",
+	"alignas",
+	"alignof",
+	"and",
+	"and_eq",
+	"asm",
+	"bitand",
+	"bitor",
+	"catch",
+	"char8_t",
+	"char16_t",
+	"char32_t",
+	"class",
+	"compl",
+	"concept",
+	"const_cast",
+	"consteval",
+	"constexpr",
+	"constinit",
+	"co_await",
+	"co_return",
+	"co_yield",
+	"decltype",
+	"delete",
+	"dynamic_cast",
+	"explicit",
+	"export",
+	"friend",
+	"mutable",
+	"namespace",
+	"new",
+	"noexcept",
+	"not",
+	"not_eq",
+	"nullptr",
+	"operator",
+	"or",
+	"or_eq",
+	"private",
+	"protected",
+	"public",
+	"reinterpret_cast",
+	"requires",
+	"static_assert",
+	"static_cast",
+	"template",
+	"this",
+	"thread_local",
+	"throw",
+	"try",
+	"typeid",
+	"typename",
+	"using",
+	"virtual",
+	"wchar_t",
+	"xor",
+	"xor_eq",
+];
+
+/// `members` with an access specifier wherever their visibility changes,
+/// starting from the default `visibility` of a `struct` or `class`.
+fn sections(
+	mut visibility: Visibility,
+	members: Vec<(Visibility, String)>,
+	cx: &mut Context,
+) -> Result<Vec<String>, GenError> {
+	let mut code = Vec::new();
+	for (member_visibility, member) in members {
+		if member_visibility != visibility {
+			visibility = member_visibility;
+			code.push(format!("{}:", visibility.render(cx)?));
+		}
+		code.push(member);
+	}
+	Ok(code)
+}
+
+/// `object` in front of a member, which is reached through `->` when it is
+/// the `this` pointer.
+fn member(object: &Expr, cx: &mut Context) -> Result<String, GenError> {
+	Ok(match object {
+		Expr::This => String::from("this->"),
+		_ => format!("{}.", receiver(object, cx)?),
+	})
+}
+
+/// `typ name(params)`, followed by `const` if the instance is only read.
+fn signature(
+	name: &Ident,
+	receiver: Receiver,
+	params: &[Parameter],
+	return_type: Option<&Type>,
+	cx: &mut Context,
+) -> Result<String, GenError> {
+	Ok(format!(
+		"{} {}({}){}",
+		match return_type {
+			Some(typ) => typ.render(cx)?,
+			None => String::from("void"),
+		},
+		name.render(cx)?,
+		cx.list(params)?,
+		match receiver {
+			Receiver::Shared => "const",
+			_ => "",
+		}
+	))
+}
+
+impl Backend for CPP {
+	fn name(&self) -> &'static str {
+		"C++"
+	}
+
+	fn extension(&self) -> &'static str {
+		"cpp"
+	}
+
+	fn keywords(&self) -> &'static [&'static str] {
+		KEYWORDS
+	}
+
+	fn naming_convention(&self, _kind: NameKind) -> Option<Case> {
+		Some(Case::Snake)
+	}
+
+	fn escape_keyword(&self, name: &str) -> String {
+		format!("_{}", name)
+	}
+
+	fn finish(&self, code: String, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}{}",
+			cx.requirements()
+				.iter()
+				.map(|itm| format!("#include <{}>\n", itm))
+				.join(""),
+			code
+		))
+	}
+
+	fn scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		let children = cx.children(scope)?;
+		Ok(cx.statements(children))
+	}
+
+	fn run_scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		FunctionBuilder::new()
+			.name("main")
+			.with_return_type(Integer)
+			.with_scope(scope.clone().with(Return(Some(1))))
+			.build()
+			.unwrap()
+			.render(cx)
+	}
+
+	fn function(&self, function: &Function, cx: &mut Context) -> Result<String, GenError> {
+		let params = cx.list(&function.params)?;
+		let body = function.scope.render(cx)?;
+		Ok(format!(
+			"{} {}({}){}",
+			match function.return_type {
+				Some(ref typ) => typ.render(cx)?,
+				None => String::from("void"),
+			},
+			function.name.render(cx)?,
+			params,
+			cx.braces(&body)
+		))
+	}
+
+	fn parameter(&self, parameter: &Parameter, cx: &mut Context) -> Result<String, GenError> {
+		let Parameter(name, typ) = parameter;
+		match typ {
+			Some(typ) => Ok(format!("{} {}", typ.render(cx)?, name.render(cx)?)),
+			None => Err(cx.missing_type("parameter")),
+		}
+	}
+
+	/// The access specifier of a section of a class.
+	fn visibility(&self, visibility: Visibility, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match visibility {
+			Visibility::Public => "public",
+			Visibility::Private => "private",
+		}))
+	}
+
+	fn structure(&self, structure: &Struct, cx: &mut Context) -> Result<String, GenError> {
+		let mut fields = Vec::new();
+		for field in &structure.fields {
+			fields.push((field.visibility, format!("{};", field.render(cx)?)));
+		}
+		let members = sections(Visibility::Public, fields, cx)?;
+		Ok(format!(
+			"struct {}{};",
+			cx.type_name(structure.name.as_str())?,
+			cx.braces(&cx.statements(members))
+		))
+	}
+
+	fn field(&self, field: &Field, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{} {}",
+			field.typ.render(cx)?,
+			cx.field_name(field.name.as_str())?
+		))
+	}
+
+	fn class(&self, class: &Class, cx: &mut Context) -> Result<String, GenError> {
+		let mut members = class.members(cx)?;
+		for (_, field) in members.iter_mut().take(class.fields.len()) {
+			field.push(';');
+		}
+		let members = sections(Visibility::Private, members, cx)?;
+		let name = cx.type_name(class.name.as_str())?;
+		let mut interfaces = Vec::new();
+		for interface in &class.interfaces {
+			interfaces.push(format!("public {}", cx.type_name(interface.as_str())?));
+		}
+		Ok(format!(
+			"class {}{}{};",
+			name,
+			if interfaces.is_empty() {
+				String::new()
+			} else {
+				format!(":{}", cx.list(&interfaces)?)
+			},
+			cx.braces(&cx.statements(members))
+		))
+	}
+
+	/// Fields are set by a member initializer list.
+	fn constructor(
+		&self,
+		class: &Class,
+		constructor: &Constructor,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let params = cx.list(&constructor.params)?;
+		let mut fields = Vec::new();
+		for (field, value) in &constructor.fields {
+			fields.push(format!(
+				"{}({})",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}({}){}{{}}",
+			cx.type_name(class.name.as_str())?,
+			params,
+			if fields.is_empty() {
+				String::new()
+			} else {
+				format!(":{}", cx.list(&fields)?)
+			}
+		))
+	}
+
+	/// Methods that only read their instance are `const`, and those that
+	/// implement an interface are marked as `override`.
+	fn method(&self, method: &Method, cx: &mut Context) -> Result<String, GenError> {
+		let function = &method.function;
+		let mut signature = signature(
+			&function.name,
+			method.receiver,
+			&function.params,
+			function.return_type.as_ref(),
+			cx,
+		)?;
+		if cx.implementing(function.name.as_str()).is_some() {
+			if method.receiver == Receiver::Shared {
+				signature.push(' ');
+			}
+			signature.push_str("override");
+		}
+		let body = function.scope.render(cx)?;
+		Ok(format!(
+			"{}{}{}",
+			match method.receiver {
+				Receiver::Static => "static ",
+				_ => "",
+			},
+			signature,
+			cx.braces(&body)
+		))
+	}
+
+	/// A class with only pure virtual methods and a virtual destructor, so
+	/// that implementations can be deleted through it.
+	fn interface(&self, interface: &Interface, cx: &mut Context) -> Result<String, GenError> {
+		let name = cx.type_name(interface.name.as_str())?;
+		let mut members = vec![format!("virtual ~{}()=default;", name)];
+		for method in &interface.methods {
+			members.push(method.render(cx)?);
+		}
+		Ok(format!(
+			"class {}{};",
+			name,
+			cx.braces(&format!("public:{}", cx.statements(members)))
+		))
+	}
+
+	fn signature(&self, signature: &Signature, cx: &mut Context) -> Result<String, GenError> {
+		if signature.receiver == Receiver::Static {
+			return Err(cx.unsupported("static methods of interfaces"));
+		}
+		Ok(format!(
+			"virtual {}=0;",
+			self::signature(
+				&signature.name,
+				signature.receiver,
+				&signature.params,
+				signature.return_type.as_ref(),
+				cx,
+			)?
+		))
+	}
+
+	/// An `enum class` if it is C-like, otherwise a `std::variant` of a
+	/// `struct` for every variant. The structs are not scoped to the enum, so
+	/// variants of two enums cannot share a name.
+	fn enumeration(&self, enumeration: &Enum, cx: &mut Context) -> Result<String, GenError> {
+		let name = cx.type_name(enumeration.name.as_str())?;
+		let mut variants = Vec::new();
+		if enumeration.is_c_like() {
+			for variant in &enumeration.variants {
+				variants.push(cx.type_name(variant.name.as_str())?);
+			}
+			return Ok(format!("enum class {}{{{}}};", name, cx.list(&variants)?));
+		}
+		cx.require("variant");
+		let mut structs = Vec::new();
+		for variant in &enumeration.variants {
+			let mut fields = Vec::new();
+			for field in &variant.fields {
+				fields.push(format!("{};", field.render(cx)?));
+			}
+			let variant = variant_type(&enumeration.name, &variant.name, cx)?;
+			structs.push(format!(
+				"struct {}{};",
+				variant,
+				cx.braces(&cx.statements(fields))
+			));
+			variants.push(variant);
+		}
+		structs.push(format!(
+			"using {}=std::variant<{}>;",
+			name,
+			cx.list(&variants)?
+		));
+		Ok(cx.statements(structs))
+	}
+
+	fn conditional(&self, conditional: &Conditional, cx: &mut Context) -> Result<String, GenError> {
+		let mut branches = Vec::new();
+		for (condition, scope) in &conditional.branches {
+			let condition = condition.render(cx)?;
+			let body = scope.render(cx)?;
+			branches.push(format!("if({}){}", condition, cx.braces(&body)));
+		}
+		Ok(format!(
+			"{}{}",
+			branches.join(&cx.continuation("else ")),
+			match conditional.otherwise {
+				Some(ref last) => {
+					let body = last.render(cx)?;
+					format!("{}{}", cx.continuation("else"), cx.braces(&body))
+				}
+				None => String::new(),
+			}
+		))
+	}
+
+	/// A `switch` over an `enum class`, or an `if` chain that asks the
+	/// `std::variant` which alternative it holds.
+	fn match_block(
+		&self,
+		match_block: &Match,
+		enumeration: &Enum,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let name = cx.type_name(enumeration.name.as_str())?;
+		let subject = match_block.subject.render(cx)?;
+		if enumeration.is_c_like() {
+			let mut cases = Vec::new();
+			for arm in &match_block.arms {
+				let body = arm.scope.render(cx)?;
+				cases.push(format!(
+					"case {}::{}:{}break;",
+					name,
+					cx.type_name(arm.variant.as_str())?,
+					cx.braces(&body)
+				));
+			}
+			if let Some(ref otherwise) = match_block.otherwise {
+				let body = otherwise.render(cx)?;
+				cases.push(format!("default:{}", cx.braces(&body)));
+			}
+			return Ok(format!(
+				"switch({}){}",
+				subject,
+				cx.braces(&cx.statements(cases))
+			));
+		}
+		let mut branches = Vec::new();
+		for arm in &match_block.arms {
+			let variant = variant_type(&enumeration.name, &arm.variant, cx)?;
+			let mut bindings = Vec::new();
+			for binding in &arm.bindings {
+				bindings.push(format!(
+					"auto {}=std::get<{}>({}).{};",
+					binding.render(cx)?,
+					variant,
+					subject,
+					cx.field_name(binding.as_str())?
+				));
+			}
+			let body = prepend(&arm.scope, bindings).render(cx)?;
+			branches.push(format!(
+				"if(std::holds_alternative<{}>({})){}",
+				variant,
+				subject,
+				cx.braces(&body)
+			));
+		}
+		let otherwise = match match_block.otherwise {
+			Some(ref otherwise) => Some(otherwise.render(cx)?),
+			None => None,
+		};
+		Ok(match (branches.is_empty(), otherwise) {
+			(true, Some(body)) => cx.braces(&body),
+			(false, Some(body)) => format!(
+				"{}{}{}",
+				branches.join(&cx.continuation("else ")),
+				cx.continuation("else"),
+				cx.braces(&body)
+			),
+			(_, None) => branches.join(&cx.continuation("else ")),
+		})
+	}
+
+	fn while_loop(&self, while_loop: &While, cx: &mut Context) -> Result<String, GenError> {
+		let condition = while_loop.condition.render(cx)?;
+		let body = while_loop.scope.render(cx)?;
+		Ok(format!("while({}){}", condition, cx.braces(&body)))
+	}
+
+	fn for_range(&self, for_range: &ForRange, cx: &mut Context) -> Result<String, GenError> {
+		let typ = Type::Primitive(Primitive::Integer).render(cx)?;
+		let counter = Counter::new(for_range, cx)?;
+		let body = for_range.scope.render(cx)?;
+		Ok(format!(
+			"for({} {}={};{};{}){}",
+			typ,
+			counter.var,
+			counter.start,
+			counter.condition,
+			counter.increment(),
+			cx.braces(&body)
+		))
+	}
+
+	fn for_each(&self, for_each: &ForEach, cx: &mut Context) -> Result<String, GenError> {
+		let var = for_each.var.render(cx)?;
+		let iterable = for_each.iterable.render(cx)?;
+		let body = for_each.scope.render(cx)?;
+		Ok(format!(
+			"for(auto {}:{}){}",
+			var,
+			iterable,
+			cx.braces(&body)
+		))
+	}
+
+	fn break_statement(&self, label: Option<&str>, cx: &mut Context) -> Result<String, GenError> {
+		cx.innermost_loop(label, "statement::Break")?;
+		Ok(String::from("break;"))
+	}
+
+	fn continue_statement(
+		&self,
+		label: Option<&str>,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		cx.innermost_loop(label, "statement::Continue")?;
+		Ok(String::from("continue;"))
+	}
+
+	fn variable_init(&self, init: &VariableInit, cx: &mut Context) -> Result<String, GenError> {
+		let VariableInit {
+			name, typ, value, ..
+		} = init;
+		match typ {
+			Some(typ) => Ok(format!(
+				"{} {}={};",
+				typ.render(cx)?,
+				name.render(cx)?,
+				value.render(cx)?
+			)),
+			None => Err(cx.missing_type("variable")),
+		}
+	}
+
+	fn assign_variable(
+		&self,
+		name: &Expr,
+		value: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}={};", name.render(cx)?, value.render(cx)?))
+	}
+
+	fn return_statement(&self, value: Option<&Expr>, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match value {
+			Some(expr) => format!("return {};", expr.render(cx)?),
+			None => String::from("return;"),
+		})
+	}
+
+	fn comment(&self, comment: &Comment, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match comment {
+			Comment::Regular(content) | Comment::Doc(content) => format!("//{}\n", content),
+			Comment::MultiLine(content) => format!("/*{}*/", content),
+		})
+	}
+
+	fn import(&self, import: &Import, cx: &mut Context) -> Result<String, GenError> {
+		match import {
+			Import::Module(location) => Ok(format!("#include \"{}\"\n", location)),
+			Import::Members(..) => Err(cx.unsupported("importing members")),
+		}
+	}
+
+	/// Requires the `std` namespace and the inclusion of `iostream`. `<<` binds
+	/// tighter than comparisons and the ternary operator.
+	fn println(&self, value: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		let code = value.render(cx)?;
+		Ok(match value {
+			Expr::Binary(..) | Expr::Ternary(..) => format!("cout << ({});", code),
+			_ => format!("cout << {};", code),
+		})
+	}
+
+	fn expression_statement(&self, expr: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("{};", expr.render(cx)?))
+	}
+
+	fn ternary(
+		&self,
+		condition: &Expr,
+		if_true: &Expr,
+		if_false: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}?{}:{}",
+			condition.render(cx)?,
+			if_true.render(cx)?,
+			if_false.render(cx)?
+		))
+	}
+
+	fn primitive_type(&self, primitive: Primitive, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match primitive {
+			Primitive::Integer => "int",
+			Primitive::Number | Primitive::Float => "double",
+			Primitive::Boolean => "bool",
+		}))
+	}
+
+	fn future_type(&self, _value: &Type, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("types::Future"))
+	}
+
+	/// Text that is not ASCII is a `u8` literal so that it is encoded as UTF-8.
+	fn string(&self, text: &str, _cx: &mut Context) -> Result<String, GenError> {
+		let code = escape::quote(text, '"', "", escape::octal);
+		Ok(if text.is_ascii() {
+			code
+		} else {
+			format!("u8{}", code)
+		})
+	}
+
+	fn character(&self, c: char, _cx: &mut Context) -> Result<String, GenError> {
+		let code = escape::quote(&c.to_string(), '\'', "", escape::octal);
+		Ok(if c.is_ascii() {
+			code
+		} else {
+			format!("U{}", code)
+		})
+	}
+
+	fn null(&self, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from("nullptr"))
+	}
+
+	/// Aggregate initialization, see [`Context::declared_order`].
+	fn struct_literal(
+		&self,
+		name: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let values = cx
+			.declared_order(name.as_str(), fields)
+			.into_iter()
+			.map(|(_, value)| value)
+			.collect::<Vec<_>>();
+		Ok(format!(
+			"{}{{{}}}",
+			cx.type_name(name.as_str())?,
+			cx.list(values)?
+		))
+	}
+
+	fn field_access(
+		&self,
+		object: &Expr,
+		field: &Ident,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}{}",
+			member(object, cx)?,
+			cx.field_name(field.as_str())?
+		))
+	}
+
+	fn method_call(
+		&self,
+		object: &Expr,
+		name: &Ident,
+		args: &[Expr],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}{}({})",
+			member(object, cx)?,
+			name.render(cx)?,
+			cx.list(args)?
+		))
+	}
+
+	fn static_call(
+		&self,
+		class: &Ident,
+		name: &Ident,
+		args: &[Expr],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}::{}({})",
+			cx.type_name(class.as_str())?,
+			name.render(cx)?,
+			cx.list(args)?
+		))
+	}
+
+	fn new_instance(
+		&self,
+		class: &Ident,
+		args: &[Expr],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}({})",
+			cx.type_name(class.as_str())?,
+			cx.list(args)?
+		))
+	}
+
+	/// A tagged variant is the `std::variant` constructed from the `struct`
+	/// of the variant, whose fields are given in the order they were declared.
+	fn variant(
+		&self,
+		enumeration: &Ident,
+		variant: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let name = cx.type_name(enumeration.as_str())?;
+		if c_like(enumeration, fields, cx) {
+			return Ok(format!("{}::{}", name, cx.type_name(variant.as_str())?));
+		}
+		let fields = match cx
+			.enumeration(enumeration.as_str())
+			.and_then(|itm| itm.variant(variant.as_str()))
+		{
+			Some(declared) => ordered(&declared.fields, fields),
+			None => fields.iter().collect(),
+		};
+		let values = fields
+			.into_iter()
+			.map(|(_, value)| value)
+			.collect::<Vec<_>>();
+		Ok(format!(
+			"{}{{{}{{{}}}}}",
+			name,
+			variant_type(enumeration, variant, cx)?,
+			cx.list(values)?
+		))
+	}
+}
@@ -0,0 +1,485 @@
+use itertools::Itertools;
+
+use crate::{
+	backends::{escape, Context, Counter},
+	block::{Conditional, ForEach, ForRange, While},
+	ir::{Expr, Item, Stmt, Type},
+	statement::VariableInit,
+	types::Primitive,
+	Backend, Case, Class, Comment, Constructor, Field, Function, GenError, Ident, Import, Language,
+	Method, NameKind, Parameter, Receiver, Scope, Struct, ToCode, Visibility,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct CSharp;
+
+const KEYWORDS: &[&str] = &[
+	"abstract",
+	"as",
+	"base",
+	"bool",
+	"break",
+	"byte",
+	"case",
+	"catch",
+	"char",
+	"checked",
+	"class",
+	"const",
+	"continue",
+	"decimal",
+	"default",
+	"delegate",
+	"do",
+	"double",
+	"else",
+	"enum",
+	"event",
+	"explicit",
+	"extern",
+	"false",
+	"finally",
+	"fixed",
+	"float",
+	"for",
+	"foreach",
+	"goto",
+	"if",
+	"implicit",
+	"in",
+	"int",
+	"interface",
+	"internal",
+	"is",
+	"lock",
+	"long",
+	"namespace",
+	"new",
+	"null",
+	"object",
+	"operator",
+	"out",
+	"override",
+	"params",
+	"private",
+	"protected",
+	"public",
+	"readonly",
+	"ref",
+	"return",
+	"sbyte",
+	"sealed",
+	"short",
+	"sizeof",
+	"stackalloc",
+	"static",
+	"string",
+	"struct",
+	"switch",
+	"this",
+	"throw",
+	"true",
+	"try",
+	"typeof",
+	"uint",
+	"ulong",
+	"unchecked",
+	"unsafe",
+	"ushort",
+	"using",
+	"virtual",
+	"void",
+	"volatile",
+	"while",
+];
+
+fn top_level_statements(cx: &Context) -> bool {
+	matches!(
+		cx.language(),
+		Language::CSharp {
+			top_level_statements: true
+		}
+	)
+}
+
+/// `text` escaped for the XML of a documentation comment.
+fn xml(text: &str) -> String {
+	text.replace('&', "&amp;")
+		.replace('<', "&lt;")
+		.replace('>', "&gt;")
+}
+
+impl Backend for CSharp {
+	fn name(&self) -> &'static str {
+		"C#"
+	}
+
+	fn extension(&self) -> &'static str {
+		"cs"
+	}
+
+	fn keywords(&self) -> &'static [&'static str] {
+		KEYWORDS
+	}
+
+	/// Methods are `PascalCase`.
+	fn naming_convention(&self, kind: NameKind) -> Option<Case> {
+		Some(match kind {
+			NameKind::Function | NameKind::Field => Case::Pascal,
+			NameKind::Variable => Case::Camel,
+		})
+	}
+
+	/// Verbatim identifiers.
+	fn escape_keyword(&self, name: &str) -> String {
+		format!("@{}", name)
+	}
+
+	fn finish(&self, code: String, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}{}",
+			cx.requirements()
+				.iter()
+				.map(|itm| format!("using {};", itm))
+				.join(""),
+			code
+		))
+	}
+
+	/// The children of the outermost scope are placed in a `Program` class.
+	/// With top-level statements, the entry point has to come before it and
+	/// the class extends the one the compiler generates.
+	fn scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		let children = cx.children(scope)?;
+		if cx.depth() != 1 {
+			return Ok(children.join(""));
+		}
+		let mut imports = String::new();
+		let mut statements = String::new();
+		let mut members = String::new();
+		for (child, code) in scope.children().iter().zip(children) {
+			match child {
+				Stmt::Item(Item::Import(_)) => imports.push_str(&code),
+				Stmt::Item(Item::RunScope(_)) if top_level_statements(cx) => {
+					statements.push_str(&code)
+				}
+				_ => members.push_str(&code),
+			}
+		}
+		Ok(format!(
+			"{}{}{}class Program{{{}}}",
+			imports,
+			statements,
+			if top_level_statements(cx) {
+				"partial "
+			} else {
+				""
+			},
+			members
+		))
+	}
+
+	fn run_scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		if top_level_statements(cx) {
+			return scope.render(cx);
+		}
+		Ok(format!(
+			"static void Main(string[] args){{{}}}",
+			scope.render(cx)?
+		))
+	}
+
+	/// Functions of the outermost scope are methods of `Program`, every other
+	/// function is a local function, which has no access modifiers.
+	fn function(&self, function: &Function, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}{} {}({}){{{}}}",
+			if cx.depth() > 2 {
+				String::new()
+			} else {
+				format!("{} static ", function.visibility.render(cx)?)
+			},
+			match function.return_type {
+				Some(ref typ) => typ.render(cx)?,
+				None => String::from("void"),
+			},
+			function.name.render(cx)?,
+			cx.join(&function.params, ",")?,
+			function.scope.render(cx)?
+		))
+	}
+
+	fn parameter(&self, parameter: &Parameter, cx: &mut Context) -> Result<String, GenError> {
+		let Parameter(name, typ) = parameter;
+		match typ {
+			Some(typ) => Ok(format!("{} {}", typ.render(cx)?, name.render(cx)?)),
+			None => Err(cx.missing_type("parameter")),
+		}
+	}
+
+	fn visibility(&self, visibility: Visibility, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match visibility {
+			Visibility::Public => "public",
+			Visibility::Private => "private",
+		}))
+	}
+
+	fn structure(&self, structure: &Struct, cx: &mut Context) -> Result<String, GenError> {
+		let mut fields = String::new();
+		for field in &structure.fields {
+			fields.push_str(&format!("{};", field.render(cx)?));
+		}
+		Ok(format!(
+			"{} struct {}{{{}}}",
+			structure.visibility.render(cx)?,
+			cx.type_name(structure.name.as_str())?,
+			fields
+		))
+	}
+
+	fn field(&self, field: &Field, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{} {} {}",
+			field.visibility.render(cx)?,
+			field.typ.render(cx)?,
+			cx.field_name(field.name.as_str())?
+		))
+	}
+
+	/// A class nested in the class around the program.
+	fn class(&self, class: &Class, cx: &mut Context) -> Result<String, GenError> {
+		let mut members = Vec::new();
+		for (idx, (visibility, member)) in class.members(cx)?.into_iter().enumerate() {
+			members.push(if idx < class.fields.len() {
+				format!("{};", member)
+			} else {
+				format!("{} {}", visibility.render(cx)?, member)
+			});
+		}
+		Ok(format!(
+			"{} class {}{{{}}}",
+			class.visibility.render(cx)?,
+			cx.type_name(class.name.as_str())?,
+			members.join("")
+		))
+	}
+
+	fn constructor(
+		&self,
+		class: &Class,
+		constructor: &Constructor,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let params = cx.join(&constructor.params, ",")?;
+		let mut fields = String::new();
+		for (field, value) in &constructor.fields {
+			fields.push_str(&format!(
+				"this.{}={};",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}({}){{{}}}",
+			cx.type_name(class.name.as_str())?,
+			params,
+			fields
+		))
+	}
+
+	fn method(&self, method: &Method, cx: &mut Context) -> Result<String, GenError> {
+		let function = &method.function;
+		Ok(format!(
+			"{}{} {}({}){{{}}}",
+			match method.receiver {
+				Receiver::Static => "static ",
+				_ => "",
+			},
+			match function.return_type {
+				Some(ref typ) => typ.render(cx)?,
+				None => String::from("void"),
+			},
+			function.name.render(cx)?,
+			cx.join(&function.params, ",")?,
+			function.scope.render(cx)?
+		))
+	}
+
+	fn conditional(&self, conditional: &Conditional, cx: &mut Context) -> Result<String, GenError> {
+		let mut branches = Vec::new();
+		for (condition, scope) in &conditional.branches {
+			branches.push(format!(
+				"if({}){{{}}}",
+				condition.render(cx)?,
+				scope.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}{}",
+			branches.iter().join("else "),
+			match conditional.otherwise {
+				Some(ref last) => format!("else{{{}}}", last.render(cx)?),
+				None => String::new(),
+			}
+		))
+	}
+
+	fn while_loop(&self, while_loop: &While, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"while({}){{{}}}",
+			while_loop.condition.render(cx)?,
+			while_loop.scope.render(cx)?
+		))
+	}
+
+	fn for_range(&self, for_range: &ForRange, cx: &mut Context) -> Result<String, GenError> {
+		let typ = Type::Primitive(Primitive::Integer).render(cx)?;
+		let counter = Counter::new(for_range, cx)?;
+		Ok(format!(
+			"for({} {}={};{};{}){{{}}}",
+			typ,
+			counter.var,
+			counter.start,
+			counter.condition,
+			counter.increment(),
+			for_range.scope.render(cx)?
+		))
+	}
+
+	fn for_each(&self, for_each: &ForEach, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"foreach(var {} in {}){{{}}}",
+			for_each.var.render(cx)?,
+			for_each.iterable.render(cx)?,
+			for_each.scope.render(cx)?
+		))
+	}
+
+	fn break_statement(&self, label: Option<&str>, cx: &mut Context) -> Result<String, GenError> {
+		cx.innermost_loop(label, "statement::Break")?;
+		Ok(String::from("break;"))
+	}
+
+	fn continue_statement(
+		&self,
+		label: Option<&str>,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		cx.innermost_loop(label, "statement::Continue")?;
+		Ok(String::from("continue;"))
+	}
+
+	/// Variables without a type are declared with `var`.
+	fn variable_init(&self, init: &VariableInit, cx: &mut Context) -> Result<String, GenError> {
+		let VariableInit {
+			name, typ, value, ..
+		} = init;
+		Ok(format!(
+			"{} {}={};",
+			match typ {
+				Some(typ) => typ.render(cx)?,
+				None => String::from("var"),
+			},
+			name.render(cx)?,
+			value.render(cx)?
+		))
+	}
+
+	fn assign_variable(
+		&self,
+		name: &Expr,
+		value: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}={};", name.render(cx)?, value.render(cx)?))
+	}
+
+	fn return_statement(&self, value: Option<&Expr>, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match value {
+			Some(expr) => format!("return {};", expr.render(cx)?),
+			None => String::from("return;"),
+		})
+	}
+
+	fn comment(&self, comment: &Comment, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match comment {
+			Comment::Regular(content) => format!("//{}\n", content),
+			Comment::MultiLine(content) => format!("/*{}*/", content),
+			Comment::Doc(content) => format!("/// <summary>{}</summary>\n", xml(content)),
+		})
+	}
+
+	/// Members are imported as aliases of their fully qualified names.
+	fn import(&self, import: &Import, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match import {
+			Import::Module(location) => format!("using {};", location),
+			Import::Members(location, members) => members
+				.iter()
+				.map(|member| format!("using {}={}.{};", member, location, member))
+				.join(""),
+		})
+	}
+
+	fn println(&self, value: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		cx.require("System");
+		Ok(format!("Console.WriteLine({});", value.render(cx)?))
+	}
+
+	fn expression_statement(&self, expr: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("{};", expr.render(cx)?))
+	}
+
+	fn ternary(
+		&self,
+		condition: &Expr,
+		if_true: &Expr,
+		if_false: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}?{}:{}",
+			condition.render(cx)?,
+			if_true.render(cx)?,
+			if_false.render(cx)?
+		))
+	}
+
+	fn primitive_type(&self, primitive: Primitive, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match primitive {
+			Primitive::Integer => "int",
+			Primitive::Number | Primitive::Float => "double",
+			Primitive::Boolean => "bool",
+		}))
+	}
+
+	fn future_type(&self, value: &Type, cx: &mut Context) -> Result<String, GenError> {
+		cx.require("System.Threading.Tasks");
+		self.generic_type("Task", std::slice::from_ref(value), cx)
+	}
+
+	fn character(&self, c: char, cx: &mut Context) -> Result<String, GenError> {
+		escape::utf16(c, cx)
+	}
+
+	/// An object initializer, which can only set public fields.
+	fn struct_literal(
+		&self,
+		name: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let mut values = Vec::new();
+		for (field, value) in fields {
+			values.push(format!(
+				"{}={}",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!(
+			"new {}{{{}}}",
+			cx.type_name(name.as_str())?,
+			cx.list(&values)?
+		))
+	}
+}
@@ -0,0 +1,104 @@
+//! Quoting of string and character literals.
+
+use crate::{
+	backends::{layout, Context},
+	GenError,
+};
+
+/// Wraps `text` in `delimiter`, escaping backslashes, the delimiter and every
+/// character of `special` with a backslash. Control characters other than
+/// `\n`, `\r` and `\t` are written with `control`, see [`is_control`], other
+/// characters as is.
+pub(super) fn quote(
+	text: &str,
+	delimiter: char,
+	special: &str,
+	control: fn(char) -> String,
+) -> String {
+	let mut code = String::with_capacity(text.len() + 2);
+	code.push(delimiter);
+	for c in text.chars() {
+		match c {
+			'\\' => code.push_str("\\\\"),
+			'\n' => code.push_str("\\n"),
+			'\r' => code.push_str("\\r"),
+			'\t' => code.push_str("\\t"),
+			_ if c == delimiter || special.contains(c) => {
+				code.push('\\');
+				code.push(c);
+			}
+			_ if is_control(c) => code.push_str(&control(c)),
+			_ => code.push(c),
+		}
+	}
+	code.push(delimiter);
+	code
+}
+
+/// Whether [`quote`] writes `c` with its `control` function: control
+/// characters and the layout tokens, which would otherwise be replaced with
+/// whitespace.
+pub(super) fn is_control(c: char) -> bool {
+	c.is_control() || matches!(c, layout::NEWLINE | layout::INDENT | layout::DEDENT)
+}
+
+/// `\u001b`, as in JavaScript and Java.
+pub(super) fn unicode(c: char) -> String {
+	format!("\\u{:04x}", c as u32)
+}
+
+/// `\u{1b}`, as in Rust and Swift.
+pub(super) fn braced(c: char) -> String {
+	format!("\\u{{{:x}}}", c as u32)
+}
+
+/// `\x1b`, as in Python and Go, or `\ue000` above a single byte.
+pub(super) fn hex(c: char) -> String {
+	match c as u32 {
+		0..=0xff => format!("\\x{:02x}", c as u32),
+		_ => unicode(c),
+	}
+}
+
+/// `\033`, which unlike `\x1b` cannot swallow the digits that follow it in C,
+/// or the universal character name `\ue000` above a single byte.
+pub(super) fn octal(c: char) -> String {
+	match c as u32 {
+		0..=0xff => format!("\\{:03o}", c as u32),
+		_ => unicode(c),
+	}
+}
+
+/// Replaces every character of `code` that is not ASCII with the `\u` escape
+/// of each of its UTF-16 code units.
+pub(super) fn ascii(code: &str) -> String {
+	let mut ascii = String::with_capacity(code.len());
+	for c in code.chars() {
+		if c.is_ascii() {
+			ascii.push(c);
+		} else {
+			for unit in c.encode_utf16(&mut [0; 2]) {
+				ascii.push_str(&format!("\\u{:04x}", unit));
+			}
+		}
+	}
+	ascii
+}
+
+/// A float that keeps its fractional part, e.g. `1.0` instead of `1`.
+pub(super) fn float(value: f64, cx: &Context) -> Result<String, GenError> {
+	if value.is_finite() {
+		Ok(format!("{:?}", value))
+	} else {
+		Err(cx.unsupported("non-finite floats"))
+	}
+}
+
+/// A character in languages where characters are UTF-16 code units.
+pub(super) fn utf16(c: char, cx: &Context) -> Result<String, GenError> {
+	if c.len_utf16() == 1 {
+		Ok(quote(&c.to_string(), '\'', "", unicode))
+	} else {
+		Err(cx.unsupported("characters outside of the Basic Multilingual Plane"))
+	}
+}
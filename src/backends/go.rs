@@ -0,0 +1,463 @@
+use itertools::Itertools;
+
+use crate::{
+	backends::{escape, receiver, Context, Counter},
+	block::{Conditional, ForEach, ForRange, While},
+	ir::{Associativity, Expr, Item, Operator, Stmt, Type},
+	statement::VariableInit,
+	types::Primitive,
+	Backend, Case, Comment, Field, Function, GenError, Ident, Import, NameKind, Parameter, Scope,
+	Struct, ToCode, Visibility,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Go;
+
+/// The package of code without an entry point.
+const PACKAGE: &str = "lib";
+
+const KEYWORDS: &[&str] = &[
+	"break",
+	"case",
+	"chan",
+	"const",
+	"continue",
+	"default",
+	"defer",
+	"else",
+	"fallthrough",
+	"for",
+	"func",
+	"go",
+	"goto",
+	"if",
+	"import",
+	"interface",
+	"map",
+	"package",
+	"range",
+	"return",
+	"select",
+	"struct",
+	"switch",
+	"type",
+	"var",
+];
+
+/// `name` capitalized when it is public and lowercased when it is private,
+/// which is how Go exports names.
+fn exported(name: &str, visibility: Visibility) -> String {
+	let mut chars = name.chars();
+	match chars.next() {
+		Some(first) => match visibility {
+			Visibility::Public => first.to_uppercase().chain(chars).collect(),
+			Visibility::Private => first.to_lowercase().chain(chars).collect(),
+		},
+		None => String::new(),
+	}
+}
+
+/// The name Go exports `function` under.
+fn exported_name(function: &Function, cx: &mut Context) -> Result<String, GenError> {
+	let name = exported(&cx.convention(function.name.as_str()), function.visibility);
+	cx.reserve(function.name.as_str(), name)
+}
+
+/// The field `name` of the struct `structure`, exported if it is public.
+/// Fields of structs that have not been rendered are written as they are.
+fn field_name(structure: &str, name: &str, cx: &mut Context) -> Result<String, GenError> {
+	let visibility = cx
+		.structure(structure)
+		.and_then(|itm| itm.field(name))
+		.map(|itm| itm.visibility);
+	let name = cx.field_name(name)?;
+	Ok(match visibility {
+		Some(visibility) => exported(&name, visibility),
+		None => name,
+	})
+}
+
+/// The `label:` in front of a loop whose body is `scope`. Go rejects labels
+/// that are never used, so it is left out unless a `break` or `continue`
+/// refers to it.
+fn label(label: Option<&str>, scope: &Scope) -> String {
+	match label {
+		Some(label) if referenced(label, scope) => format!("{}:", label),
+		_ => String::new(),
+	}
+}
+
+/// Whether a `break` or `continue` in `scope` refers to the loop `label`.
+/// Nested loops with the same label shadow it, and nested functions cannot
+/// refer to it at all.
+fn referenced(label: &str, scope: &Scope) -> bool {
+	scope.children().iter().any(|child| match child {
+		Stmt::Break(Some(target)) | Stmt::Continue(Some(target)) => target == label,
+		Stmt::Conditional(conditional) => {
+			conditional
+				.branches
+				.iter()
+				.any(|(_, scope)| referenced(label, scope))
+				|| conditional
+					.otherwise
+					.as_ref()
+					.is_some_and(|scope| referenced(label, scope))
+		}
+		Stmt::While(While {
+			label: inner,
+			scope,
+			..
+		})
+		| Stmt::ForRange(ForRange {
+			label: inner,
+			scope,
+			..
+		})
+		| Stmt::ForEach(ForEach {
+			label: inner,
+			scope,
+			..
+		}) => inner.as_deref() != Some(label) && referenced(label, scope),
+		Stmt::Match(match_block) => {
+			match_block
+				.arms
+				.iter()
+				.any(|arm| referenced(label, &arm.scope))
+				|| match_block
+					.otherwise
+					.as_ref()
+					.is_some_and(|scope| referenced(label, scope))
+		}
+		_ => false,
+	})
+}
+
+impl Backend for Go {
+	fn name(&self) -> &'static str {
+		"Go"
+	}
+
+	fn extension(&self) -> &'static str {
+		"go"
+	}
+
+	fn trailing_commas(&self) -> bool {
+		true
+	}
+
+	fn keywords(&self) -> &'static [&'static str] {
+		KEYWORDS
+	}
+
+	/// Public functions are then capitalized to be exported.
+	fn naming_convention(&self, _kind: NameKind) -> Option<Case> {
+		Some(Case::Camel)
+	}
+
+	/// Programs are in `package main`, everything else in [`PACKAGE`].
+	fn finish(&self, code: String, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"package {}\n{}{}",
+			if cx.has_entry_point() {
+				"main"
+			} else {
+				PACKAGE
+			},
+			cx.requirements()
+				.iter()
+				.map(|itm| format!("import \"{}\"\n", itm))
+				.join(""),
+			code
+		))
+	}
+
+	fn scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		// Functions may be called before they are declared, so rename them all
+		// up front.
+		for child in scope.children() {
+			if let Stmt::Item(Item::Function(function)) = child {
+				let name = exported_name(function, cx)?;
+				cx.rename(function.name.as_str(), name);
+			}
+		}
+		Ok(cx.children(scope)?.join(""))
+	}
+
+	fn run_scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("func main(){{{}}}\n", scope.render(cx)?))
+	}
+
+	fn function(&self, function: &Function, cx: &mut Context) -> Result<String, GenError> {
+		let name = exported_name(function, cx)?;
+		cx.rename(function.name.as_str(), &name);
+		Ok(format!(
+			"func {}({}){}{{{}}}\n",
+			name,
+			cx.join(&function.params, ",")?,
+			match function.return_type {
+				Some(ref typ) => format!(" {}", typ.render(cx)?),
+				None => String::new(),
+			},
+			function.scope.render(cx)?
+		))
+	}
+
+	fn parameter(&self, parameter: &Parameter, cx: &mut Context) -> Result<String, GenError> {
+		let Parameter(name, typ) = parameter;
+		match typ {
+			Some(typ) => Ok(format!("{} {}", name.render(cx)?, typ.render(cx)?)),
+			None => Err(cx.missing_type("parameter")),
+		}
+	}
+
+	/// Go exports names by capitalizing them instead.
+	fn visibility(&self, _visibility: Visibility, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::new())
+	}
+
+	fn structure(&self, structure: &Struct, cx: &mut Context) -> Result<String, GenError> {
+		let name = exported(structure.name.as_str(), structure.visibility);
+		let name = cx.reserve(structure.name.as_str(), name)?;
+		cx.rename(structure.name.as_str(), &name);
+		Ok(format!(
+			"type {} struct{{{}}}\n",
+			name,
+			cx.join(&structure.fields, ";")?
+		))
+	}
+
+	fn field(&self, field: &Field, cx: &mut Context) -> Result<String, GenError> {
+		let name = exported(&cx.field_name(field.name.as_str())?, field.visibility);
+		Ok(format!("{} {}", name, field.typ.render(cx)?))
+	}
+
+	fn conditional(&self, conditional: &Conditional, cx: &mut Context) -> Result<String, GenError> {
+		let mut branches = Vec::new();
+		for (condition, scope) in &conditional.branches {
+			branches.push(format!(
+				"if {}{{{}}}",
+				condition.render(cx)?,
+				scope.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}{};",
+			branches.iter().join("else "),
+			match conditional.otherwise {
+				Some(ref last) => format!("else{{{}}}", last.render(cx)?),
+				None => String::new(),
+			}
+		))
+	}
+
+	/// A `for` with only a condition.
+	fn while_loop(&self, while_loop: &While, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}for {}{{{}}};",
+			label(while_loop.label.as_deref(), &while_loop.scope),
+			while_loop.condition.render(cx)?,
+			while_loop.scope.render(cx)?
+		))
+	}
+
+	fn for_range(&self, for_range: &ForRange, cx: &mut Context) -> Result<String, GenError> {
+		let counter = Counter::new(for_range, cx)?;
+		Ok(format!(
+			"{}for {}:={};{};{}{{{}}};",
+			label(for_range.label.as_deref(), &for_range.scope),
+			counter.var,
+			counter.start,
+			counter.condition,
+			counter.increment(),
+			for_range.scope.render(cx)?
+		))
+	}
+
+	fn for_each(&self, for_each: &ForEach, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}for _,{}:=range {}{{{}}};",
+			label(for_each.label.as_deref(), &for_each.scope),
+			for_each.var.render(cx)?,
+			for_each.iterable.render(cx)?,
+			for_each.scope.render(cx)?
+		))
+	}
+
+	fn break_statement(&self, label: Option<&str>, cx: &mut Context) -> Result<String, GenError> {
+		cx.loop_target(label, "statement::Break")?;
+		Ok(match label {
+			Some(label) => format!("break {};", label),
+			None => String::from("break;"),
+		})
+	}
+
+	fn continue_statement(
+		&self,
+		label: Option<&str>,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		cx.loop_target(label, "statement::Continue")?;
+		Ok(match label {
+			Some(label) => format!("continue {};", label),
+			None => String::from("continue;"),
+		})
+	}
+
+	fn variable_init(&self, init: &VariableInit, cx: &mut Context) -> Result<String, GenError> {
+		let VariableInit {
+			name, typ, value, ..
+		} = init;
+		Ok(match typ {
+			Some(typ) => format!(
+				"var {} {}={};",
+				name.render(cx)?,
+				typ.render(cx)?,
+				value.render(cx)?
+			),
+			None => format!("{}:={};", name.render(cx)?, value.render(cx)?),
+		})
+	}
+
+	fn assign_variable(
+		&self,
+		name: &Expr,
+		value: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}={};", name.render(cx)?, value.render(cx)?))
+	}
+
+	fn return_statement(&self, value: Option<&Expr>, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match value {
+			Some(expr) => format!("return {};", expr.render(cx)?),
+			None => String::from("return;"),
+		})
+	}
+
+	fn comment(&self, comment: &Comment, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match comment {
+			Comment::Regular(content) | Comment::Doc(content) => format!("//{}\n", content),
+			Comment::MultiLine(content) => format!("/*{}*/", content),
+		})
+	}
+
+	/// Go only allows imports before any declaration, and only once per
+	/// package, so they are emitted by [`Backend::finish`].
+	fn import(&self, import: &Import, cx: &mut Context) -> Result<String, GenError> {
+		match import {
+			Import::Module(location) => {
+				cx.require(location.as_str());
+				Ok(String::new())
+			}
+			Import::Members(..) => Err(cx.unsupported("importing members")),
+		}
+	}
+
+	fn println(&self, value: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		cx.require("fmt");
+		Ok(format!("fmt.Println({});", value.render(cx)?))
+	}
+
+	fn expression_statement(&self, expr: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("{};", expr.render(cx)?))
+	}
+
+	/// Go has no conditional expression, so this is lowered to an immediately
+	/// invoked function literal, which needs the type of the result.
+	fn ternary(
+		&self,
+		condition: &Expr,
+		if_true: &Expr,
+		if_false: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let typ = match cx.type_of(if_true).or_else(|| cx.type_of(if_false)) {
+			Some(typ) => typ,
+			None => return Err(cx.missing_type("ternary")),
+		};
+		Ok(format!(
+			"func() {}{{if {}{{return {}}};return {}}}()",
+			typ.render(cx)?,
+			condition.render(cx)?,
+			if_true.render(cx)?,
+			if_false.render(cx)?
+		))
+	}
+
+	fn primitive_type(&self, primitive: Primitive, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match primitive {
+			Primitive::Integer => "int",
+			Primitive::Number | Primitive::Float => "float64",
+			Primitive::Boolean => "bool",
+		}))
+	}
+
+	fn generic_type(
+		&self,
+		name: &str,
+		args: &[Type],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}[{}]", name, cx.join(args, ",")?))
+	}
+
+	/// A channel that receives the value once it is ready.
+	fn future_type(&self, value: &Type, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("<-chan {}", value.render(cx)?))
+	}
+
+	fn precedence(&self, operator: Operator) -> (u8, Associativity) {
+		operator.flat_precedence()
+	}
+
+	fn string(&self, text: &str, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(escape::quote(text, '"', "", escape::hex))
+	}
+
+	/// A rune.
+	fn character(&self, c: char, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(escape::quote(&c.to_string(), '\'', "", escape::hex))
+	}
+
+	fn null(&self, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from("nil"))
+	}
+
+	fn struct_literal(
+		&self,
+		name: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let mut values = Vec::new();
+		for (field, value) in fields {
+			values.push(format!(
+				"{}:{}",
+				field_name(name.as_str(), field.as_str(), cx)?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}{{{}}}",
+			cx.type_name(name.as_str())?,
+			cx.list(&values)?
+		))
+	}
+
+	fn field_access(
+		&self,
+		object: &Expr,
+		field: &Ident,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let structure = match cx.type_of(object) {
+			Some(Type::Named(name)) => name,
+			_ => String::new(),
+		};
+		Ok(format!(
+			"{}.{}",
+			receiver(object, cx)?,
+			field_name(&structure, field.as_str(), cx)?
+		))
+	}
+}
@@ -0,0 +1,572 @@
+use itertools::Itertools;
+
+use crate::{
+	backends::{escape, offset, Context},
+	block::{Conditional, ForEach, ForRange, While},
+	ir::{Associativity, Expr, Operator, Stmt, Type},
+	statement::VariableInit,
+	types::Primitive,
+	Backend, Case, Comment, Field, Function, GenError, Ident, Import, Literal, NameKind, Parameter,
+	Scope, Struct, ToCode, Value, Visibility,
+};
+
+/// The pure subset of Haskell, where every function body is a single
+/// expression.
+#[derive(Debug, Clone, Copy)]
+pub struct Haskell;
+
+const KEYWORDS: &[&str] = &[
+	"case", "class", "data", "default", "deriving", "do", "else", "foreign", "if", "import", "in",
+	"infix", "infixl", "infixr", "instance", "let", "module", "newtype", "of", "then", "type",
+	"where",
+];
+
+/// Lowers `stmts[start..]` into an expression. Statements that fall through
+/// continue with `fallthrough`, which is how code after a conditional ends up
+/// in each of its branches.
+fn expression(
+	stmts: &[Stmt],
+	start: usize,
+	fallthrough: Option<&str>,
+	cx: &mut Context,
+) -> Result<String, GenError> {
+	let stmt = match stmts.get(start) {
+		Some(stmt) => stmt,
+		None => {
+			return match fallthrough {
+				Some(code) => Ok(code.to_owned()),
+				None => Err(cx.unsupported("a function without a final statement::Return")),
+			}
+		}
+	};
+	let segment = format!("[{}]", start);
+	match stmt {
+		Stmt::Return(Some(value)) => {
+			cx.within(segment, |cx| cx.within("return", |cx| value.render(cx)))
+		}
+		Stmt::VariableInit(init) => {
+			let binding = cx.within(segment, |cx| init.render(cx))?;
+			Ok(format!(
+				"{} in {}",
+				binding,
+				expression(stmts, start + 1, fallthrough, cx)?
+			))
+		}
+		Stmt::Conditional(conditional) => {
+			let rest = if start + 1 < stmts.len() {
+				Some(expression(stmts, start + 1, fallthrough, cx)?)
+			} else {
+				fallthrough.map(str::to_owned)
+			};
+			cx.within(segment, |cx| {
+				cx.within("if", |cx| {
+					let mut code = String::new();
+					for (condition, scope) in &conditional.branches {
+						code.push_str(&format!(
+							"if {} then {} else ",
+							condition.render(cx)?,
+							expression(scope.children(), 0, rest.as_deref(), cx)?
+						));
+					}
+					code.push_str(&match (&conditional.otherwise, rest) {
+						(Some(last), rest) => expression(last.children(), 0, rest.as_deref(), cx)?,
+						(None, Some(rest)) => rest,
+						(None, None) => {
+							return Err(
+								cx.unsupported("a function without a final statement::Return")
+							)
+						}
+					});
+					Ok(code)
+				})
+			})
+		}
+		Stmt::Comment(comment) => {
+			let comment = cx.within(segment, |cx| comment.render(cx))?;
+			Ok(format!(
+				"{}{}",
+				comment,
+				expression(stmts, start + 1, fallthrough, cx)?
+			))
+		}
+		Stmt::Assign(..) => cx.within(segment, |cx| {
+			Err(cx.unsupported("statement::AssignVariable"))
+		}),
+		Stmt::Println(_) => cx.within(segment, |cx| {
+			Err(cx.unsupported("print::Println outside of RunScope"))
+		}),
+		Stmt::While(_)
+		| Stmt::ForRange(_)
+		| Stmt::ForEach(_)
+		| Stmt::Break(_)
+		| Stmt::Continue(_) => cx.within(segment, |cx| {
+			Err(cx.unsupported("loops outside of RunScope"))
+		}),
+		Stmt::Match(_) => cx.within(segment, |cx| Err(cx.unsupported("block::Match"))),
+		Stmt::Return(None) => cx.within(segment, |cx| {
+			Err(cx.unsupported("statement::Return without a value"))
+		}),
+		Stmt::Expr(_) | Stmt::Item(_) | Stmt::Raw(_) => cx.within(segment, |cx| {
+			Err(cx.unsupported("statements without a result"))
+		}),
+	}
+}
+
+/// `name :: A -> B -> R`, when every type is known.
+fn signature(function: &Function, cx: &mut Context) -> Result<String, GenError> {
+	let mut types = Vec::new();
+	for Parameter(_, typ) in &function.params {
+		match typ {
+			Some(typ) => types.push(typ.render(cx)?),
+			None => return Ok(String::new()),
+		}
+	}
+	match function.return_type {
+		Some(ref typ) => types.push(typ.render(cx)?),
+		None => return Ok(String::new()),
+	}
+	Ok(format!(
+		"{} :: {}\n",
+		function.name.render(cx)?,
+		types.join(" -> ")
+	))
+}
+
+/// The statements of a `do` block.
+fn actions(scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+	let children = cx.children(scope)?;
+	Ok(if children.is_empty() {
+		String::from("return ()")
+	} else {
+		format!("do {{{}}}", children.join("; "))
+	})
+}
+
+/// A decimal escape, followed by the empty escape `\&` so that it does not
+/// swallow the digits after it.
+fn control(c: char) -> String {
+	format!("\\{}\\&", c as u32)
+}
+
+/// An argument of a function application, which binds tighter than any
+/// operator.
+fn argument(arg: &Expr, cx: &mut Context) -> Result<String, GenError> {
+	let code = arg.render(cx)?;
+	Ok(match arg {
+		Expr::Binary(..) | Expr::Ternary(..) => format!("({})", code),
+		Expr::Call(_, args) if !args.is_empty() => format!("({})", code),
+		Expr::Field(..) => format!("({})", code),
+		Expr::Int(value) | Expr::Literal(Literal::Int { value, .. }) if *value < 0 => {
+			format!("({})", code)
+		}
+		Expr::Literal(Literal::Float(value)) if value.is_sign_negative() => format!("({})", code),
+		_ => code,
+	})
+}
+
+/// The classes that `derives` can be derived as in Haskell.
+fn deriving(derives: &[String]) -> Vec<&'static str> {
+	let mut classes = Vec::new();
+	for derive in derives {
+		let class = match derive.as_str() {
+			"Debug" => "Show",
+			"PartialEq" | "Eq" => "Eq",
+			"PartialOrd" | "Ord" => "Ord",
+			_ => continue,
+		};
+		if !classes.contains(&class) {
+			classes.push(class);
+		}
+	}
+	classes
+}
+
+impl Backend for Haskell {
+	fn name(&self) -> &'static str {
+		"Haskell"
+	}
+
+	fn extension(&self) -> &'static str {
+		"hs"
+	}
+
+	fn keywords(&self) -> &'static [&'static str] {
+		KEYWORDS
+	}
+
+	fn naming_convention(&self, _kind: NameKind) -> Option<Case> {
+		Some(Case::Camel)
+	}
+
+	fn escape_keyword(&self, name: &str) -> String {
+		format!("{}'", name)
+	}
+
+	fn scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		Ok(cx.children(scope)?.join(""))
+	}
+
+	fn run_scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("main :: IO ()\nmain = {}\n", actions(scope, cx)?))
+	}
+
+	fn function(&self, function: &Function, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}{} = {}\n",
+			signature(function, cx)?,
+			std::iter::once(function.name.render(cx)?)
+				.chain(
+					function
+						.params
+						.iter()
+						.map(|param| param.render(cx))
+						.collect::<Result<Vec<_>, _>>()?
+				)
+				.join(" "),
+			expression(function.scope.children(), 0, None, cx)?
+		))
+	}
+
+	fn parameter(&self, parameter: &Parameter, cx: &mut Context) -> Result<String, GenError> {
+		parameter.0.render(cx)
+	}
+
+	/// Modules export every top-level name unless they list them.
+	fn visibility(&self, _visibility: Visibility, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::new())
+	}
+
+	/// A record, which derives the classes that have an equivalent in
+	/// [`Struct::derives`]. Haskell has no private fields.
+	fn structure(&self, structure: &Struct, cx: &mut Context) -> Result<String, GenError> {
+		let name = cx.type_name(structure.name.as_str())?;
+		let classes = deriving(&structure.derives);
+		Ok(format!(
+			"data {} = {}{}{}\n",
+			name,
+			name,
+			if structure.fields.is_empty() {
+				String::new()
+			} else {
+				format!(" {{{}}}", cx.join(&structure.fields, ", ")?)
+			},
+			if classes.is_empty() {
+				String::new()
+			} else {
+				format!(" deriving ({})", classes.join(", "))
+			}
+		))
+	}
+
+	fn field(&self, field: &Field, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{} :: {}",
+			cx.field_name(field.name.as_str())?,
+			field.typ.render(cx)?
+		))
+	}
+
+	/// A conditional inside of a `do` block, which needs an `else`.
+	fn conditional(&self, conditional: &Conditional, cx: &mut Context) -> Result<String, GenError> {
+		let mut code = String::new();
+		for (condition, scope) in &conditional.branches {
+			code.push_str(&format!(
+				"if {} then {} else ",
+				condition.render(cx)?,
+				actions(scope, cx)?
+			));
+		}
+		code.push_str(&match conditional.otherwise {
+			Some(ref last) => actions(last, cx)?,
+			None => String::from("return ()"),
+		});
+		Ok(code)
+	}
+
+	/// A loop needs mutable state.
+	fn while_loop(&self, _while_loop: &While, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("block::While"))
+	}
+
+	/// `mapM_` over an arithmetic sequence, whose end is inclusive.
+	fn for_range(&self, for_range: &ForRange, cx: &mut Context) -> Result<String, GenError> {
+		if cx.depth() <= 1 {
+			return Err(cx.unsupported("loops outside of RunScope"));
+		}
+		if for_range.label.is_some() {
+			return Err(cx.unsupported("labeled loops"));
+		}
+		let start = for_range.start.render(cx)?;
+		let last = offset(&for_range.end, -for_range.step.signum());
+		let then = match for_range.step {
+			1 => String::new(),
+			step => format!(",{}", offset(&for_range.start, step).render(cx)?),
+		};
+		Ok(format!(
+			"mapM_ (\\{} -> {}) [{}{}..{}]",
+			for_range.var.render(cx)?,
+			actions(&for_range.scope, cx)?,
+			start,
+			then,
+			last.render(cx)?
+		))
+	}
+
+	fn for_each(&self, for_each: &ForEach, cx: &mut Context) -> Result<String, GenError> {
+		if cx.depth() <= 1 {
+			return Err(cx.unsupported("loops outside of RunScope"));
+		}
+		if for_each.label.is_some() {
+			return Err(cx.unsupported("labeled loops"));
+		}
+		Ok(format!(
+			"mapM_ (\\{} -> {}) {}",
+			for_each.var.render(cx)?,
+			actions(&for_each.scope, cx)?,
+			argument(&for_each.iterable, cx)?
+		))
+	}
+
+	/// A top-level binding, or a `let` that starts a `let ... in` expression or
+	/// binds inside of a `do` block.
+	fn variable_init(&self, init: &VariableInit, cx: &mut Context) -> Result<String, GenError> {
+		let VariableInit {
+			name,
+			mutable,
+			typ,
+			value,
+		} = init;
+		if *mutable == Some(true) {
+			return Err(cx.unsupported("mutable variables"));
+		}
+		let name = name.render(cx)?;
+		let binding = format!("{} = {}", name, value.render(cx)?);
+		Ok(match typ {
+			Some(typ) if cx.depth() <= 1 => {
+				format!("{} :: {}\n{}\n", name, typ.render(cx)?, binding)
+			}
+			None if cx.depth() <= 1 => format!("{}\n", binding),
+			_ => format!("let {{{}}}", binding),
+		})
+	}
+
+	fn assign_variable(
+		&self,
+		_name: &Expr,
+		_value: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Err(cx.unsupported("statement::AssignVariable"))
+	}
+
+	fn return_statement(
+		&self,
+		_value: Option<&Expr>,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Err(cx.unsupported("statement::Return in RunScope"))
+	}
+
+	/// Comments inside of expressions are block comments so that they do not
+	/// swallow the rest of the line.
+	fn comment(&self, comment: &Comment, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match comment {
+			Comment::Regular(content) if cx.depth() <= 1 => format!("--{}\n", content),
+			Comment::Doc(content) if cx.depth() <= 1 => format!("-- |{}\n", content),
+			Comment::Regular(content) | Comment::MultiLine(content) | Comment::Doc(content) => {
+				format!("{{-{}-}} ", content)
+			}
+		})
+	}
+
+	fn import(&self, import: &Import, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match import {
+			Import::Module(location) => format!("import {}\n", location),
+			Import::Members(location, members) => {
+				format!("import {} ({})\n", location, members.iter().join(","))
+			}
+		})
+	}
+
+	fn println(&self, value: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		if cx.depth() <= 1 {
+			return Err(cx.unsupported("print::Println outside of RunScope"));
+		}
+		Ok(format!("print {}", argument(value, cx)?))
+	}
+
+	fn expression_statement(&self, expr: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		if cx.depth() <= 1 {
+			return Err(cx.unsupported("statements without a result"));
+		}
+		expr.render(cx)
+	}
+
+	fn ternary(
+		&self,
+		condition: &Expr,
+		if_true: &Expr,
+		if_false: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"if {} then {} else {}",
+			condition.render(cx)?,
+			if_true.render(cx)?,
+			if_false.render(cx)?
+		))
+	}
+
+	fn primitive_type(&self, primitive: Primitive, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match primitive {
+			Primitive::Integer => "Int",
+			Primitive::Number | Primitive::Float => "Double",
+			Primitive::Boolean => "Bool",
+		}))
+	}
+
+	fn generic_type(
+		&self,
+		name: &str,
+		args: &[Type],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("({} {})", name, cx.join(args, " ")?))
+	}
+
+	fn future_type(&self, value: &Type, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("(IO {})", value.render(cx)?))
+	}
+
+	/// Arguments are separated by spaces.
+	fn function_call(
+		&self,
+		name: &Expr,
+		args: &[Expr],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let mut code = name.render(cx)?;
+		for arg in args {
+			code.push(' ');
+			code.push_str(&argument(arg, cx)?);
+		}
+		Ok(code)
+	}
+
+	/// Comparisons are `infix 4`, so they cannot be chained.
+	fn precedence(&self, operator: Operator) -> (u8, Associativity) {
+		operator.flat_precedence()
+	}
+
+	fn operator(&self, operator: Operator) -> &'static str {
+		match operator {
+			Operator::NotEqual => "/=",
+			Operator::Mod => " `mod` ",
+			_ => operator.symbol(),
+		}
+	}
+
+	/// `/` only divides fractional numbers.
+	fn binary(
+		&self,
+		operator: Operator,
+		lhs: &Expr,
+		rhs: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let integer = Some(Type::Primitive(Primitive::Integer));
+		let symbol = match operator {
+			Operator::Divide if cx.type_of(lhs) == integer && cx.type_of(rhs) == integer => {
+				" `div` "
+			}
+			_ => self.operator(operator),
+		};
+		let lhs = cx.operand(operator, lhs, false)?;
+		let rhs = cx.operand(operator, rhs, true)?;
+		Ok(format!("{}{}{}", lhs, symbol, rhs))
+	}
+
+	fn value(&self, value: &Value, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match value {
+			Value::True => "True",
+			Value::False => "False",
+		}))
+	}
+
+	fn string(&self, text: &str, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(escape::quote(text, '"', "", control))
+	}
+
+	fn character(&self, c: char, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(escape::quote(&c.to_string(), '\'', "", control))
+	}
+
+	fn null(&self, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from("Nothing"))
+	}
+
+	/// Record construction syntax.
+	fn struct_literal(
+		&self,
+		name: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let mut values = Vec::new();
+		for (field, value) in fields {
+			values.push(format!(
+				"{} = {}",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{} {{{}}}",
+			cx.type_name(name.as_str())?,
+			values.join(", ")
+		))
+	}
+
+	/// Fields are read by applying their accessor function.
+	fn field_access(
+		&self,
+		object: &Expr,
+		field: &Ident,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{} {}",
+			cx.field_name(field.as_str())?,
+			argument(object, cx)?
+		))
+	}
+
+	fn method_call(
+		&self,
+		_object: &Expr,
+		_name: &Ident,
+		_args: &[Expr],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Err(cx.unsupported("methods"))
+	}
+
+	fn static_call(
+		&self,
+		_class: &Ident,
+		_name: &Ident,
+		_args: &[Expr],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Err(cx.unsupported("methods"))
+	}
+
+	fn new_instance(
+		&self,
+		_class: &Ident,
+		_args: &[Expr],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Err(cx.unsupported("ClassBuilder"))
+	}
+}
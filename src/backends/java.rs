@@ -0,0 +1,505 @@
+use itertools::Itertools;
+
+use crate::{
+	backends::{escape, label, receiver, Context, Counter},
+	block::{Conditional, ForEach, ForRange, While},
+	ir::{Expr, Item, Stmt, Type},
+	statement::VariableInit,
+	types::Primitive,
+	Backend, Case, Class, Comment, Constructor, Field, Function, GenError, Ident, Import, Language,
+	Method, NameKind, Parameter, Receiver, Scope, Struct, ToCode, Visibility,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Java;
+
+const KEYWORDS: &[&str] = &[
+	"abstract",
+	"assert",
+	"boolean",
+	"break",
+	"byte",
+	"case",
+	"catch",
+	"char",
+	"class",
+	"const",
+	"continue",
+	"default",
+	"do",
+	"double",
+	"else",
+	"enum",
+	"extends",
+	"false",
+	"final",
+	"finally",
+	"float",
+	"for",
+	"goto",
+	"if",
+	"implements",
+	"import",
+	"instanceof",
+	"int",
+	"interface",
+	"long",
+	"native",
+	"new",
+	"null",
+	"package",
+	"private",
+	"protected",
+	"public",
+	"record",
+	"return",
+	"short",
+	"static",
+	"strictfp",
+	"super",
+	"switch",
+	"synchronized",
+	"this",
+	"throw",
+	"throws",
+	"transient",
+	"true",
+	"try",
+	"var",
+	"void",
+	"volatile",
+	"while",
+	"yield",
+];
+
+/// The name of the class that the outermost scope is wrapped in.
+fn class_name(cx: &Context) -> &'static str {
+	match cx.language() {
+		Language::Java { class_name } => class_name,
+		_ => "Main",
+	}
+}
+
+/// Renders `typ` as a reference type, which is what type arguments must be.
+fn boxed(typ: &Type, cx: &mut Context) -> Result<String, GenError> {
+	match typ {
+		Type::Primitive(primitive) => Ok(String::from(match primitive {
+			Primitive::Integer => "Integer",
+			Primitive::Number | Primitive::Float => "Double",
+			Primitive::Boolean => "Boolean",
+		})),
+		_ => typ.render(cx),
+	}
+}
+
+impl Backend for Java {
+	fn name(&self) -> &'static str {
+		"Java"
+	}
+
+	fn extension(&self) -> &'static str {
+		"java"
+	}
+
+	fn keywords(&self) -> &'static [&'static str] {
+		KEYWORDS
+	}
+
+	fn naming_convention(&self, _kind: NameKind) -> Option<Case> {
+		Some(Case::Camel)
+	}
+
+	fn finish(&self, code: String, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}{}",
+			cx.requirements()
+				.iter()
+				.map(|itm| format!("import {};", itm))
+				.join(""),
+			code
+		))
+	}
+
+	/// Java has no free functions, so the children of the outermost scope are
+	/// wrapped in a class, leaving only the imports outside of it.
+	fn scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		let children = cx.children(scope)?;
+		if cx.depth() != 1 {
+			return Ok(children.join(""));
+		}
+		let (imports, members): (Vec<_>, Vec<_>) = scope
+			.children()
+			.iter()
+			.zip(children)
+			.partition(|(child, _)| matches!(child, Stmt::Item(Item::Import(_))));
+		Ok(format!(
+			"{}public class {}{{{}}}",
+			imports.into_iter().map(|(_, code)| code).join(""),
+			class_name(cx),
+			members.into_iter().map(|(_, code)| code).join("")
+		))
+	}
+
+	fn run_scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"public static void main(String[] args){{{}}}",
+			scope.render(cx)?
+		))
+	}
+
+	/// Only functions of the outermost scope become methods of the class, as
+	/// Java has no local functions.
+	fn function(&self, function: &Function, cx: &mut Context) -> Result<String, GenError> {
+		if cx.depth() > 2 {
+			return Err(cx.unsupported("nested functions"));
+		}
+		Ok(format!(
+			"{} static {} {}({}){{{}}}",
+			function.visibility.render(cx)?,
+			match function.return_type {
+				Some(ref typ) => typ.render(cx)?,
+				None => String::from("void"),
+			},
+			function.name.render(cx)?,
+			cx.join(&function.params, ",")?,
+			function.scope.render(cx)?
+		))
+	}
+
+	fn parameter(&self, parameter: &Parameter, cx: &mut Context) -> Result<String, GenError> {
+		let Parameter(name, typ) = parameter;
+		match typ {
+			Some(typ) => Ok(format!("{} {}", typ.render(cx)?, name.render(cx)?)),
+			None => Err(cx.missing_type("parameter")),
+		}
+	}
+
+	fn visibility(&self, visibility: Visibility, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match visibility {
+			Visibility::Public => "public",
+			Visibility::Private => "private",
+		}))
+	}
+
+	/// A `record`, whose components are always public.
+	fn structure(&self, structure: &Struct, cx: &mut Context) -> Result<String, GenError> {
+		if structure
+			.fields
+			.iter()
+			.any(|field| field.visibility == Visibility::Private)
+		{
+			return Err(cx.unsupported("private fields"));
+		}
+		Ok(format!(
+			"{} record {}({}){{}}",
+			structure.visibility.render(cx)?,
+			cx.type_name(structure.name.as_str())?,
+			cx.list(&structure.fields)?
+		))
+	}
+
+	fn field(&self, field: &Field, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{} {}",
+			field.typ.render(cx)?,
+			cx.field_name(field.name.as_str())?
+		))
+	}
+
+	/// A `static` nested class, which does not need an instance of the class
+	/// around it.
+	fn class(&self, class: &Class, cx: &mut Context) -> Result<String, GenError> {
+		let mut members = Vec::new();
+		for (idx, (visibility, member)) in class.members(cx)?.into_iter().enumerate() {
+			members.push(format!(
+				"{} {}{}",
+				visibility.render(cx)?,
+				member,
+				if idx < class.fields.len() { ";" } else { "" }
+			));
+		}
+		Ok(format!(
+			"{} static class {}{{{}}}",
+			class.visibility.render(cx)?,
+			cx.type_name(class.name.as_str())?,
+			members.join("")
+		))
+	}
+
+	fn constructor(
+		&self,
+		class: &Class,
+		constructor: &Constructor,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let params = cx.join(&constructor.params, ",")?;
+		let mut fields = String::new();
+		for (field, value) in &constructor.fields {
+			fields.push_str(&format!(
+				"this.{}={};",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}({}){{{}}}",
+			cx.type_name(class.name.as_str())?,
+			params,
+			fields
+		))
+	}
+
+	fn method(&self, method: &Method, cx: &mut Context) -> Result<String, GenError> {
+		let function = &method.function;
+		Ok(format!(
+			"{}{} {}({}){{{}}}",
+			match method.receiver {
+				Receiver::Static => "static ",
+				_ => "",
+			},
+			match function.return_type {
+				Some(ref typ) => typ.render(cx)?,
+				None => String::from("void"),
+			},
+			function.name.render(cx)?,
+			cx.join(&function.params, ",")?,
+			function.scope.render(cx)?
+		))
+	}
+
+	fn conditional(&self, conditional: &Conditional, cx: &mut Context) -> Result<String, GenError> {
+		let mut branches = Vec::new();
+		for (condition, scope) in &conditional.branches {
+			branches.push(format!(
+				"if({}){{{}}}",
+				condition.render(cx)?,
+				scope.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}{}",
+			branches.iter().join("else "),
+			match conditional.otherwise {
+				Some(ref last) => format!("else{{{}}}", last.render(cx)?),
+				None => String::new(),
+			}
+		))
+	}
+
+	fn while_loop(&self, while_loop: &While, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}while({}){{{}}}",
+			label(while_loop.label.as_deref(), ":"),
+			while_loop.condition.render(cx)?,
+			while_loop.scope.render(cx)?
+		))
+	}
+
+	fn for_range(&self, for_range: &ForRange, cx: &mut Context) -> Result<String, GenError> {
+		let typ = Type::Primitive(Primitive::Integer).render(cx)?;
+		let counter = Counter::new(for_range, cx)?;
+		Ok(format!(
+			"{}for({} {}={};{};{}){{{}}}",
+			label(for_range.label.as_deref(), ":"),
+			typ,
+			counter.var,
+			counter.start,
+			counter.condition,
+			counter.increment(),
+			for_range.scope.render(cx)?
+		))
+	}
+
+	fn for_each(&self, for_each: &ForEach, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}for(var {}:{}){{{}}}",
+			label(for_each.label.as_deref(), ":"),
+			for_each.var.render(cx)?,
+			for_each.iterable.render(cx)?,
+			for_each.scope.render(cx)?
+		))
+	}
+
+	fn break_statement(&self, label: Option<&str>, cx: &mut Context) -> Result<String, GenError> {
+		cx.loop_target(label, "statement::Break")?;
+		Ok(match label {
+			Some(label) => format!("break {};", label),
+			None => String::from("break;"),
+		})
+	}
+
+	fn continue_statement(
+		&self,
+		label: Option<&str>,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		cx.loop_target(label, "statement::Continue")?;
+		Ok(match label {
+			Some(label) => format!("continue {};", label),
+			None => String::from("continue;"),
+		})
+	}
+
+	/// Variables without a type are declared with `var`.
+	fn variable_init(&self, init: &VariableInit, cx: &mut Context) -> Result<String, GenError> {
+		let VariableInit {
+			name,
+			mutable,
+			typ,
+			value,
+		} = init;
+		Ok(format!(
+			"{}{} {}={};",
+			match mutable {
+				Some(true) => "",
+				Some(false) | None => "final ",
+			},
+			match typ {
+				Some(typ) => typ.render(cx)?,
+				None => String::from("var"),
+			},
+			name.render(cx)?,
+			value.render(cx)?
+		))
+	}
+
+	fn assign_variable(
+		&self,
+		name: &Expr,
+		value: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}={};", name.render(cx)?, value.render(cx)?))
+	}
+
+	fn return_statement(&self, value: Option<&Expr>, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match value {
+			Some(expr) => format!("return {};", expr.render(cx)?),
+			None => String::from("return;"),
+		})
+	}
+
+	fn comment(&self, comment: &Comment, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match comment {
+			Comment::Regular(content) => format!("//{}\n", content),
+			Comment::MultiLine(content) => format!("/*{}*/", content),
+			Comment::Doc(content) => format!("/**{}*/", content),
+		})
+	}
+
+	/// Modules are package names, whose classes are all imported.
+	fn import(&self, import: &Import, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match import {
+			Import::Module(location) => format!("import {}.*;", location),
+			Import::Members(location, members) => members
+				.iter()
+				.map(|member| format!("import {}.{};", location, member))
+				.join(""),
+		})
+	}
+
+	fn println(&self, value: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("System.out.println({});", value.render(cx)?))
+	}
+
+	fn expression_statement(&self, expr: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("{};", expr.render(cx)?))
+	}
+
+	fn ternary(
+		&self,
+		condition: &Expr,
+		if_true: &Expr,
+		if_false: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}?{}:{}",
+			condition.render(cx)?,
+			if_true.render(cx)?,
+			if_false.render(cx)?
+		))
+	}
+
+	fn primitive_type(&self, primitive: Primitive, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match primitive {
+			Primitive::Integer => "int",
+			Primitive::Number | Primitive::Float => "double",
+			Primitive::Boolean => "boolean",
+		}))
+	}
+
+	/// Type arguments cannot be primitives, so they are boxed.
+	fn generic_type(
+		&self,
+		name: &str,
+		args: &[Type],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let args = args
+			.iter()
+			.map(|arg| boxed(arg, cx))
+			.collect::<Result<Vec<_>, _>>()?;
+		Ok(format!("{}<{}>", name, args.join(",")))
+	}
+
+	fn future_type(&self, value: &Type, cx: &mut Context) -> Result<String, GenError> {
+		cx.require("java.util.concurrent.CompletableFuture");
+		self.generic_type("CompletableFuture", std::slice::from_ref(value), cx)
+	}
+
+	/// The encoding of source files depends on the platform, so only ASCII
+	/// is written.
+	fn string(&self, text: &str, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(escape::ascii(&escape::quote(
+			text,
+			'"',
+			"",
+			escape::unicode,
+		)))
+	}
+
+	fn character(&self, c: char, cx: &mut Context) -> Result<String, GenError> {
+		Ok(escape::ascii(&escape::utf16(c, cx)?))
+	}
+
+	/// The canonical constructor, see [`Context::declared_order`].
+	fn struct_literal(
+		&self,
+		name: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let values = cx
+			.declared_order(name.as_str(), fields)
+			.into_iter()
+			.map(|(_, value)| value)
+			.collect::<Vec<_>>();
+		Ok(format!(
+			"new {}({})",
+			cx.type_name(name.as_str())?,
+			cx.list(values)?
+		))
+	}
+
+	/// Record components are read through their accessor method, fields of
+	/// classes directly, so the type of `object` has to be known.
+	fn field_access(
+		&self,
+		object: &Expr,
+		field: &Ident,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let class = match cx.type_of(object) {
+			Some(Type::Named(name)) if cx.class(&name).is_some() => true,
+			Some(Type::Named(name)) if cx.structure(&name).is_some() => false,
+			_ => return Err(cx.missing_type("object of a field access")),
+		};
+		Ok(format!(
+			"{}.{}{}",
+			receiver(object, cx)?,
+			cx.field_name(field.as_str())?,
+			if class { "" } else { "()" }
+		))
+	}
+}
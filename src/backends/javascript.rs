@@ -0,0 +1,289 @@
+use itertools::Itertools;
+
+use crate::{
+	backends::{typescript, Context, TypeScript},
+	block::{Conditional, ForEach, ForRange, While},
+	ir::{Expr, Item, Operator, Stmt, Type},
+	statement::VariableInit,
+	types::Primitive,
+	Backend, Case, Class, Comment, Constructor, Function, GenError, Ident, Import, Language,
+	Method, ModuleSystem, NameKind, Parameter, Scope, Struct, ToCode, Visibility,
+};
+
+/// TypeScript without the type annotations.
+#[derive(Debug, Clone, Copy)]
+pub struct JavaScript;
+
+fn module_system(cx: &Context) -> ModuleSystem {
+	match cx.language() {
+		Language::JavaScript { module_system } => module_system,
+		_ => ModuleSystem::ESM,
+	}
+}
+
+/// A JSDoc comment for `function`, which documents the types of its
+/// parameters and result.
+fn js_doc(content: &str, function: &Function, cx: &mut Context) -> Result<String, GenError> {
+	cx.with_language(Language::TypeScript, |cx| {
+		let mut tags = String::new();
+		for Parameter(name, typ) in &function.params {
+			if let Some(typ) = typ {
+				tags.push_str(&format!(
+					"\n * @param {{{}}} {}",
+					typ.render(cx)?,
+					name.render(cx)?
+				));
+			}
+		}
+		if let Some(ref typ) = function.return_type {
+			tags.push_str(&format!("\n * @returns {{{}}}", typ.render(cx)?));
+		}
+		Ok(if tags.is_empty() {
+			format!("/**{}*/", content)
+		} else {
+			format!("/**{}{}\n */", content, tags)
+		})
+	})
+}
+
+impl Backend for JavaScript {
+	fn name(&self) -> &'static str {
+		"JavaScript"
+	}
+
+	fn extension(&self) -> &'static str {
+		"js"
+	}
+
+	fn trailing_commas(&self) -> bool {
+		true
+	}
+
+	fn keywords(&self) -> &'static [&'static str] {
+		TypeScript.keywords()
+	}
+
+	fn naming_convention(&self, kind: NameKind) -> Option<Case> {
+		TypeScript.naming_convention(kind)
+	}
+
+	/// Doc comments that come right before a function become JSDoc.
+	fn scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		let mut children = cx.children(scope)?;
+		for (idx, pair) in scope.children().windows(2).enumerate() {
+			if let [Stmt::Comment(Comment::Doc(content)), Stmt::Item(Item::Function(function))] =
+				pair
+			{
+				children[idx] =
+					cx.within(format!("[{}]", idx), |cx| js_doc(content, function, cx))?;
+			}
+		}
+		Ok(cx.statements(children))
+	}
+
+	fn run_scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		TypeScript.run_scope(scope, cx)
+	}
+
+	/// CommonJS exports public functions after declaring them, under their
+	/// unescaped name as keywords are valid property names.
+	fn function(&self, function: &Function, cx: &mut Context) -> Result<String, GenError> {
+		let code = typescript::function(function, false, cx)?;
+		Ok(match (module_system(cx), function.visibility) {
+			(ModuleSystem::CommonJS, Visibility::Public) => format!(
+				"{}module.exports.{}={};",
+				code,
+				cx.convention(function.name.as_str()),
+				function.name.render(cx)?
+			),
+			_ => code,
+		})
+	}
+
+	fn parameter(&self, parameter: &Parameter, cx: &mut Context) -> Result<String, GenError> {
+		parameter.0.render(cx)
+	}
+
+	fn visibility(&self, visibility: Visibility, cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match (module_system(cx), visibility) {
+			(ModuleSystem::ESM, Visibility::Public) => "export",
+			_ => "",
+		}))
+	}
+
+	/// A `class` whose constructor assigns every field.
+	fn structure(&self, structure: &Struct, cx: &mut Context) -> Result<String, GenError> {
+		let name = cx.type_name(structure.name.as_str())?;
+		let mut params = Vec::new();
+		let mut assignments = Vec::new();
+		for field in &structure.fields {
+			let field = cx.field_name(field.name.as_str())?;
+			assignments.push(format!("this.{}={};", field, field));
+			params.push(field);
+		}
+		let visibility = structure.visibility.render(cx)?;
+		let constructor = format!(
+			"constructor({}){}",
+			cx.list(&params)?,
+			cx.braces(&cx.statements(assignments))
+		);
+		let code = format!(
+			"{}{}class {}{}",
+			visibility,
+			if visibility.is_empty() { "" } else { " " },
+			name,
+			cx.braces(&constructor)
+		);
+		Ok(match (module_system(cx), structure.visibility) {
+			(ModuleSystem::CommonJS, Visibility::Public) => {
+				format!("{}module.exports.{}={};", code, name, name)
+			}
+			_ => code,
+		})
+	}
+
+	/// A `class` whose fields are only assigned by its constructor. Every
+	/// member is public.
+	fn class(&self, class: &Class, cx: &mut Context) -> Result<String, GenError> {
+		let members = class
+			.members(cx)?
+			.into_iter()
+			.skip(class.fields.len())
+			.map(|(_, member)| member)
+			.collect();
+		let name = cx.type_name(class.name.as_str())?;
+		let visibility = class.visibility.render(cx)?;
+		let code = format!(
+			"{}{}class {}{}",
+			visibility,
+			if visibility.is_empty() { "" } else { " " },
+			name,
+			cx.braces(&cx.statements(members))
+		);
+		Ok(match (module_system(cx), class.visibility) {
+			(ModuleSystem::CommonJS, Visibility::Public) => {
+				format!("{}module.exports.{}={};", code, name, name)
+			}
+			_ => code,
+		})
+	}
+
+	fn constructor(
+		&self,
+		_class: &Class,
+		constructor: &Constructor,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		typescript::constructor(constructor, cx)
+	}
+
+	fn method(&self, method: &Method, cx: &mut Context) -> Result<String, GenError> {
+		typescript::method(method, false, cx)
+	}
+
+	fn conditional(&self, conditional: &Conditional, cx: &mut Context) -> Result<String, GenError> {
+		TypeScript.conditional(conditional, cx)
+	}
+
+	fn while_loop(&self, while_loop: &While, cx: &mut Context) -> Result<String, GenError> {
+		TypeScript.while_loop(while_loop, cx)
+	}
+
+	fn for_range(&self, for_range: &ForRange, cx: &mut Context) -> Result<String, GenError> {
+		TypeScript.for_range(for_range, cx)
+	}
+
+	fn for_each(&self, for_each: &ForEach, cx: &mut Context) -> Result<String, GenError> {
+		TypeScript.for_each(for_each, cx)
+	}
+
+	fn break_statement(&self, label: Option<&str>, cx: &mut Context) -> Result<String, GenError> {
+		TypeScript.break_statement(label, cx)
+	}
+
+	fn continue_statement(
+		&self,
+		label: Option<&str>,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		TypeScript.continue_statement(label, cx)
+	}
+
+	fn variable_init(&self, init: &VariableInit, cx: &mut Context) -> Result<String, GenError> {
+		typescript::variable_init(init, false, cx)
+	}
+
+	fn assign_variable(
+		&self,
+		name: &Expr,
+		value: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		TypeScript.assign_variable(name, value, cx)
+	}
+
+	fn return_statement(&self, value: Option<&Expr>, cx: &mut Context) -> Result<String, GenError> {
+		TypeScript.return_statement(value, cx)
+	}
+
+	fn comment(&self, comment: &Comment, cx: &mut Context) -> Result<String, GenError> {
+		TypeScript.comment(comment, cx)
+	}
+
+	fn import(&self, import: &Import, cx: &mut Context) -> Result<String, GenError> {
+		match module_system(cx) {
+			ModuleSystem::ESM => TypeScript.import(import, cx),
+			ModuleSystem::CommonJS => Ok(match import {
+				Import::Module(location) => format!("require(\"{}\");", location),
+				Import::Members(location, members) => format!(
+					"const {{{}}}=require(\"{}\");",
+					members.iter().join(","),
+					location
+				),
+			}),
+		}
+	}
+
+	fn println(&self, value: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		TypeScript.println(value, cx)
+	}
+
+	fn expression_statement(&self, expr: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		TypeScript.expression_statement(expr, cx)
+	}
+
+	fn ternary(
+		&self,
+		condition: &Expr,
+		if_true: &Expr,
+		if_false: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		TypeScript.ternary(condition, if_true, if_false, cx)
+	}
+
+	fn primitive_type(&self, primitive: Primitive, cx: &mut Context) -> Result<String, GenError> {
+		TypeScript.primitive_type(primitive, cx)
+	}
+
+	fn future_type(&self, value: &Type, cx: &mut Context) -> Result<String, GenError> {
+		TypeScript.future_type(value, cx)
+	}
+
+	fn operator(&self, operator: Operator) -> &'static str {
+		TypeScript.operator(operator)
+	}
+
+	fn character(&self, c: char, cx: &mut Context) -> Result<String, GenError> {
+		TypeScript.character(c, cx)
+	}
+
+	fn struct_literal(
+		&self,
+		name: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		typescript::construct(name, fields, cx)
+	}
+}
@@ -0,0 +1,364 @@
+use itertools::Itertools;
+
+use crate::{
+	backends::{escape, label, offset, Context},
+	block::{Conditional, ForEach, ForRange, While},
+	ir::{Expr, Type},
+	statement::VariableInit,
+	types::Primitive,
+	Backend, Case, Comment, Field, Function, GenError, Ident, Import, NameKind, Parameter, Scope,
+	Struct, ToCode, Visibility,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Kotlin;
+
+const KEYWORDS: &[&str] = &[
+	"as",
+	"break",
+	"class",
+	"continue",
+	"do",
+	"else",
+	"false",
+	"for",
+	"fun",
+	"if",
+	"in",
+	"interface",
+	"is",
+	"null",
+	"object",
+	"package",
+	"return",
+	"super",
+	"this",
+	"throw",
+	"true",
+	"try",
+	"typealias",
+	"typeof",
+	"val",
+	"var",
+	"when",
+	"while",
+];
+
+impl Backend for Kotlin {
+	fn name(&self) -> &'static str {
+		"Kotlin"
+	}
+
+	fn extension(&self) -> &'static str {
+		"kt"
+	}
+
+	fn trailing_commas(&self) -> bool {
+		true
+	}
+
+	fn keywords(&self) -> &'static [&'static str] {
+		KEYWORDS
+	}
+
+	fn naming_convention(&self, _kind: NameKind) -> Option<Case> {
+		Some(Case::Camel)
+	}
+
+	fn escape_keyword(&self, name: &str) -> String {
+		format!("`{}`", name)
+	}
+
+	fn finish(&self, code: String, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}{}",
+			cx.requirements()
+				.iter()
+				.map(|itm| format!("import {}\n", itm))
+				.join(""),
+			code
+		))
+	}
+
+	fn scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		Ok(cx.children(scope)?.join(""))
+	}
+
+	fn run_scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("fun main(){{{}}}", scope.render(cx)?))
+	}
+
+	fn function(&self, function: &Function, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{} fun {}({}){}{{{}}}",
+			function.visibility.render(cx)?,
+			function.name.render(cx)?,
+			cx.join(&function.params, ",")?,
+			match function.return_type {
+				Some(ref typ) => format!(":{}", typ.render(cx)?),
+				None => String::new(),
+			},
+			function.scope.render(cx)?
+		))
+	}
+
+	fn parameter(&self, parameter: &Parameter, cx: &mut Context) -> Result<String, GenError> {
+		let Parameter(name, typ) = parameter;
+		match typ {
+			Some(typ) => Ok(format!("{}:{}", name.render(cx)?, typ.render(cx)?)),
+			None => Err(cx.missing_type("parameter")),
+		}
+	}
+
+	fn visibility(&self, visibility: Visibility, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match visibility {
+			Visibility::Public => "public",
+			Visibility::Private => "private",
+		}))
+	}
+
+	/// A `data class`, or a plain `class` if it has no fields.
+	fn structure(&self, structure: &Struct, cx: &mut Context) -> Result<String, GenError> {
+		let visibility = structure.visibility.render(cx)?;
+		let name = cx.type_name(structure.name.as_str())?;
+		Ok(if structure.fields.is_empty() {
+			format!("{} class {};", visibility, name)
+		} else {
+			format!(
+				"{} data class {}({});",
+				visibility,
+				name,
+				cx.list(&structure.fields)?
+			)
+		})
+	}
+
+	fn field(&self, field: &Field, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}val {}:{}",
+			match field.visibility {
+				Visibility::Public => "",
+				Visibility::Private => "private ",
+			},
+			cx.field_name(field.name.as_str())?,
+			field.typ.render(cx)?
+		))
+	}
+
+	fn conditional(&self, conditional: &Conditional, cx: &mut Context) -> Result<String, GenError> {
+		let mut branches = Vec::new();
+		for (condition, scope) in &conditional.branches {
+			branches.push(format!(
+				"if({}){{{}}}",
+				condition.render(cx)?,
+				scope.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}{}",
+			branches.iter().join("else "),
+			match conditional.otherwise {
+				Some(ref last) => format!("else{{{}}}", last.render(cx)?),
+				None => String::new(),
+			}
+		))
+	}
+
+	fn while_loop(&self, while_loop: &While, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}while({}){{{}}}",
+			label(while_loop.label.as_deref(), "@"),
+			while_loop.condition.render(cx)?,
+			while_loop.scope.render(cx)?
+		))
+	}
+
+	/// Counts up with `until` and down with `downTo`, which includes its end.
+	fn for_range(&self, for_range: &ForRange, cx: &mut Context) -> Result<String, GenError> {
+		let range = if for_range.step > 0 {
+			format!(
+				"{} until {}",
+				for_range.start.render(cx)?,
+				for_range.end.render(cx)?
+			)
+		} else {
+			let last = offset(&for_range.end, 1);
+			format!(
+				"{} downTo {}",
+				for_range.start.render(cx)?,
+				last.render(cx)?
+			)
+		};
+		Ok(format!(
+			"{}for({} in {}{}){{{}}}",
+			label(for_range.label.as_deref(), "@"),
+			for_range.var.render(cx)?,
+			range,
+			match for_range.step.unsigned_abs() {
+				1 => String::new(),
+				step => format!(" step {}", step),
+			},
+			for_range.scope.render(cx)?
+		))
+	}
+
+	fn for_each(&self, for_each: &ForEach, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}for({} in {}){{{}}}",
+			label(for_each.label.as_deref(), "@"),
+			for_each.var.render(cx)?,
+			for_each.iterable.render(cx)?,
+			for_each.scope.render(cx)?
+		))
+	}
+
+	fn break_statement(&self, label: Option<&str>, cx: &mut Context) -> Result<String, GenError> {
+		cx.loop_target(label, "statement::Break")?;
+		Ok(match label {
+			Some(label) => format!("break@{};", label),
+			None => String::from("break;"),
+		})
+	}
+
+	fn continue_statement(
+		&self,
+		label: Option<&str>,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		cx.loop_target(label, "statement::Continue")?;
+		Ok(match label {
+			Some(label) => format!("continue@{};", label),
+			None => String::from("continue;"),
+		})
+	}
+
+	fn variable_init(&self, init: &VariableInit, cx: &mut Context) -> Result<String, GenError> {
+		let VariableInit {
+			name,
+			mutable,
+			typ,
+			value,
+		} = init;
+		Ok(format!(
+			"{} {}{}={};",
+			match mutable {
+				Some(true) => "var",
+				Some(false) | None => "val",
+			},
+			name.render(cx)?,
+			match typ {
+				Some(typ) => format!(":{}", typ.render(cx)?),
+				None => String::new(),
+			},
+			value.render(cx)?
+		))
+	}
+
+	fn assign_variable(
+		&self,
+		name: &Expr,
+		value: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}={};", name.render(cx)?, value.render(cx)?))
+	}
+
+	fn return_statement(&self, value: Option<&Expr>, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match value {
+			Some(expr) => format!("return {};", expr.render(cx)?),
+			None => String::from("return;"),
+		})
+	}
+
+	fn comment(&self, comment: &Comment, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match comment {
+			Comment::Regular(content) => format!("//{}\n", content),
+			Comment::MultiLine(content) => format!("/*{}*/", content),
+			Comment::Doc(content) => format!("/**{}*/", content),
+		})
+	}
+
+	/// Modules are package names, whose declarations are all imported.
+	/// Imports have to come before every declaration, so they are written by
+	/// [`Backend::finish`].
+	fn import(&self, import: &Import, cx: &mut Context) -> Result<String, GenError> {
+		match import {
+			Import::Module(location) => cx.require(format!("{}.*", location)),
+			Import::Members(location, members) => {
+				for member in members {
+					cx.require(format!("{}.{}", location, member));
+				}
+			}
+		}
+		Ok(String::new())
+	}
+
+	fn println(&self, value: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("println({});", value.render(cx)?))
+	}
+
+	fn expression_statement(&self, expr: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("{};", expr.render(cx)?))
+	}
+
+	/// `if` is an expression in Kotlin.
+	fn ternary(
+		&self,
+		condition: &Expr,
+		if_true: &Expr,
+		if_false: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"if({}) {} else {}",
+			condition.render(cx)?,
+			if_true.render(cx)?,
+			if_false.render(cx)?
+		))
+	}
+
+	fn primitive_type(&self, primitive: Primitive, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match primitive {
+			Primitive::Integer => "Int",
+			Primitive::Number | Primitive::Float => "Double",
+			Primitive::Boolean => "Boolean",
+		}))
+	}
+
+	/// The result of `async` from kotlinx.coroutines.
+	fn future_type(&self, value: &Type, cx: &mut Context) -> Result<String, GenError> {
+		cx.require("kotlinx.coroutines.Deferred");
+		self.generic_type("Deferred", std::slice::from_ref(value), cx)
+	}
+
+	/// `$` starts a string template.
+	fn string(&self, text: &str, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(escape::quote(text, '"', "$", escape::unicode))
+	}
+
+	fn character(&self, c: char, cx: &mut Context) -> Result<String, GenError> {
+		escape::utf16(c, cx)
+	}
+
+	/// The constructor, with named arguments.
+	fn struct_literal(
+		&self,
+		name: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let mut values = Vec::new();
+		for (field, value) in fields {
+			values.push(format!(
+				"{}={}",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}({})",
+			cx.type_name(name.as_str())?,
+			cx.list(&values)?
+		))
+	}
+}
@@ -0,0 +1,60 @@
+//! Indentation for languages where it matters.
+//!
+//! Backends cannot know how deeply the code they return will end up nested, so
+//! instead of whitespace they emit layout tokens, which [`layout`] replaces
+//! with newlines and indentation once the whole program has been rendered.
+
+use crate::IndentType;
+
+/// Starts a new line at the current indentation, unless nothing has been
+/// written to the current line yet.
+pub const NEWLINE: char = '\u{E000}';
+/// Indents the lines that follow one level deeper.
+pub const INDENT: char = '\u{E001}';
+/// Undoes an [`INDENT`].
+pub const DEDENT: char = '\u{E002}';
+
+/// `lines` as an indented block that starts on a new line.
+pub fn block(lines: impl IntoIterator<Item = String>) -> String {
+	format!("{}{}{}", INDENT, self::lines(lines), DEDENT)
+}
+
+/// Each of `lines` on a new line.
+pub fn lines(lines: impl IntoIterator<Item = String>) -> String {
+	lines
+		.into_iter()
+		.map(|line| format!("{}{}", NEWLINE, line))
+		.collect()
+}
+
+/// Replaces the layout tokens in `code`. Blank lines are not indented, and
+/// the first line does not start with a newline.
+pub fn layout(code: &str, indent_type: IndentType) -> String {
+	let indent = String::from(indent_type);
+	let mut output = String::with_capacity(code.len());
+	let mut depth = 0usize;
+	let mut line_start = false;
+	for chr in code.chars() {
+		match chr {
+			NEWLINE if line_start || output.is_empty() => line_start = true,
+			NEWLINE => {
+				output.push('\n');
+				line_start = true;
+			}
+			INDENT => depth += 1,
+			DEDENT => depth = depth.saturating_sub(1),
+			'\n' => {
+				output.push('\n');
+				line_start = true;
+			}
+			_ => {
+				if line_start {
+					output.push_str(&indent.repeat(depth));
+					line_start = false;
+				}
+				output.push(chr);
+			}
+		}
+	}
+	output
+}
@@ -0,0 +1,330 @@
+use itertools::Itertools;
+
+use crate::{
+	backends::{escape, offset, Context},
+	block::{Conditional, ForEach, ForRange, While},
+	ir::{Associativity, Expr, Item, Operator, Stmt, Type},
+	statement::VariableInit,
+	types::Primitive,
+	Backend, Case, Comment, Function, GenError, Import, NameKind, Parameter, Scope, ToCode,
+	Visibility,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Lua;
+
+const KEYWORDS: &[&str] = &[
+	"and", "break", "do", "else", "elseif", "end", "false", "for", "function", "goto", "if", "in",
+	"local", "nil", "not", "or", "repeat", "return", "then", "true", "until", "while",
+];
+
+/// The table that public functions are stored in and that the chunk returns.
+const MODULE: &str = "M";
+
+/// Whether `expr` is known to never be `false` or `nil`.
+fn truthy(expr: &Expr, cx: &Context) -> bool {
+	matches!(
+		cx.type_of(expr),
+		Some(Type::Primitive(
+			Primitive::Integer | Primitive::Number | Primitive::Float
+		))
+	)
+}
+
+impl Backend for Lua {
+	fn name(&self) -> &'static str {
+		"Lua"
+	}
+
+	fn extension(&self) -> &'static str {
+		"lua"
+	}
+
+	fn keywords(&self) -> &'static [&'static str] {
+		KEYWORDS
+	}
+
+	fn naming_convention(&self, _kind: NameKind) -> Option<Case> {
+		Some(Case::Snake)
+	}
+
+	/// Public functions of the outermost scope are stored in a module table,
+	/// which is returned at the end of the chunk. Every other function is
+	/// declared as a local at the top, so that it can be called before it is
+	/// defined, and a `return` that is followed by more statements is wrapped
+	/// in a block because it has to be the last statement of one.
+	fn scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		let mut public = false;
+		let mut locals = Vec::new();
+		for child in scope.children() {
+			if let Stmt::Item(Item::Function(function)) = child {
+				cx.declare_function(function.name.as_str());
+				let name = cx.escape(function.name.as_str())?;
+				if cx.depth() == 1 && function.visibility == Visibility::Public {
+					cx.rename(function.name.as_str(), format!("{}.{}", MODULE, name));
+					public = true;
+				} else {
+					locals.push(name);
+				}
+			}
+		}
+		let mut children = cx.children(scope)?;
+		let last = children.len().saturating_sub(1);
+		for (idx, child) in scope.children().iter().enumerate() {
+			if let Stmt::Return(_) = child {
+				if idx != last {
+					children[idx] = format!("do {}end;", children[idx]);
+				}
+			}
+		}
+		let code = if locals.is_empty() {
+			children.join("")
+		} else {
+			format!("local {};{}", locals.join(","), children.join(""))
+		};
+		Ok(if public {
+			format!("local {}={{}};{}return {};", MODULE, code, MODULE)
+		} else {
+			code
+		})
+	}
+
+	fn run_scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		scope.render(cx)
+	}
+
+	/// The function has been declared by the scope around it, see
+	/// [`Lua::scope`], unless it is rendered on its own.
+	fn function(&self, function: &Function, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}function {}({}) {}end;",
+			if cx.depth() == 1 {
+				function.visibility.render(cx)?
+			} else {
+				String::new()
+			},
+			cx.resolve(function.name.as_str())?,
+			cx.join(&function.params, ",")?,
+			function.scope.render(cx)?
+		))
+	}
+
+	fn parameter(&self, parameter: &Parameter, cx: &mut Context) -> Result<String, GenError> {
+		parameter.0.render(cx)
+	}
+
+	fn visibility(&self, visibility: Visibility, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match visibility {
+			Visibility::Public => "",
+			Visibility::Private => "local ",
+		}))
+	}
+
+	fn conditional(&self, conditional: &Conditional, cx: &mut Context) -> Result<String, GenError> {
+		let mut branches = Vec::new();
+		for (condition, scope) in &conditional.branches {
+			branches.push(format!(
+				"if {} then {}",
+				condition.render(cx)?,
+				scope.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}{}end;",
+			branches.iter().join("else"),
+			match conditional.otherwise {
+				Some(ref last) => format!("else {}", last.render(cx)?),
+				None => String::new(),
+			}
+		))
+	}
+
+	fn while_loop(&self, while_loop: &While, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"while {} do {}end;",
+			while_loop.condition.render(cx)?,
+			while_loop.scope.render(cx)?
+		))
+	}
+
+	/// A numeric `for`, whose limit is inclusive.
+	fn for_range(&self, for_range: &ForRange, cx: &mut Context) -> Result<String, GenError> {
+		let limit = offset(&for_range.end, -for_range.step.signum());
+		Ok(format!(
+			"for {}={},{}{} do {}end;",
+			for_range.var.render(cx)?,
+			for_range.start.render(cx)?,
+			limit.render(cx)?,
+			match for_range.step {
+				1 => String::new(),
+				step => format!(",{}", step),
+			},
+			for_range.scope.render(cx)?
+		))
+	}
+
+	fn for_each(&self, for_each: &ForEach, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"for _,{} in ipairs({}) do {}end;",
+			for_each.var.render(cx)?,
+			for_each.iterable.render(cx)?,
+			for_each.scope.render(cx)?
+		))
+	}
+
+	fn break_statement(&self, label: Option<&str>, cx: &mut Context) -> Result<String, GenError> {
+		cx.innermost_loop(label, "statement::Break")?;
+		Ok(String::from("break;"))
+	}
+
+	/// Lua has no `continue`.
+	fn continue_statement(
+		&self,
+		_label: Option<&str>,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Err(cx.unsupported("statement::Continue"))
+	}
+
+	/// Variables of the outermost scope are globals, every other variable is
+	/// local.
+	fn variable_init(&self, init: &VariableInit, cx: &mut Context) -> Result<String, GenError> {
+		let VariableInit { name, value, .. } = init;
+		Ok(format!(
+			"{}{}={};",
+			if cx.depth() == 1 { "" } else { "local " },
+			name.render(cx)?,
+			value.render(cx)?
+		))
+	}
+
+	fn assign_variable(
+		&self,
+		name: &Expr,
+		value: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}={};", name.render(cx)?, value.render(cx)?))
+	}
+
+	fn return_statement(&self, value: Option<&Expr>, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match value {
+			Some(expr) => format!("return {};", expr.render(cx)?),
+			None => String::from("return;"),
+		})
+	}
+
+	fn comment(&self, comment: &Comment, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match comment {
+			Comment::Regular(content) => format!("--{}\n", content),
+			Comment::MultiLine(content) => format!("--[[{}]]", content),
+			Comment::Doc(content) => format!("---{}\n", content),
+		})
+	}
+
+	fn import(&self, import: &Import, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match import {
+			Import::Module(location) => format!("require(\"{}\");", location),
+			Import::Members(location, members) => members
+				.iter()
+				.map(|member| format!("local {}=require(\"{}\").{};", member, location, member))
+				.join(""),
+		})
+	}
+
+	fn println(&self, value: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("print({});", value.render(cx)?))
+	}
+
+	fn expression_statement(&self, expr: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("{};", expr.render(cx)?))
+	}
+
+	/// `c and a or b` evaluates to `b` when `a` is `false` or `nil`, so it is
+	/// only used when `a` is a number and a function is called otherwise.
+	fn ternary(
+		&self,
+		condition: &Expr,
+		if_true: &Expr,
+		if_false: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(if truthy(if_true, cx) {
+			format!(
+				"({} and {} or {})",
+				condition.render(cx)?,
+				if_true.render(cx)?,
+				if_false.render(cx)?
+			)
+		} else {
+			format!(
+				"(function() if {} then return {};else return {};end;end)()",
+				condition.render(cx)?,
+				if_true.render(cx)?,
+				if_false.render(cx)?
+			)
+		})
+	}
+
+	/// The names used by `math.type`, for documentation only.
+	fn primitive_type(&self, primitive: Primitive, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match primitive {
+			Primitive::Integer => "integer",
+			Primitive::Number | Primitive::Float => "number",
+			Primitive::Boolean => "boolean",
+		}))
+	}
+
+	fn future_type(&self, _value: &Type, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("types::Future"))
+	}
+
+	fn precedence(&self, operator: Operator) -> (u8, Associativity) {
+		operator.flat_precedence()
+	}
+
+	fn operator(&self, operator: Operator) -> &'static str {
+		match operator {
+			Operator::NotEqual => "~=",
+			Operator::And => " and ",
+			Operator::Or => " or ",
+			_ => operator.symbol(),
+		}
+	}
+
+	/// Dividing two integers uses floor division, which Lua 5.3 added.
+	fn binary(
+		&self,
+		operator: Operator,
+		lhs: &Expr,
+		rhs: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let integer = Some(Type::Primitive(Primitive::Integer));
+		let symbol = match operator {
+			Operator::Divide if cx.type_of(lhs) == integer && cx.type_of(rhs) == integer => "//",
+			_ => self.operator(operator),
+		};
+		let lhs = cx.operand(operator, lhs, false)?;
+		let rhs = cx.operand(operator, rhs, true)?;
+		Ok(format!("{}{}{}", lhs, symbol, rhs))
+	}
+
+	/// Control characters are decimal escapes, padded so that they do not
+	/// swallow the digits that follow them. Decimal escapes are single bytes,
+	/// so wider characters are `\u{e000}`.
+	fn string(&self, text: &str, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(escape::quote(text, '"', "", |c| match c as u32 {
+			0..=0xff => format!("\\{:03}", c as u32),
+			_ => escape::braced(c),
+		}))
+	}
+
+	fn character(&self, c: char, cx: &mut Context) -> Result<String, GenError> {
+		self.string(&c.to_string(), cx)
+	}
+
+	fn null(&self, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from("nil"))
+	}
+}
@@ -0,0 +1,526 @@
+//! Code generation targets.
+//!
+//! Every node renders itself by dispatching to the [`Backend`] of the
+//! [`Language`](crate::Language) it is given, so supporting a new target only
+//! requires a new [`Backend`] implementation. Third-party backends are plugged
+//! in through [`Language::Custom`](crate::Language::Custom).
+
+mod c;
+mod context;
+mod cpp;
+mod csharp;
+mod escape;
+mod go;
+mod haskell;
+mod java;
+mod javascript;
+mod kotlin;
+pub mod layout;
+mod lua;
+mod php;
+mod python;
+mod ruby;
+mod rust;
+mod swift;
+mod typescript;
+mod wat;
+mod zig;
+
+use std::fmt::Debug;
+
+use crate::{
+	block::{Conditional, ForEach, ForRange, Match, While},
+	ir::{Associativity, Expr, Operator, Stmt, Type},
+	statement::VariableInit,
+	types::Primitive,
+	Case, Class, Comment, Constructor, Enum, Field, Function, GenError, Ident, Import, Interface,
+	Literal, Method, NameKind, Parameter, Scope, Signature, Struct, ToCode, Value, Visibility,
+};
+
+/// `expr+offset`, folded if `expr` is an integer, for targets whose ranges
+/// include their end.
+fn offset(expr: &Expr, offset: i64) -> Expr {
+	match expr {
+		Expr::Int(value) => Expr::Int(value + offset),
+		_ if offset < 0 => Expr::Binary(
+			Operator::Subtract,
+			Box::new(expr.clone()),
+			Box::new(Expr::Int(-offset)),
+		),
+		_ => Expr::Binary(
+			Operator::Add,
+			Box::new(expr.clone()),
+			Box::new(Expr::Int(offset)),
+		),
+	}
+}
+
+/// `object` in front of a `.`, which binds tighter than any operator.
+fn receiver(object: &Expr, cx: &mut Context) -> Result<String, GenError> {
+	let code = object.render(cx)?;
+	Ok(match object {
+		Expr::Binary(..) | Expr::Ternary(..) => format!("({})", code),
+		Expr::Int(value) | Expr::Literal(Literal::Int { value, .. }) if *value < 0 => {
+			format!("({})", code)
+		}
+		_ => code,
+	})
+}
+
+/// Whether the enum `name` is rendered as C-like, see [`Enum::is_c_like`].
+/// Enums that have not been rendered are assumed to be C-like when a variant
+/// of them has no fields.
+fn c_like(name: &Ident, fields: &[(Ident, Expr)], cx: &Context) -> bool {
+	cx.enumeration(name.as_str())
+		.map_or(fields.is_empty(), Enum::is_c_like)
+}
+
+/// The type that the variant `variant` of a tagged enum is declared as in
+/// targets where it is not scoped to `enumeration`, so variants of two enums
+/// cannot share a name.
+fn variant_type(
+	enumeration: &Ident,
+	variant: &Ident,
+	cx: &mut Context,
+) -> Result<String, GenError> {
+	cx.reserve(
+		&format!("{}::{}", enumeration.as_str(), variant.as_str()),
+		variant.as_str().to_owned(),
+	)
+}
+
+/// `fields` sorted by the position of their name in `declared`.
+fn ordered<'a>(declared: &[Field], fields: &'a [(Ident, Expr)]) -> Vec<&'a (Ident, Expr)> {
+	let mut fields = fields.iter().collect::<Vec<_>>();
+	fields.sort_by_key(|(field, _)| {
+		declared
+			.iter()
+			.position(|itm| itm.name == *field)
+			.unwrap_or(usize::MAX)
+	});
+	fields
+}
+
+/// `scope` with `lines` inserted before its first statement, e.g. to bind
+/// the fields of a variant in a [`crate::block::Arm`].
+fn prepend(scope: &Scope, lines: Vec<String>) -> Scope {
+	let mut scope = scope.clone();
+	scope
+		.children_mut()
+		.splice(0..0, lines.into_iter().map(Stmt::Raw));
+	scope
+}
+
+/// The label in front of a loop, followed by `separator`, e.g. `:` for
+/// `outer:while(true)`.
+fn label(label: Option<&str>, separator: &str) -> String {
+	label.map_or_else(String::new, |label| format!("{}{}", label, separator))
+}
+
+/// The parts of a C-style `for` loop that counts through a [`ForRange`].
+struct Counter {
+	var: String,
+	start: String,
+	/// `var<end`, or `var>end` when counting down.
+	condition: String,
+	/// `var+=step`, or `var-=step` when counting down.
+	update: String,
+	step: i64,
+}
+
+impl Counter {
+	fn new(for_range: &ForRange, cx: &mut Context) -> Result<Self, GenError> {
+		let var = for_range.var.render(cx)?;
+		let start = for_range.start.render(cx)?;
+		let operator = if for_range.step > 0 {
+			Operator::LessThan
+		} else {
+			Operator::GreaterThan
+		};
+		let end = cx.operand(operator, &for_range.end, true)?;
+		let condition = cx.infix(&var, cx.backend().operator(operator), &end);
+		let update = if for_range.step > 0 {
+			format!("{}+={}", var, for_range.step)
+		} else {
+			format!("{}-={}", var, for_range.step.unsigned_abs())
+		};
+		Ok(Self {
+			var,
+			start,
+			condition,
+			update,
+			step: for_range.step,
+		})
+	}
+
+	/// [`Counter::update`], written as `var++` or `var--` when it is one.
+	fn increment(&self) -> String {
+		match self.step {
+			1 => format!("{}++", self.var),
+			-1 => format!("{}--", self.var),
+			_ => self.update.clone(),
+		}
+	}
+}
+
+pub use c::C;
+pub use context::Context;
+pub use cpp::CPP;
+pub use csharp::CSharp;
+pub use go::Go;
+pub use haskell::Haskell;
+pub use java::Java;
+pub use javascript::JavaScript;
+pub use kotlin::Kotlin;
+pub use lua::Lua;
+pub use php::PHP;
+pub use python::Python;
+pub use ruby::Ruby;
+pub use rust::Rust;
+pub use swift::Swift;
+pub use typescript::TypeScript;
+pub use wat::WAT;
+pub use zig::Zig;
+
+/// Renders each kind of node for a single target language.
+///
+/// Child nodes are rendered with [`ToCode::render`] using the same [`Context`]
+/// so that they reach the same backend and report errors at the right path.
+pub trait Backend: Debug {
+	/// The name of the target language.
+	fn name(&self) -> &'static str;
+
+	/// The file extension of source files in the target language, without the
+	/// leading dot.
+	fn extension(&self) -> &'static str;
+
+	/// Called with the rendered code once the outermost node has been
+	/// rendered, e.g. to prepend the [`Context::requirements`].
+	fn finish(&self, code: String, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(code)
+	}
+
+	fn scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError>;
+
+	/// The entry point of a program, e.g. `fn main` in Rust.
+	fn run_scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError>;
+
+	fn function(&self, function: &Function, cx: &mut Context) -> Result<String, GenError>;
+
+	fn parameter(&self, parameter: &Parameter, cx: &mut Context) -> Result<String, GenError>;
+
+	fn visibility(&self, visibility: Visibility, cx: &mut Context) -> Result<String, GenError>;
+
+	/// An `if`/`else if`/`else` chain.
+	fn conditional(&self, conditional: &Conditional, cx: &mut Context) -> Result<String, GenError>;
+
+	fn while_loop(&self, _while_loop: &While, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("block::While"))
+	}
+
+	fn for_range(&self, _for_range: &ForRange, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("block::ForRange"))
+	}
+
+	fn for_each(&self, _for_each: &ForEach, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("block::ForEach"))
+	}
+
+	/// A [`Match`] whose arms have been checked against `enumeration`.
+	fn match_block(
+		&self,
+		_match_block: &Match,
+		_enumeration: &Enum,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Err(cx.unsupported("block::Match"))
+	}
+
+	/// A `break` out of the loop at [`Context::loop_target`].
+	fn break_statement(&self, _label: Option<&str>, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("statement::Break"))
+	}
+
+	/// A `continue` of the loop at [`Context::loop_target`].
+	fn continue_statement(
+		&self,
+		_label: Option<&str>,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Err(cx.unsupported("statement::Continue"))
+	}
+
+	fn variable_init(&self, init: &VariableInit, cx: &mut Context) -> Result<String, GenError>;
+
+	fn assign_variable(
+		&self,
+		name: &Expr,
+		value: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError>;
+
+	fn return_statement(&self, value: Option<&Expr>, cx: &mut Context) -> Result<String, GenError>;
+
+	fn comment(&self, comment: &Comment, cx: &mut Context) -> Result<String, GenError>;
+
+	fn import(&self, import: &Import, cx: &mut Context) -> Result<String, GenError>;
+
+	fn println(&self, value: &Expr, cx: &mut Context) -> Result<String, GenError>;
+
+	/// An expression evaluated for its side effects, e.g. a function call.
+	fn expression_statement(&self, expr: &Expr, cx: &mut Context) -> Result<String, GenError>;
+
+	fn ternary(
+		&self,
+		condition: &Expr,
+		if_true: &Expr,
+		if_false: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError>;
+
+	fn primitive_type(&self, primitive: Primitive, cx: &mut Context) -> Result<String, GenError>;
+
+	fn future_type(&self, value: &Type, cx: &mut Context) -> Result<String, GenError>;
+
+	fn generic_type(
+		&self,
+		name: &str,
+		args: &[Type],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}<{}>", name, cx.join(args, ",")?))
+	}
+
+	/// A reference to a type declared in the generated code.
+	fn named_type(&self, name: &str, cx: &mut Context) -> Result<String, GenError> {
+		cx.type_name(name)
+	}
+
+	/// The declaration of a data type, see [`StructBuilder`](crate::StructBuilder).
+	fn structure(&self, _structure: &Struct, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("StructBuilder"))
+	}
+
+	/// A field inside of [`Backend::structure`].
+	fn field(&self, field: &Field, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}:{}",
+			cx.field_name(field.name.as_str())?,
+			field.typ.render(cx)?
+		))
+	}
+
+	/// The declaration of a data type with methods, see [`Class::members`].
+	fn class(&self, _class: &Class, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("ClassBuilder"))
+	}
+
+	fn constructor(
+		&self,
+		_class: &Class,
+		_constructor: &Constructor,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Err(cx.unsupported("constructors"))
+	}
+
+	fn method(&self, _method: &Method, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("methods"))
+	}
+
+	/// The declaration of an enum, see [`Enum::is_c_like`].
+	fn enumeration(&self, _enumeration: &Enum, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("EnumBuilder"))
+	}
+
+	/// The declaration of an interface, see [`Class::interfaces`].
+	fn interface(&self, _interface: &Interface, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("InterfaceBuilder"))
+	}
+
+	/// A method of an [`Interface`], which has no body.
+	fn signature(&self, _signature: &Signature, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("InterfaceBuilder"))
+	}
+
+	/// The instance inside of a method.
+	fn this(&self, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from("this"))
+	}
+
+	fn integer(&self, value: i64, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(value.to_string())
+	}
+
+	/// An integer typed as `suffix`, see [`Literal::Int`](crate::Literal::Int).
+	fn suffixed_integer(
+		&self,
+		value: i64,
+		suffix: Primitive,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		match suffix {
+			Primitive::Integer => self.integer(value, cx),
+			Primitive::Number | Primitive::Float => self.float(value as f64, cx),
+			Primitive::Boolean => Err(cx.unsupported("boolean literal suffixes")),
+		}
+	}
+
+	fn float(&self, value: f64, cx: &mut Context) -> Result<String, GenError> {
+		escape::float(value, cx)
+	}
+
+	fn string(&self, text: &str, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(escape::quote(text, '"', "", escape::unicode))
+	}
+
+	fn character(&self, c: char, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(escape::quote(&c.to_string(), '\'', "", escape::unicode))
+	}
+
+	fn null(&self, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from("null"))
+	}
+
+	/// A reference to a variable or function, see [`Context::is_variable`].
+	fn identifier(&self, name: &str, cx: &mut Context) -> Result<String, GenError> {
+		cx.resolve(name)
+	}
+
+	/// Whether an argument or parameter list may end in a comma, which
+	/// [`Context::list`] adds when it puts the items on lines of their own.
+	fn trailing_commas(&self) -> bool {
+		false
+	}
+
+	/// The convention that names of `kind` are written in when
+	/// [`RenderOptions::naming`](crate::RenderOptions::naming) asks for it.
+	fn naming_convention(&self, _kind: NameKind) -> Option<Case> {
+		None
+	}
+
+	/// Names that cannot be used as identifiers as they are.
+	fn keywords(&self) -> &'static [&'static str] {
+		&[]
+	}
+
+	/// How a name that is one of the [`Backend::keywords`] is written instead.
+	fn escape_keyword(&self, name: &str) -> String {
+		format!("{}_", name)
+	}
+
+	fn function_call(
+		&self,
+		name: &Expr,
+		args: &[Expr],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}({})", name.render(cx)?, cx.list(args)?))
+	}
+
+	/// An instance of the struct `name`, see [`Context::declared_order`].
+	fn struct_literal(
+		&self,
+		_name: &Ident,
+		_fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Err(cx.unsupported("struct literals"))
+	}
+
+	fn field_access(
+		&self,
+		object: &Expr,
+		field: &Ident,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}.{}",
+			receiver(object, cx)?,
+			cx.field_name(field.as_str())?
+		))
+	}
+
+	fn method_call(
+		&self,
+		object: &Expr,
+		name: &Ident,
+		args: &[Expr],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}.{}({})",
+			receiver(object, cx)?,
+			name.render(cx)?,
+			cx.list(args)?
+		))
+	}
+
+	fn static_call(
+		&self,
+		class: &Ident,
+		name: &Ident,
+		args: &[Expr],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}.{}({})",
+			cx.type_name(class.as_str())?,
+			name.render(cx)?,
+			cx.list(args)?
+		))
+	}
+
+	/// The variant `variant` of the enum `enumeration`.
+	fn variant(
+		&self,
+		_enumeration: &Ident,
+		_variant: &Ident,
+		_fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Err(cx.unsupported("enum variants"))
+	}
+
+	/// A call of the constructor of `class`.
+	fn new_instance(
+		&self,
+		class: &Ident,
+		args: &[Expr],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"new {}({})",
+			cx.type_name(class.as_str())?,
+			cx.list(args)?
+		))
+	}
+
+	fn operator(&self, operator: Operator) -> &'static str {
+		operator.symbol()
+	}
+
+	/// How tightly `operator` binds, see [`Context::operand`].
+	fn precedence(&self, operator: Operator) -> (u8, Associativity) {
+		operator.precedence()
+	}
+
+	fn binary(
+		&self,
+		operator: Operator,
+		lhs: &Expr,
+		rhs: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let lhs = cx.operand(operator, lhs, false)?;
+		let rhs = cx.operand(operator, rhs, true)?;
+		Ok(cx.infix(&lhs, self.operator(operator), &rhs))
+	}
+
+	fn value(&self, value: &Value, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match value {
+			Value::True => "true",
+			Value::False => "false",
+		}))
+	}
+}
@@ -0,0 +1,494 @@
+use itertools::Itertools;
+
+use crate::{
+	backends::{escape, receiver, Context, Counter},
+	block::{Conditional, ForEach, ForRange, While},
+	ir::{Expr, Operator, Type},
+	statement::VariableInit,
+	types::Primitive,
+	Backend, Case, Class, Comment, Constructor, Field, Function, GenError, Ident, Import, Method,
+	NameKind, Parameter, Receiver, Scope, Struct, ToCode, Visibility,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct PHP;
+
+const KEYWORDS: &[&str] = &[
+	"abstract",
+	"and",
+	"array",
+	"as",
+	"break",
+	"callable",
+	"case",
+	"catch",
+	"class",
+	"clone",
+	"const",
+	"continue",
+	"declare",
+	"default",
+	"do",
+	"echo",
+	"else",
+	"elseif",
+	"empty",
+	"enddeclare",
+	"endfor",
+	"endforeach",
+	"endif",
+	"endswitch",
+	"endwhile",
+	"eval",
+	"exit",
+	"extends",
+	"final",
+	"finally",
+	"fn",
+	"for",
+	"foreach",
+	"function",
+	"global",
+	"goto",
+	"if",
+	"implements",
+	"include",
+	"include_once",
+	"instanceof",
+	"insteadof",
+	"interface",
+	"isset",
+	"list",
+	"match",
+	"namespace",
+	"new",
+	"or",
+	"print",
+	"private",
+	"protected",
+	"public",
+	"readonly",
+	"require",
+	"require_once",
+	"return",
+	"static",
+	"switch",
+	"throw",
+	"trait",
+	"try",
+	"unset",
+	"use",
+	"var",
+	"while",
+	"xor",
+	"yield",
+];
+
+const HEADER: &str = "<?php\n";
+
+/// How many loops a `break` or `continue` with `label` leaves.
+fn levels(label: Option<&str>, construct: &str, cx: &Context) -> Result<usize, GenError> {
+	Ok(cx.loops().len() - cx.loop_target(label, construct)?)
+}
+
+impl Backend for PHP {
+	fn name(&self) -> &'static str {
+		"PHP"
+	}
+
+	fn extension(&self) -> &'static str {
+		"php"
+	}
+
+	fn keywords(&self) -> &'static [&'static str] {
+		KEYWORDS
+	}
+
+	fn naming_convention(&self, _kind: NameKind) -> Option<Case> {
+		Some(Case::Camel)
+	}
+
+	/// The outermost scope is a whole file, which starts with the opening tag.
+	fn scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		let code = cx.children(scope)?.join("");
+		Ok(if cx.depth() == 1 {
+			format!("{}{}", HEADER, code)
+		} else {
+			code
+		})
+	}
+
+	fn run_scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		let code = scope.render(cx)?;
+		Ok(if cx.depth() == 1 {
+			format!("{}{}", HEADER, code)
+		} else {
+			code
+		})
+	}
+
+	fn function(&self, function: &Function, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"function {}({}){}{{{}}}",
+			function.name.render(cx)?,
+			cx.join(&function.params, ",")?,
+			match function.return_type {
+				Some(ref typ) => format!(":{}", typ.render(cx)?),
+				None => String::new(),
+			},
+			function.scope.render(cx)?
+		))
+	}
+
+	fn parameter(&self, parameter: &Parameter, cx: &mut Context) -> Result<String, GenError> {
+		let Parameter(name, typ) = parameter;
+		Ok(match typ {
+			Some(typ) => format!("{} {}", typ.render(cx)?, name.render(cx)?),
+			None => name.render(cx)?,
+		})
+	}
+
+	/// Functions are always global.
+	fn visibility(&self, _visibility: Visibility, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::new())
+	}
+
+	/// A class whose constructor promotes its parameters to properties.
+	fn structure(&self, structure: &Struct, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"class {}{{public function __construct({}){{}}}}",
+			cx.type_name(structure.name.as_str())?,
+			cx.join(&structure.fields, ",")?
+		))
+	}
+
+	fn field(&self, field: &Field, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{} {} ${}",
+			match field.visibility {
+				Visibility::Public => "public",
+				Visibility::Private => "private",
+			},
+			field.typ.render(cx)?,
+			cx.field_name(field.name.as_str())?
+		))
+	}
+
+	fn class(&self, class: &Class, cx: &mut Context) -> Result<String, GenError> {
+		let mut members = Vec::new();
+		for (idx, (visibility, member)) in class.members(cx)?.into_iter().enumerate() {
+			members.push(if idx < class.fields.len() {
+				format!("{};", member)
+			} else {
+				format!(
+					"{} {}",
+					match visibility {
+						Visibility::Public => "public",
+						Visibility::Private => "private",
+					},
+					member
+				)
+			});
+		}
+		Ok(format!(
+			"class {}{{{}}}",
+			cx.type_name(class.name.as_str())?,
+			members.join("")
+		))
+	}
+
+	fn constructor(
+		&self,
+		_class: &Class,
+		constructor: &Constructor,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let params = cx.join(&constructor.params, ",")?;
+		let mut fields = String::new();
+		for (field, value) in &constructor.fields {
+			fields.push_str(&format!(
+				"$this->{}={};",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!("function __construct({}){{{}}}", params, fields))
+	}
+
+	fn method(&self, method: &Method, cx: &mut Context) -> Result<String, GenError> {
+		let function = &method.function;
+		Ok(format!(
+			"{}function {}({}){}{{{}}}",
+			match method.receiver {
+				Receiver::Static => "static ",
+				_ => "",
+			},
+			function.name.render(cx)?,
+			cx.join(&function.params, ",")?,
+			match function.return_type {
+				Some(ref typ) => format!(":{}", typ.render(cx)?),
+				None => String::new(),
+			},
+			function.scope.render(cx)?
+		))
+	}
+
+	fn this(&self, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from("$this"))
+	}
+
+	fn conditional(&self, conditional: &Conditional, cx: &mut Context) -> Result<String, GenError> {
+		let mut branches = Vec::new();
+		for (condition, scope) in &conditional.branches {
+			branches.push(format!(
+				"if({}){{{}}}",
+				condition.render(cx)?,
+				scope.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}{}",
+			branches.iter().join("else"),
+			match conditional.otherwise {
+				Some(ref last) => format!("else{{{}}}", last.render(cx)?),
+				None => String::new(),
+			}
+		))
+	}
+
+	fn while_loop(&self, while_loop: &While, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"while({}){{{}}}",
+			while_loop.condition.render(cx)?,
+			while_loop.scope.render(cx)?
+		))
+	}
+
+	fn for_range(&self, for_range: &ForRange, cx: &mut Context) -> Result<String, GenError> {
+		let counter = Counter::new(for_range, cx)?;
+		Ok(format!(
+			"for({}={};{};{}){{{}}}",
+			counter.var,
+			counter.start,
+			counter.condition,
+			counter.increment(),
+			for_range.scope.render(cx)?
+		))
+	}
+
+	fn for_each(&self, for_each: &ForEach, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"foreach({} as {}){{{}}}",
+			for_each.iterable.render(cx)?,
+			for_each.var.render(cx)?,
+			for_each.scope.render(cx)?
+		))
+	}
+
+	/// Outer loops are left by how many loops to leave.
+	fn break_statement(&self, label: Option<&str>, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match levels(label, "statement::Break", cx)? {
+			1 => String::from("break;"),
+			levels => format!("break {};", levels),
+		})
+	}
+
+	fn continue_statement(
+		&self,
+		label: Option<&str>,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(match levels(label, "statement::Continue", cx)? {
+			1 => String::from("continue;"),
+			levels => format!("continue {};", levels),
+		})
+	}
+
+	fn variable_init(&self, init: &VariableInit, cx: &mut Context) -> Result<String, GenError> {
+		let VariableInit { name, value, .. } = init;
+		Ok(format!("{}={};", name.render(cx)?, value.render(cx)?))
+	}
+
+	fn assign_variable(
+		&self,
+		name: &Expr,
+		value: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}={};", name.render(cx)?, value.render(cx)?))
+	}
+
+	fn return_statement(&self, value: Option<&Expr>, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match value {
+			Some(expr) => format!("return {};", expr.render(cx)?),
+			None => String::from("return;"),
+		})
+	}
+
+	fn comment(&self, comment: &Comment, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match comment {
+			Comment::Regular(content) => format!("//{}\n", content),
+			Comment::MultiLine(content) => format!("/*{}*/", content),
+			Comment::Doc(content) => format!("/**{}*/", content),
+		})
+	}
+
+	fn import(&self, import: &Import, cx: &mut Context) -> Result<String, GenError> {
+		match import {
+			Import::Module(location) => Ok(format!("require_once \"{}\";", location)),
+			Import::Members(..) => Err(cx.unsupported("importing members")),
+		}
+	}
+
+	fn println(&self, value: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("echo {},PHP_EOL;", value.render(cx)?))
+	}
+
+	fn expression_statement(&self, expr: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("{};", expr.render(cx)?))
+	}
+
+	fn ternary(
+		&self,
+		condition: &Expr,
+		if_true: &Expr,
+		if_false: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}?{}:{}",
+			condition.render(cx)?,
+			if_true.render(cx)?,
+			if_false.render(cx)?
+		))
+	}
+
+	fn primitive_type(&self, primitive: Primitive, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match primitive {
+			Primitive::Integer => "int",
+			Primitive::Number | Primitive::Float => "float",
+			Primitive::Boolean => "bool",
+		}))
+	}
+
+	fn generic_type(
+		&self,
+		_name: &str,
+		_args: &[Type],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Err(cx.unsupported("types::Generic"))
+	}
+
+	fn future_type(&self, _value: &Type, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("types::Future"))
+	}
+
+	/// Variables are prefixed with `$`, unlike functions, so only function
+	/// names can clash with [`Backend::keywords`].
+	fn identifier(&self, name: &str, cx: &mut Context) -> Result<String, GenError> {
+		if cx.is_variable(name) {
+			let variable = format!("${}", cx.convention(name));
+			cx.reserve(name, variable)
+		} else {
+			cx.resolve(name)
+		}
+	}
+
+	/// The callee is a function even if a variable of the same name is in
+	/// scope, e.g. `count($count)`.
+	fn function_call(
+		&self,
+		name: &Expr,
+		args: &[Expr],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let callee = match name {
+			Expr::Ident(name) => cx.resolve(name.as_str())?,
+			_ => name.render(cx)?,
+		};
+		Ok(format!("{}({})", callee, cx.list(args)?))
+	}
+
+	fn operator(&self, operator: Operator) -> &'static str {
+		match operator {
+			Operator::Equal => "===",
+			Operator::NotEqual => "!==",
+			_ => operator.symbol(),
+		}
+	}
+
+	/// `$` interpolates variables into double quoted strings.
+	fn string(&self, text: &str, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(escape::quote(text, '"', "$", escape::braced))
+	}
+
+	fn character(&self, c: char, cx: &mut Context) -> Result<String, GenError> {
+		self.string(&c.to_string(), cx)
+	}
+
+	/// The constructor, with named arguments.
+	fn struct_literal(
+		&self,
+		name: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let mut values = Vec::new();
+		for (field, value) in fields {
+			values.push(format!(
+				"{}:{}",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!(
+			"new {}({})",
+			cx.type_name(name.as_str())?,
+			values.join(",")
+		))
+	}
+
+	fn field_access(
+		&self,
+		object: &Expr,
+		field: &Ident,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}->{}",
+			receiver(object, cx)?,
+			cx.field_name(field.as_str())?
+		))
+	}
+
+	fn method_call(
+		&self,
+		object: &Expr,
+		name: &Ident,
+		args: &[Expr],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}->{}({})",
+			receiver(object, cx)?,
+			name.render(cx)?,
+			cx.list(args)?
+		))
+	}
+
+	fn static_call(
+		&self,
+		class: &Ident,
+		name: &Ident,
+		args: &[Expr],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}::{}({})",
+			cx.type_name(class.as_str())?,
+			name.render(cx)?,
+			cx.list(args)?
+		))
+	}
+}
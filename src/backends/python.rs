@@ -0,0 +1,678 @@
+use itertools::Itertools;
+
+use crate::{
+	backends::{c_like, escape, layout, prepend, variant_type, Context},
+	block::{Conditional, ForEach, ForRange, Match, While},
+	ir::{Associativity, Expr, Operator, Type},
+	statement::VariableInit,
+	types::Primitive,
+	Backend, Case, Class, Comment, Constructor, Enum, Function, GenError, Ident, Import, Interface,
+	Language, Method, NameKind, Parameter, Receiver, Scope, Signature, Struct, ToCode, Value,
+	Visibility,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Python;
+
+const KEYWORDS: &[&str] = &[
+	"False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+	"def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+	"in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+	"with", "yield", "match", "case", "type",
+];
+
+/// Whether the [`Language::Python`] being rendered includes type hints.
+fn include_types(cx: &Context) -> bool {
+	match cx.language() {
+		Language::Python { include_types, .. } => include_types,
+		_ => false,
+	}
+}
+
+/// The body of a block, which has to contain at least one statement.
+fn body(scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+	let children = cx.children(scope)?;
+	Ok(if children.is_empty() {
+		layout::block(vec![String::from("pass")])
+	} else {
+		layout::block(children)
+	})
+}
+
+/// The `def` line of a method, with `@staticmethod` above it if it has no
+/// instance and `@abstractmethod` below that if it `is_abstract`.
+fn signature(
+	is_abstract: bool,
+	name: &Ident,
+	receiver: Receiver,
+	params: &[Parameter],
+	return_type: Option<&Type>,
+	cx: &mut Context,
+) -> Result<String, GenError> {
+	let params = cx.join(params, ",")?;
+	Ok(format!(
+		"{}{}def {}({}{}{}){}:",
+		match receiver {
+			Receiver::Static => format!("@staticmethod{}", layout::NEWLINE),
+			_ => String::new(),
+		},
+		if is_abstract {
+			format!("@abstractmethod{}", layout::NEWLINE)
+		} else {
+			String::new()
+		},
+		name.render(cx)?,
+		match receiver {
+			Receiver::Static => "",
+			_ => "self",
+		},
+		if receiver == Receiver::Static || params.is_empty() {
+			""
+		} else {
+			","
+		},
+		params,
+		match return_type {
+			Some(typ) if include_types(cx) => format!("->{}", typ.render(cx)?),
+			_ => String::new(),
+		}
+	))
+}
+
+/// The [`Language::Python`] release being targeted.
+fn version(cx: &Context) -> (u8, u8) {
+	match cx.language() {
+		Language::Python { version, .. } => version,
+		_ => (3, 10),
+	}
+}
+
+impl Backend for Python {
+	fn name(&self) -> &'static str {
+		"Python"
+	}
+
+	fn extension(&self) -> &'static str {
+		"py"
+	}
+
+	fn trailing_commas(&self) -> bool {
+		true
+	}
+
+	fn keywords(&self) -> &'static [&'static str] {
+		KEYWORDS
+	}
+
+	fn naming_convention(&self, _kind: NameKind) -> Option<Case> {
+		Some(Case::Snake)
+	}
+
+	/// Requirements are qualified names, which are imported from their module.
+	fn finish(&self, code: String, cx: &mut Context) -> Result<String, GenError> {
+		let imports = cx
+			.requirements()
+			.iter()
+			.sorted_by_key(|itm| !itm.starts_with("__future__."))
+			.map(|itm| match itm.rsplit_once('.') {
+				Some((module, name)) => format!("from {} import {}\n", module, name),
+				None => format!("import {}\n", itm),
+			})
+			.join("");
+		Ok(format!("{}{}", imports, code))
+	}
+
+	/// Every child is on a line of its own.
+	fn scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		Ok(layout::lines(cx.children(scope)?))
+	}
+
+	fn run_scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		scope.render(cx)
+	}
+
+	fn function(&self, function: &Function, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"def {}({}){}:{}",
+			function.name.render(cx)?,
+			cx.join(&function.params, ",")?,
+			match function.return_type {
+				Some(ref typ) if include_types(cx) => format!("->{}", typ.render(cx)?),
+				_ => String::new(),
+			},
+			body(&function.scope, cx)?
+		))
+	}
+
+	fn parameter(&self, parameter: &Parameter, cx: &mut Context) -> Result<String, GenError> {
+		let Parameter(name, typ) = parameter;
+		Ok(format!(
+			"{}{}",
+			name.render(cx)?,
+			match typ {
+				Some(typ) if include_types(cx) => format!(":{}", typ.render(cx)?),
+				_ => String::new(),
+			}
+		))
+	}
+
+	fn visibility(&self, _visibility: Visibility, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("visibility"))
+	}
+
+	/// A `@dataclass`, whose fields are always annotated. Python has no
+	/// private fields.
+	fn structure(&self, structure: &Struct, cx: &mut Context) -> Result<String, GenError> {
+		cx.require("dataclasses.dataclass");
+		let fields = structure
+			.fields
+			.iter()
+			.map(|field| field.render(cx))
+			.collect::<Result<Vec<_>, _>>()?;
+		Ok(format!(
+			"@dataclass{}class {}:{}",
+			layout::NEWLINE,
+			cx.type_name(structure.name.as_str())?,
+			layout::block(if fields.is_empty() {
+				vec![String::from("pass")]
+			} else {
+				fields
+			})
+		))
+	}
+
+	/// The fields are only annotated in the class body when types are
+	/// included, as the constructor assigns them. Annotations are postponed so
+	/// that methods can refer to their own class. Python has no private
+	/// members.
+	fn class(&self, class: &Class, cx: &mut Context) -> Result<String, GenError> {
+		let typed = include_types(cx);
+		if typed {
+			cx.require("__future__.annotations");
+		}
+		let fields = if typed { 0 } else { class.fields.len() };
+		let lines = class
+			.members(cx)?
+			.into_iter()
+			.skip(fields)
+			.map(|(_, member)| member)
+			.collect::<Vec<_>>();
+		let name = cx.type_name(class.name.as_str())?;
+		let mut interfaces = Vec::new();
+		for interface in &class.interfaces {
+			interfaces.push(cx.type_name(interface.as_str())?);
+		}
+		Ok(format!(
+			"class {}{}:{}",
+			name,
+			if interfaces.is_empty() {
+				String::new()
+			} else {
+				format!("({})", interfaces.join(","))
+			},
+			layout::block(if lines.is_empty() {
+				vec![String::from("pass")]
+			} else {
+				lines
+			})
+		))
+	}
+
+	fn constructor(
+		&self,
+		_class: &Class,
+		constructor: &Constructor,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let params = cx.join(&constructor.params, ",")?;
+		let mut fields = Vec::new();
+		for (field, value) in &constructor.fields {
+			fields.push(format!(
+				"self.{}={}",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!(
+			"def __init__(self{}{}):{}",
+			if params.is_empty() { "" } else { "," },
+			params,
+			layout::block(if fields.is_empty() {
+				vec![String::from("pass")]
+			} else {
+				fields
+			})
+		))
+	}
+
+	fn method(&self, method: &Method, cx: &mut Context) -> Result<String, GenError> {
+		let function = &method.function;
+		Ok(format!(
+			"{}{}",
+			signature(
+				false,
+				&function.name,
+				method.receiver,
+				&function.params,
+				function.return_type.as_ref(),
+				cx
+			)?,
+			body(&function.scope, cx)?
+		))
+	}
+
+	/// An abstract base class, which classes that implement it inherit from.
+	fn interface(&self, interface: &Interface, cx: &mut Context) -> Result<String, GenError> {
+		cx.require("abc.ABC");
+		cx.require("abc.abstractmethod");
+		let methods = interface
+			.methods
+			.iter()
+			.map(|method| method.render(cx))
+			.collect::<Result<Vec<_>, _>>()?;
+		Ok(format!(
+			"class {}(ABC):{}",
+			cx.type_name(interface.name.as_str())?,
+			layout::block(if methods.is_empty() {
+				vec![String::from("pass")]
+			} else {
+				methods
+			})
+		))
+	}
+
+	fn signature(&self, signature: &Signature, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}{}",
+			self::signature(
+				true,
+				&signature.name,
+				signature.receiver,
+				&signature.params,
+				signature.return_type.as_ref(),
+				cx,
+			)?,
+			layout::block(vec![String::from("pass")])
+		))
+	}
+
+	fn this(&self, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from("self"))
+	}
+
+	/// An `Enum` if it is C-like, otherwise a base class with a dataclass
+	/// for every variant. The dataclasses are module level, so variants of two
+	/// enums cannot share a name.
+	fn enumeration(&self, enumeration: &Enum, cx: &mut Context) -> Result<String, GenError> {
+		let name = cx.type_name(enumeration.name.as_str())?;
+		if enumeration.is_c_like() {
+			cx.require("enum.Enum");
+			let mut variants = Vec::new();
+			for (idx, variant) in enumeration.variants.iter().enumerate() {
+				variants.push(format!(
+					"{}={}",
+					cx.type_name(variant.name.as_str())?,
+					idx + 1
+				));
+			}
+			return Ok(format!(
+				"class {}(Enum):{}",
+				name,
+				layout::block(if variants.is_empty() {
+					vec![String::from("pass")]
+				} else {
+					variants
+				})
+			));
+		}
+		cx.require("dataclasses.dataclass");
+		let mut classes = vec![format!(
+			"class {}:{}",
+			name,
+			layout::block(vec![String::from("pass")])
+		)];
+		for variant in &enumeration.variants {
+			let fields = variant
+				.fields
+				.iter()
+				.map(|field| field.render(cx))
+				.collect::<Result<Vec<_>, _>>()?;
+			classes.push(format!(
+				"@dataclass{}class {}({}):{}",
+				layout::NEWLINE,
+				variant_type(&enumeration.name, &variant.name, cx)?,
+				name,
+				layout::block(if fields.is_empty() {
+					vec![String::from("pass")]
+				} else {
+					fields
+				})
+			));
+		}
+		Ok(layout::lines(classes))
+	}
+
+	fn conditional(&self, conditional: &Conditional, cx: &mut Context) -> Result<String, GenError> {
+		let mut branches = Vec::new();
+		for (idx, (condition, scope)) in conditional.branches.iter().enumerate() {
+			branches.push(format!(
+				"{} {}:{}",
+				if idx == 0 { "if" } else { "elif" },
+				condition.render(cx)?,
+				body(scope, cx)?
+			));
+		}
+		if let Some(ref last) = conditional.otherwise {
+			branches.push(format!("else:{}", body(last, cx)?));
+		}
+		Ok(branches.join(&layout::NEWLINE.to_string()))
+	}
+
+	/// A `match` statement from Python 3.10 on, and an `if` chain before.
+	fn match_block(
+		&self,
+		match_block: &Match,
+		enumeration: &Enum,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let c_like = enumeration.is_c_like();
+		let name = cx.type_name(enumeration.name.as_str())?;
+		let subject = match_block.subject.render(cx)?;
+		let mut branches = Vec::new();
+		if version(cx) >= (3, 10) {
+			for arm in &match_block.arms {
+				let pattern = if c_like {
+					format!("{}.{}", name, cx.type_name(arm.variant.as_str())?)
+				} else {
+					let variant = variant_type(&enumeration.name, &arm.variant, cx)?;
+					let mut bindings = Vec::new();
+					for binding in &arm.bindings {
+						bindings.push(format!(
+							"{}={}",
+							cx.field_name(binding.as_str())?,
+							binding.render(cx)?
+						));
+					}
+					format!("{}({})", variant, bindings.join(","))
+				};
+				branches.push(format!("case {}:{}", pattern, body(&arm.scope, cx)?));
+			}
+			if let Some(ref otherwise) = match_block.otherwise {
+				branches.push(format!("case _:{}", body(otherwise, cx)?));
+			}
+			return Ok(format!("match {}:{}", subject, layout::block(branches)));
+		}
+		for (idx, arm) in match_block.arms.iter().enumerate() {
+			let variant = if c_like {
+				cx.type_name(arm.variant.as_str())?
+			} else {
+				variant_type(&enumeration.name, &arm.variant, cx)?
+			};
+			let mut bindings = Vec::new();
+			for binding in &arm.bindings {
+				bindings.push(format!(
+					"{}={}.{}",
+					binding.render(cx)?,
+					subject,
+					cx.field_name(binding.as_str())?
+				));
+			}
+			branches.push(format!(
+				"{} {}:{}",
+				if idx == 0 { "if" } else { "elif" },
+				if c_like {
+					format!("{}=={}.{}", subject, name, variant)
+				} else {
+					format!("isinstance({},{})", subject, variant)
+				},
+				body(&prepend(&arm.scope, bindings), cx)?
+			));
+		}
+		if let Some(ref otherwise) = match_block.otherwise {
+			branches.push(if branches.is_empty() {
+				layout::lines(cx.children(otherwise)?)
+			} else {
+				format!("else:{}", body(otherwise, cx)?)
+			});
+		}
+		Ok(layout::lines(branches))
+	}
+
+	fn while_loop(&self, while_loop: &While, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"while {}:{}",
+			while_loop.condition.render(cx)?,
+			body(&while_loop.scope, cx)?
+		))
+	}
+
+	/// A range from zero in steps of one is written as `range(end)`.
+	fn for_range(&self, for_range: &ForRange, cx: &mut Context) -> Result<String, GenError> {
+		let mut args = Vec::new();
+		if !matches!(for_range.start, Expr::Int(0)) || for_range.step != 1 {
+			args.push(for_range.start.clone());
+		}
+		args.push(for_range.end.clone());
+		if for_range.step != 1 {
+			args.push(Expr::Int(for_range.step));
+		}
+		Ok(format!(
+			"for {} in range({}):{}",
+			for_range.var.render(cx)?,
+			cx.list(&args)?,
+			body(&for_range.scope, cx)?
+		))
+	}
+
+	fn for_each(&self, for_each: &ForEach, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"for {} in {}:{}",
+			for_each.var.render(cx)?,
+			for_each.iterable.render(cx)?,
+			body(&for_each.scope, cx)?
+		))
+	}
+
+	fn break_statement(&self, label: Option<&str>, cx: &mut Context) -> Result<String, GenError> {
+		cx.innermost_loop(label, "statement::Break")?;
+		Ok(String::from("break"))
+	}
+
+	fn continue_statement(
+		&self,
+		label: Option<&str>,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		cx.innermost_loop(label, "statement::Continue")?;
+		Ok(String::from("continue"))
+	}
+
+	fn variable_init(&self, init: &VariableInit, cx: &mut Context) -> Result<String, GenError> {
+		let VariableInit {
+			name, typ, value, ..
+		} = init;
+		Ok(format!(
+			"{}{}={}",
+			name.render(cx)?,
+			match typ {
+				Some(typ) if include_types(cx) => format!(":{}", typ.render(cx)?),
+				_ => String::new(),
+			},
+			value.render(cx)?
+		))
+	}
+
+	fn assign_variable(
+		&self,
+		name: &Expr,
+		value: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}={}", name.render(cx)?, value.render(cx)?))
+	}
+
+	fn return_statement(&self, value: Option<&Expr>, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match value {
+			Some(expr) => format!("return {}", expr.render(cx)?),
+			None => String::from("return"),
+		})
+	}
+
+	fn comment(&self, comment: &Comment, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match comment {
+			Comment::Regular(content) => format!("#{}", content),
+			Comment::MultiLine(content) | Comment::Doc(content) => {
+				format!("\"\"\"{}\"\"\"", content)
+			}
+		})
+	}
+
+	fn import(&self, import: &Import, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match import {
+			Import::Module(location) => format!("import {}", location),
+			Import::Members(location, members) => {
+				format!("from {} import {}", location, members.iter().join(","))
+			}
+		})
+	}
+
+	fn println(&self, value: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("print({})", value.render(cx)?))
+	}
+
+	fn expression_statement(&self, expr: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		expr.render(cx)
+	}
+
+	fn ternary(
+		&self,
+		condition: &Expr,
+		if_true: &Expr,
+		if_false: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{} if {} else {}",
+			if_true.render(cx)?,
+			condition.render(cx)?,
+			if_false.render(cx)?
+		))
+	}
+
+	fn primitive_type(&self, primitive: Primitive, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match primitive {
+			Primitive::Integer => "int",
+			Primitive::Number | Primitive::Float => "float",
+			Primitive::Boolean => "bool",
+		}))
+	}
+
+	fn generic_type(
+		&self,
+		name: &str,
+		args: &[Type],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}[{}]", name, cx.join(args, ",")?))
+	}
+
+	fn future_type(&self, _value: &Type, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("types::Future"))
+	}
+
+	/// Comparisons chain, so `a<b==c` is `a<b and b==c`.
+	fn precedence(&self, operator: Operator) -> (u8, Associativity) {
+		operator.flat_precedence()
+	}
+
+	fn operator(&self, operator: Operator) -> &'static str {
+		match operator {
+			Operator::And => " and ",
+			Operator::Or => " or ",
+			_ => operator.symbol(),
+		}
+	}
+
+	fn value(&self, value: &Value, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match value {
+			Value::True => "True",
+			Value::False => "False",
+		}))
+	}
+
+	/// Strings are never f-strings, so braces are written as is.
+	fn string(&self, text: &str, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(escape::quote(text, '"', "", escape::hex))
+	}
+
+	fn character(&self, c: char, cx: &mut Context) -> Result<String, GenError> {
+		self.string(&c.to_string(), cx)
+	}
+
+	fn null(&self, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from("None"))
+	}
+
+	fn struct_literal(
+		&self,
+		name: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let mut values = Vec::new();
+		for (field, value) in fields {
+			values.push(format!(
+				"{}={}",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}({})",
+			cx.type_name(name.as_str())?,
+			cx.list(&values)?
+		))
+	}
+
+	fn new_instance(
+		&self,
+		class: &Ident,
+		args: &[Expr],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}({})",
+			cx.type_name(class.as_str())?,
+			cx.list(args)?
+		))
+	}
+
+	fn variant(
+		&self,
+		enumeration: &Ident,
+		variant: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		if c_like(enumeration, fields, cx) {
+			return Ok(format!(
+				"{}.{}",
+				cx.type_name(enumeration.as_str())?,
+				cx.type_name(variant.as_str())?
+			));
+		}
+		let mut values = Vec::new();
+		for (field, value) in fields {
+			values.push(format!(
+				"{}={}",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}({})",
+			variant_type(enumeration, variant, cx)?,
+			cx.list(&values)?
+		))
+	}
+}
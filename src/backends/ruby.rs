@@ -0,0 +1,270 @@
+use itertools::Itertools;
+
+use crate::{
+	backends::{escape, offset, Context},
+	block::{Conditional, ForEach, ForRange, While},
+	ir::{Expr, Stmt, Type},
+	statement::VariableInit,
+	types::Primitive,
+	Backend, Case, Comment, Function, GenError, Ident, Import, NameKind, Parameter, Scope, Struct,
+	ToCode, Visibility,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Ruby;
+
+const KEYWORDS: &[&str] = &[
+	"BEGIN", "END", "__FILE__", "__LINE__", "alias", "and", "begin", "break", "case", "class",
+	"def", "defined?", "do", "else", "elsif", "end", "ensure", "false", "for", "if", "in",
+	"module", "next", "nil", "not", "or", "redo", "rescue", "retry", "return", "self", "super",
+	"then", "true", "undef", "unless", "until", "when", "while", "yield",
+];
+
+impl Backend for Ruby {
+	fn name(&self) -> &'static str {
+		"Ruby"
+	}
+
+	fn extension(&self) -> &'static str {
+		"rb"
+	}
+
+	fn keywords(&self) -> &'static [&'static str] {
+		KEYWORDS
+	}
+
+	fn naming_convention(&self, _kind: NameKind) -> Option<Case> {
+		Some(Case::Snake)
+	}
+
+	fn scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		Ok(cx.children(scope)?.join(""))
+	}
+
+	fn run_scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		scope.render(cx)
+	}
+
+	/// A method returns its last expression, so a final `return` is left out.
+	fn function(&self, function: &Function, cx: &mut Context) -> Result<String, GenError> {
+		let mut body = function.scope.clone();
+		if let Some(last) = body.children_mut().last_mut() {
+			if let Stmt::Return(Some(value)) = last {
+				*last = Stmt::Expr(value.clone());
+			}
+		}
+		Ok(format!(
+			"def {}({});{}end;",
+			function.name.render(cx)?,
+			cx.join(&function.params, ",")?,
+			body.render(cx)?
+		))
+	}
+
+	fn parameter(&self, parameter: &Parameter, cx: &mut Context) -> Result<String, GenError> {
+		parameter.0.render(cx)
+	}
+
+	/// Methods defined at the top level are always private to `Object`.
+	fn visibility(&self, _visibility: Visibility, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::new())
+	}
+
+	/// A `Struct` class with keyword arguments. Ruby has no private fields.
+	fn structure(&self, structure: &Struct, cx: &mut Context) -> Result<String, GenError> {
+		let mut members = Vec::new();
+		for field in &structure.fields {
+			members.push(format!(":{}", cx.field_name(field.name.as_str())?));
+		}
+		members.push(String::from("keyword_init:true"));
+		Ok(format!(
+			"{}=Struct.new({});",
+			cx.type_name(structure.name.as_str())?,
+			members.join(",")
+		))
+	}
+
+	fn conditional(&self, conditional: &Conditional, cx: &mut Context) -> Result<String, GenError> {
+		let mut branches = Vec::new();
+		for (idx, (condition, scope)) in conditional.branches.iter().enumerate() {
+			branches.push(format!(
+				"{} {};{}",
+				if idx == 0 { "if" } else { "elsif" },
+				condition.render(cx)?,
+				scope.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}{}end;",
+			branches.iter().join(""),
+			match conditional.otherwise {
+				Some(ref last) => format!("else;{}", last.render(cx)?),
+				None => String::new(),
+			}
+		))
+	}
+
+	fn while_loop(&self, while_loop: &While, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"while {};{}end;",
+			while_loop.condition.render(cx)?,
+			while_loop.scope.render(cx)?
+		))
+	}
+
+	/// Iterates over an exclusive range, or steps towards the inclusive limit
+	/// before `end` when the step is not one.
+	fn for_range(&self, for_range: &ForRange, cx: &mut Context) -> Result<String, GenError> {
+		let var = for_range.var.render(cx)?;
+		let start = for_range.start.render(cx)?;
+		let iterator = if for_range.step == 1 {
+			format!("({}...{}).each", start, for_range.end.render(cx)?)
+		} else {
+			let limit = offset(&for_range.end, -for_range.step.signum());
+			format!(
+				"{}.step({},{})",
+				match for_range.start {
+					Expr::Binary(..) | Expr::Ternary(..) => format!("({})", start),
+					_ => start,
+				},
+				limit.render(cx)?,
+				for_range.step
+			)
+		};
+		Ok(format!(
+			"{} do |{}|;{}end;",
+			iterator,
+			var,
+			for_range.scope.render(cx)?
+		))
+	}
+
+	fn for_each(&self, for_each: &ForEach, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}.each do |{}|;{}end;",
+			for_each.iterable.render(cx)?,
+			for_each.var.render(cx)?,
+			for_each.scope.render(cx)?
+		))
+	}
+
+	fn break_statement(&self, label: Option<&str>, cx: &mut Context) -> Result<String, GenError> {
+		cx.innermost_loop(label, "statement::Break")?;
+		Ok(String::from("break;"))
+	}
+
+	fn continue_statement(
+		&self,
+		label: Option<&str>,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		cx.innermost_loop(label, "statement::Continue")?;
+		Ok(String::from("next;"))
+	}
+
+	fn variable_init(&self, init: &VariableInit, cx: &mut Context) -> Result<String, GenError> {
+		let VariableInit { name, value, .. } = init;
+		Ok(format!("{}={};", name.render(cx)?, value.render(cx)?))
+	}
+
+	fn assign_variable(
+		&self,
+		name: &Expr,
+		value: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}={};", name.render(cx)?, value.render(cx)?))
+	}
+
+	fn return_statement(&self, value: Option<&Expr>, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match value {
+			Some(expr) => format!("return {};", expr.render(cx)?),
+			None => String::from("return;"),
+		})
+	}
+
+	/// `=begin` and `=end` only count at the start of a line.
+	fn comment(&self, comment: &Comment, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match comment {
+			Comment::Regular(content) | Comment::Doc(content) => format!("#{}\n", content),
+			Comment::MultiLine(content) => format!("\n=begin\n{}\n=end\n", content),
+		})
+	}
+
+	fn import(&self, import: &Import, cx: &mut Context) -> Result<String, GenError> {
+		match import {
+			Import::Module(location) => Ok(format!("require_relative \"{}\";", location)),
+			Import::Members(..) => Err(cx.unsupported("importing members")),
+		}
+	}
+
+	fn println(&self, value: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("puts({});", value.render(cx)?))
+	}
+
+	fn expression_statement(&self, expr: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("{};", expr.render(cx)?))
+	}
+
+	/// A `?` right after a name is part of the name.
+	fn ternary(
+		&self,
+		condition: &Expr,
+		if_true: &Expr,
+		if_false: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{} ? {} : {}",
+			condition.render(cx)?,
+			if_true.render(cx)?,
+			if_false.render(cx)?
+		))
+	}
+
+	fn primitive_type(&self, primitive: Primitive, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match primitive {
+			Primitive::Integer => "Integer",
+			Primitive::Number | Primitive::Float => "Float",
+			Primitive::Boolean => "Boolean",
+		}))
+	}
+
+	fn future_type(&self, _value: &Type, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("types::Future"))
+	}
+
+	/// `#` starts an interpolation in double quoted strings.
+	fn string(&self, text: &str, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(escape::quote(text, '"', "#", escape::unicode))
+	}
+
+	fn character(&self, c: char, cx: &mut Context) -> Result<String, GenError> {
+		self.string(&c.to_string(), cx)
+	}
+
+	fn null(&self, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from("nil"))
+	}
+
+	fn struct_literal(
+		&self,
+		name: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let mut values = Vec::new();
+		for (field, value) in fields {
+			values.push(format!(
+				"{}:{}",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}.new({})",
+			cx.type_name(name.as_str())?,
+			values.join(",")
+		))
+	}
+}
@@ -0,0 +1,671 @@
+use itertools::Itertools;
+
+use crate::{
+	backends::{escape, offset, Context},
+	block::{Conditional, ForEach, ForRange, Match, While},
+	ir::{Associativity, Expr, Operator, Type},
+	statement::VariableInit,
+	types::Primitive,
+	Backend, Case, Class, Comment, Constructor, Enum, Field, Function, FunctionBuilder, GenError,
+	Ident, Import, Interface, Method, NameKind, Parameter, Receiver, Scope, Signature, Struct,
+	ToCode, Visibility,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Rust;
+
+const KEYWORDS: &[&str] = &[
+	"as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+	"false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+	"ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+	"unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+	"override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+/// The `'label:` in front of a loop.
+fn label(label: Option<&str>) -> String {
+	label.map_or_else(String::new, |label| format!("'{}:", label))
+}
+
+/// `fn name(params)->typ` with the receiver in front of the parameters.
+fn signature(
+	name: &Ident,
+	receiver: Receiver,
+	params: &[Parameter],
+	return_type: Option<&Type>,
+	cx: &mut Context,
+) -> Result<String, GenError> {
+	let receiver = match receiver {
+		Receiver::Static => "",
+		Receiver::Shared => "&self",
+		Receiver::Mutable => "&mut self",
+	};
+	let params = cx.list(params)?;
+	Ok(format!(
+		"fn {}({}{}{}){}",
+		name.render(cx)?,
+		receiver,
+		if receiver.is_empty() || params.is_empty() {
+			""
+		} else {
+			","
+		},
+		params,
+		match return_type {
+			Some(r_type) => format!("->{}", r_type.render(cx)?),
+			None => String::new(),
+		}
+	))
+}
+
+impl Backend for Rust {
+	fn name(&self) -> &'static str {
+		"Rust"
+	}
+
+	fn extension(&self) -> &'static str {
+		"rs"
+	}
+
+	fn trailing_commas(&self) -> bool {
+		true
+	}
+
+	fn keywords(&self) -> &'static [&'static str] {
+		KEYWORDS
+	}
+
+	fn naming_convention(&self, _kind: NameKind) -> Option<Case> {
+		Some(Case::Snake)
+	}
+
+	/// Raw identifiers, except for the keywords that cannot be raw.
+	fn escape_keyword(&self, name: &str) -> String {
+		match name {
+			"crate" | "self" | "Self" | "super" => format!("{}_", name),
+			_ => format!("r#{}", name),
+		}
+	}
+
+	fn scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		let children = cx.children(scope)?;
+		Ok(cx.statements(children))
+	}
+
+	fn run_scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		FunctionBuilder::new()
+			.name("main")
+			.with_scope(scope.clone())
+			.build()
+			.unwrap()
+			.render(cx)
+	}
+
+	fn function(&self, function: &Function, cx: &mut Context) -> Result<String, GenError> {
+		let params = cx.list(&function.params)?;
+		let body = function.scope.render(cx)?;
+		Ok(format!(
+			"{}{}fn {}({}){}{}",
+			function.visibility.render(cx)?,
+			match function.visibility {
+				Visibility::Public => " ",
+				_ => "",
+			},
+			function.name.render(cx)?,
+			params,
+			match function.return_type {
+				Some(ref r_type) => format!("->{}", r_type.render(cx)?),
+				None => String::new(),
+			},
+			cx.braces(&body)
+		))
+	}
+
+	fn parameter(&self, parameter: &Parameter, cx: &mut Context) -> Result<String, GenError> {
+		let Parameter(name, typ) = parameter;
+		match typ {
+			Some(typ) => Ok(format!("{}:{}", name.render(cx)?, typ.render(cx)?)),
+			None => Err(cx.missing_type("parameter")),
+		}
+	}
+
+	fn visibility(&self, visibility: Visibility, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match visibility {
+			Visibility::Public => "pub",
+			Visibility::Private => "",
+		}))
+	}
+
+	fn structure(&self, structure: &Struct, cx: &mut Context) -> Result<String, GenError> {
+		let fields = structure
+			.fields
+			.iter()
+			.map(|field| Ok(format!("{},", field.render(cx)?)))
+			.collect::<Result<Vec<_>, GenError>>()?;
+		Ok(format!(
+			"{}{}struct {}{}",
+			if structure.derives.is_empty() {
+				String::new()
+			} else {
+				format!("#[derive({})]", structure.derives.join(","))
+			},
+			match structure.visibility {
+				Visibility::Public => "pub ",
+				Visibility::Private => "",
+			},
+			cx.type_name(structure.name.as_str())?,
+			cx.braces(&cx.statements(fields))
+		))
+	}
+
+	fn field(&self, field: &Field, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}{}:{}",
+			match field.visibility {
+				Visibility::Public => "pub ",
+				Visibility::Private => "",
+			},
+			cx.field_name(field.name.as_str())?,
+			field.typ.render(cx)?
+		))
+	}
+
+	/// A `struct` with the fields, followed by an `impl` block with the
+	/// constructor and the other methods, if there are any, and an `impl`
+	/// block for every interface.
+	fn class(&self, class: &Class, cx: &mut Context) -> Result<String, GenError> {
+		let mut fields = class.members(cx)?;
+		let mut items = fields.split_off(class.fields.len());
+		let methods = items.split_off(items.len() - class.methods.len());
+		let mut implementations = vec![Vec::new(); class.interfaces.len()];
+		for (method, (_, code)) in class.methods.iter().zip(methods) {
+			let interface = cx
+				.implementing(method.function.name.as_str())
+				.map(|interface| interface.name.clone());
+			match interface.and_then(|name| class.interfaces.iter().position(|itm| *itm == name)) {
+				Some(idx) => implementations[idx].push(code),
+				None => items.push((method.function.visibility, code)),
+			}
+		}
+		let name = cx.type_name(class.name.as_str())?;
+		let mut code = format!(
+			"{}struct {}{}",
+			match class.visibility {
+				Visibility::Public => "pub ",
+				Visibility::Private => "",
+			},
+			name,
+			cx.braces(&cx.statements(fields.into_iter().map(|(_, field)| field + ",").collect()))
+		);
+		if !items.is_empty() {
+			code.push_str(&format!(
+				"impl {}{}",
+				name,
+				cx.braces(&cx.statements(items.into_iter().map(|(_, item)| item).collect()))
+			));
+		}
+		for (interface, methods) in class.interfaces.iter().zip(implementations) {
+			code.push_str(&format!(
+				"impl {} for {}{}",
+				cx.type_name(interface.as_str())?,
+				name,
+				cx.braces(&cx.statements(methods))
+			));
+		}
+		Ok(code)
+	}
+
+	/// An associated function called `new`.
+	fn constructor(
+		&self,
+		_class: &Class,
+		constructor: &Constructor,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let params = cx.list(&constructor.params)?;
+		let mut fields = Vec::new();
+		for (field, value) in &constructor.fields {
+			fields.push(format!(
+				"{}:{}",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		let fields = format!("Self{{{}}}", cx.list(&fields)?);
+		Ok(format!(
+			"{}fn new({})->Self{}",
+			match constructor.visibility {
+				Visibility::Public => "pub ",
+				Visibility::Private => "",
+			},
+			params,
+			cx.braces(&fields)
+		))
+	}
+
+	/// Methods that implement an interface go in its `impl` block, where
+	/// they cannot have a visibility.
+	fn method(&self, method: &Method, cx: &mut Context) -> Result<String, GenError> {
+		let function = &method.function;
+		let implementing = cx.implementing(function.name.as_str()).is_some();
+		let signature = signature(
+			&function.name,
+			method.receiver,
+			&function.params,
+			function.return_type.as_ref(),
+			cx,
+		)?;
+		let body = function.scope.render(cx)?;
+		Ok(format!(
+			"{}{}{}",
+			match function.visibility {
+				Visibility::Public if !implementing => "pub ",
+				_ => "",
+			},
+			signature,
+			cx.braces(&body)
+		))
+	}
+
+	fn interface(&self, interface: &Interface, cx: &mut Context) -> Result<String, GenError> {
+		let methods = interface
+			.methods
+			.iter()
+			.map(|method| method.render(cx))
+			.collect::<Result<Vec<_>, _>>()?;
+		Ok(format!(
+			"{}trait {}{}",
+			match interface.visibility {
+				Visibility::Public => "pub ",
+				Visibility::Private => "",
+			},
+			cx.type_name(interface.name.as_str())?,
+			cx.braces(&cx.statements(methods))
+		))
+	}
+
+	fn signature(&self, signature: &Signature, cx: &mut Context) -> Result<String, GenError> {
+		Ok(self::signature(
+			&signature.name,
+			signature.receiver,
+			&signature.params,
+			signature.return_type.as_ref(),
+			cx,
+		)? + ";")
+	}
+
+	fn this(&self, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from("self"))
+	}
+
+	fn enumeration(&self, enumeration: &Enum, cx: &mut Context) -> Result<String, GenError> {
+		let mut variants = Vec::new();
+		for variant in &enumeration.variants {
+			let name = cx.type_name(variant.name.as_str())?;
+			variants.push(if variant.fields.is_empty() {
+				format!("{},", name)
+			} else {
+				format!("{}{{{}}},", name, cx.list(&variant.fields)?)
+			});
+		}
+		Ok(format!(
+			"{}enum {}{}",
+			match enumeration.visibility {
+				Visibility::Public => "pub ",
+				Visibility::Private => "",
+			},
+			cx.type_name(enumeration.name.as_str())?,
+			cx.braces(&cx.statements(variants))
+		))
+	}
+
+	fn conditional(&self, conditional: &Conditional, cx: &mut Context) -> Result<String, GenError> {
+		let mut branches = Vec::new();
+		for (condition, scope) in &conditional.branches {
+			let condition = condition.render(cx)?;
+			let body = scope.render(cx)?;
+			branches.push(format!("if {}{}", condition, cx.braces(&body)));
+		}
+		Ok(format!(
+			"{}{};",
+			branches.join(&cx.continuation("else ")),
+			match conditional.otherwise {
+				Some(ref last) => {
+					let body = last.render(cx)?;
+					format!("{}{}", cx.continuation("else"), cx.braces(&body))
+				}
+				None => String::new(),
+			}
+		))
+	}
+
+	/// Binds the fields of an arm with a struct pattern, which ignores the
+	/// rest with `..`.
+	fn match_block(
+		&self,
+		match_block: &Match,
+		enumeration: &Enum,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let name = cx.type_name(enumeration.name.as_str())?;
+		let mut arms = Vec::new();
+		for arm in &match_block.arms {
+			let variant = enumeration.variant(arm.variant.as_str()).unwrap();
+			let mut bindings = Vec::new();
+			for binding in &arm.bindings {
+				let field = cx.field_name(binding.as_str())?;
+				let var = binding.render(cx)?;
+				bindings.push(if field == var {
+					var
+				} else {
+					format!("{}:{}", field, var)
+				});
+			}
+			if bindings.len() < variant.fields.len() {
+				bindings.push(String::from(".."));
+			}
+			let body = arm.scope.render(cx)?;
+			arms.push(format!(
+				"{}::{}{}=>{}",
+				name,
+				cx.type_name(arm.variant.as_str())?,
+				if variant.fields.is_empty() {
+					String::new()
+				} else {
+					format!("{{{}}}", bindings.join(","))
+				},
+				cx.braces(&body)
+			));
+		}
+		if let Some(ref otherwise) = match_block.otherwise {
+			let body = otherwise.render(cx)?;
+			arms.push(format!("_=>{}", cx.braces(&body)));
+		}
+		let subject = match_block.subject.render(cx)?;
+		Ok(format!(
+			"match {}{}",
+			subject,
+			cx.braces(&cx.statements(arms))
+		))
+	}
+
+	fn while_loop(&self, while_loop: &While, cx: &mut Context) -> Result<String, GenError> {
+		let condition = while_loop.condition.render(cx)?;
+		let body = while_loop.scope.render(cx)?;
+		Ok(format!(
+			"{}while {}{}",
+			label(while_loop.label.as_deref()),
+			condition,
+			cx.braces(&body)
+		))
+	}
+
+	/// Counting down walks the inclusive range from `end+1` to `start` in
+	/// reverse.
+	fn for_range(&self, for_range: &ForRange, cx: &mut Context) -> Result<String, GenError> {
+		let var = for_range.var.render(cx)?;
+		let range = if for_range.step > 0 {
+			format!(
+				"{}..{}",
+				for_range.start.render(cx)?,
+				for_range.end.render(cx)?
+			)
+		} else {
+			let after = offset(&for_range.end, 1);
+			format!(
+				"({}..={}).rev()",
+				after.render(cx)?,
+				for_range.start.render(cx)?
+			)
+		};
+		let range = match for_range.step.unsigned_abs() {
+			1 => range,
+			step if for_range.step > 0 => format!("({}).step_by({})", range, step),
+			step => format!("{}.step_by({})", range, step),
+		};
+		let body = for_range.scope.render(cx)?;
+		Ok(format!(
+			"{}for {} in {}{}",
+			label(for_range.label.as_deref()),
+			var,
+			range,
+			cx.braces(&body)
+		))
+	}
+
+	fn for_each(&self, for_each: &ForEach, cx: &mut Context) -> Result<String, GenError> {
+		let var = for_each.var.render(cx)?;
+		let iterable = for_each.iterable.render(cx)?;
+		let body = for_each.scope.render(cx)?;
+		Ok(format!(
+			"{}for {} in {}{}",
+			label(for_each.label.as_deref()),
+			var,
+			iterable,
+			cx.braces(&body)
+		))
+	}
+
+	fn break_statement(&self, label: Option<&str>, cx: &mut Context) -> Result<String, GenError> {
+		cx.loop_target(label, "statement::Break")?;
+		Ok(match label {
+			Some(label) => format!("break '{};", label),
+			None => String::from("break;"),
+		})
+	}
+
+	fn continue_statement(
+		&self,
+		label: Option<&str>,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		cx.loop_target(label, "statement::Continue")?;
+		Ok(match label {
+			Some(label) => format!("continue '{};", label),
+			None => String::from("continue;"),
+		})
+	}
+
+	fn variable_init(&self, init: &VariableInit, cx: &mut Context) -> Result<String, GenError> {
+		let VariableInit {
+			name,
+			mutable,
+			typ,
+			value,
+		} = init;
+		Ok(format!(
+			"let {}{}{}={};",
+			match mutable {
+				Some(true) => "mut ",
+				_ => "",
+			},
+			name.render(cx)?,
+			match typ {
+				Some(typ) => format!(":{}", typ.render(cx)?),
+				None => String::new(),
+			},
+			value.render(cx)?
+		))
+	}
+
+	fn assign_variable(
+		&self,
+		name: &Expr,
+		value: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}={};", name.render(cx)?, value.render(cx)?))
+	}
+
+	fn return_statement(&self, value: Option<&Expr>, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match value {
+			Some(expr) => format!("return {};", expr.render(cx)?),
+			None => String::from("return;"),
+		})
+	}
+
+	fn comment(&self, comment: &Comment, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match comment {
+			Comment::Regular(content) => format!("//{}\n", content),
+			Comment::MultiLine(content) => format!("/*{}*/", content),
+			Comment::Doc(content) => format!("///{}\n", content),
+		})
+	}
+
+	fn import(&self, import: &Import, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match import {
+			Import::Module(location) => format!("mod {};", location),
+			Import::Members(location, members) => {
+				format!("use {}::{{{}}};", location, members.iter().join(","))
+			}
+		})
+	}
+
+	fn println(&self, value: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("println!(\"{{:?}}\",{});", value.render(cx)?))
+	}
+
+	fn expression_statement(&self, expr: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("{};", expr.render(cx)?))
+	}
+
+	fn ternary(
+		&self,
+		condition: &Expr,
+		if_true: &Expr,
+		if_false: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"if {}{{{}}}else{{{}}}",
+			condition.render(cx)?,
+			if_true.render(cx)?,
+			if_false.render(cx)?
+		))
+	}
+
+	fn primitive_type(&self, primitive: Primitive, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match primitive {
+			Primitive::Integer => "i32",
+			Primitive::Number | Primitive::Float => "f32",
+			Primitive::Boolean => "bool",
+		}))
+	}
+
+	fn future_type(&self, value: &Type, cx: &mut Context) -> Result<String, GenError> {
+		self.generic_type("Future", std::slice::from_ref(value), cx)
+	}
+
+	/// Comparisons cannot be chained.
+	fn precedence(&self, operator: Operator) -> (u8, Associativity) {
+		operator.flat_precedence()
+	}
+
+	/// Raw strings are used for text with quotes or backslashes unless it has
+	/// characters that have to be escaped.
+	fn string(&self, text: &str, _cx: &mut Context) -> Result<String, GenError> {
+		if text.contains(['"', '\\']) && !text.contains(escape::is_control) {
+			let mut hashes = String::new();
+			while text.contains(&format!("\"{}", hashes)) {
+				hashes.push('#');
+			}
+			return Ok(format!("r{}\"{}\"{}", hashes, text, hashes));
+		}
+		Ok(escape::quote(text, '"', "", escape::braced))
+	}
+
+	fn character(&self, c: char, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(escape::quote(&c.to_string(), '\'', "", escape::braced))
+	}
+
+	fn suffixed_integer(
+		&self,
+		value: i64,
+		suffix: Primitive,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		match suffix {
+			Primitive::Boolean => Err(cx.unsupported("boolean literal suffixes")),
+			_ => Ok(format!("{}{}", value, self.primitive_type(suffix, cx)?)),
+		}
+	}
+
+	fn null(&self, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from("None"))
+	}
+
+	fn struct_literal(
+		&self,
+		name: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let mut values = Vec::new();
+		for (field, value) in fields {
+			values.push(format!(
+				"{}:{}",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}{{{}}}",
+			cx.type_name(name.as_str())?,
+			cx.list(&values)?
+		))
+	}
+
+	fn static_call(
+		&self,
+		class: &Ident,
+		name: &Ident,
+		args: &[Expr],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}::{}({})",
+			cx.type_name(class.as_str())?,
+			name.render(cx)?,
+			cx.list(args)?
+		))
+	}
+
+	fn new_instance(
+		&self,
+		class: &Ident,
+		args: &[Expr],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}::new({})",
+			cx.type_name(class.as_str())?,
+			cx.list(args)?
+		))
+	}
+
+	fn variant(
+		&self,
+		enumeration: &Ident,
+		variant: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let name = format!(
+			"{}::{}",
+			cx.type_name(enumeration.as_str())?,
+			cx.type_name(variant.as_str())?
+		);
+		if fields.is_empty() {
+			return Ok(name);
+		}
+		let mut values = Vec::new();
+		for (field, value) in fields {
+			values.push(format!(
+				"{}:{}",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!("{}{{{}}}", name, cx.list(&values)?))
+	}
+}
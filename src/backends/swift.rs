@@ -0,0 +1,379 @@
+use itertools::Itertools;
+
+use crate::{
+	backends::{escape, label, Context},
+	block::{Conditional, ForEach, ForRange, While},
+	ir::{Associativity, Expr, Operator, Type},
+	statement::VariableInit,
+	types::Primitive,
+	Backend, Case, Comment, Field, Function, GenError, Ident, Import, NameKind, Parameter, Scope,
+	Struct, ToCode, Visibility,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Swift;
+
+const KEYWORDS: &[&str] = &[
+	"Any",
+	"Self",
+	"as",
+	"associatedtype",
+	"await",
+	"break",
+	"case",
+	"catch",
+	"class",
+	"continue",
+	"default",
+	"defer",
+	"deinit",
+	"do",
+	"else",
+	"enum",
+	"extension",
+	"fallthrough",
+	"false",
+	"fileprivate",
+	"for",
+	"func",
+	"guard",
+	"if",
+	"import",
+	"in",
+	"init",
+	"inout",
+	"internal",
+	"is",
+	"let",
+	"nil",
+	"open",
+	"operator",
+	"precedencegroup",
+	"private",
+	"protocol",
+	"public",
+	"repeat",
+	"rethrows",
+	"return",
+	"self",
+	"static",
+	"struct",
+	"subscript",
+	"super",
+	"switch",
+	"throw",
+	"throws",
+	"true",
+	"try",
+	"typealias",
+	"var",
+	"where",
+	"while",
+];
+
+impl Backend for Swift {
+	fn name(&self) -> &'static str {
+		"Swift"
+	}
+
+	fn extension(&self) -> &'static str {
+		"swift"
+	}
+
+	fn keywords(&self) -> &'static [&'static str] {
+		KEYWORDS
+	}
+
+	fn naming_convention(&self, _kind: NameKind) -> Option<Case> {
+		Some(Case::Camel)
+	}
+
+	fn escape_keyword(&self, name: &str) -> String {
+		format!("`{}`", name)
+	}
+
+	fn scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		Ok(cx.children(scope)?.join(""))
+	}
+
+	/// Swift runs the top-level statements of `main.swift`.
+	fn run_scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		scope.render(cx)
+	}
+
+	/// A function returning a [`Type::Future`] is declared `async` and returns
+	/// the value it resolves to.
+	fn function(&self, function: &Function, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{} func {}({}){}{{{}}}",
+			function.visibility.render(cx)?,
+			function.name.render(cx)?,
+			cx.join(&function.params, ",")?,
+			match function.return_type {
+				Some(Type::Future(ref value)) => format!(" async->{}", value.render(cx)?),
+				Some(ref typ) => format!("->{}", typ.render(cx)?),
+				None => String::new(),
+			},
+			function.scope.render(cx)?
+		))
+	}
+
+	/// Parameters have no argument label so that calls look the same as in
+	/// every other language.
+	fn parameter(&self, parameter: &Parameter, cx: &mut Context) -> Result<String, GenError> {
+		let Parameter(name, typ) = parameter;
+		match typ {
+			Some(typ) => Ok(format!("_ {}:{}", name.render(cx)?, typ.render(cx)?)),
+			None => Err(cx.missing_type("parameter")),
+		}
+	}
+
+	fn visibility(&self, visibility: Visibility, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match visibility {
+			Visibility::Public => "public",
+			Visibility::Private => "private",
+		}))
+	}
+
+	/// Public fields are left internal, which is how far their struct is
+	/// visible unless it is public.
+	fn structure(&self, structure: &Struct, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}struct {}{{{}}}",
+			match structure.visibility {
+				Visibility::Public => "public ",
+				Visibility::Private => "",
+			},
+			cx.type_name(structure.name.as_str())?,
+			cx.join(&structure.fields, ";")?
+		))
+	}
+
+	fn field(&self, field: &Field, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}let {}:{}",
+			match field.visibility {
+				Visibility::Public => "",
+				Visibility::Private => "private ",
+			},
+			cx.field_name(field.name.as_str())?,
+			field.typ.render(cx)?
+		))
+	}
+
+	fn conditional(&self, conditional: &Conditional, cx: &mut Context) -> Result<String, GenError> {
+		let mut branches = Vec::new();
+		for (condition, scope) in &conditional.branches {
+			branches.push(format!(
+				"if {}{{{}}}",
+				condition.render(cx)?,
+				scope.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}{}",
+			branches.iter().join("else "),
+			match conditional.otherwise {
+				Some(ref last) => format!("else{{{}}}", last.render(cx)?),
+				None => String::new(),
+			}
+		))
+	}
+
+	fn while_loop(&self, while_loop: &While, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}while {}{{{}}}",
+			label(while_loop.label.as_deref(), ": "),
+			while_loop.condition.render(cx)?,
+			while_loop.scope.render(cx)?
+		))
+	}
+
+	/// Steps other than one go through `stride`.
+	fn for_range(&self, for_range: &ForRange, cx: &mut Context) -> Result<String, GenError> {
+		let range = if for_range.step == 1 {
+			format!(
+				"{}..<{}",
+				for_range.start.render(cx)?,
+				for_range.end.render(cx)?
+			)
+		} else {
+			format!(
+				"stride(from:{},to:{},by:{})",
+				for_range.start.render(cx)?,
+				for_range.end.render(cx)?,
+				for_range.step
+			)
+		};
+		Ok(format!(
+			"{}for {} in {}{{{}}}",
+			label(for_range.label.as_deref(), ": "),
+			for_range.var.render(cx)?,
+			range,
+			for_range.scope.render(cx)?
+		))
+	}
+
+	fn for_each(&self, for_each: &ForEach, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}for {} in {}{{{}}}",
+			label(for_each.label.as_deref(), ": "),
+			for_each.var.render(cx)?,
+			for_each.iterable.render(cx)?,
+			for_each.scope.render(cx)?
+		))
+	}
+
+	fn break_statement(&self, label: Option<&str>, cx: &mut Context) -> Result<String, GenError> {
+		cx.loop_target(label, "statement::Break")?;
+		Ok(match label {
+			Some(label) => format!("break {};", label),
+			None => String::from("break;"),
+		})
+	}
+
+	fn continue_statement(
+		&self,
+		label: Option<&str>,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		cx.loop_target(label, "statement::Continue")?;
+		Ok(match label {
+			Some(label) => format!("continue {};", label),
+			None => String::from("continue;"),
+		})
+	}
+
+	fn variable_init(&self, init: &VariableInit, cx: &mut Context) -> Result<String, GenError> {
+		let VariableInit {
+			name,
+			mutable,
+			typ,
+			value,
+		} = init;
+		Ok(format!(
+			"{} {}{}={};",
+			match mutable {
+				Some(true) => "var",
+				Some(false) | None => "let",
+			},
+			name.render(cx)?,
+			match typ {
+				Some(typ) => format!(":{}", typ.render(cx)?),
+				None => String::new(),
+			},
+			value.render(cx)?
+		))
+	}
+
+	fn assign_variable(
+		&self,
+		name: &Expr,
+		value: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}={};", name.render(cx)?, value.render(cx)?))
+	}
+
+	fn return_statement(&self, value: Option<&Expr>, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match value {
+			Some(expr) => format!("return {};", expr.render(cx)?),
+			None => String::from("return;"),
+		})
+	}
+
+	fn comment(&self, comment: &Comment, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match comment {
+			Comment::Regular(content) => format!("//{}\n", content),
+			Comment::MultiLine(content) => format!("/*{}*/", content),
+			Comment::Doc(content) => format!("///{}\n", content),
+		})
+	}
+
+	/// Importing single declarations needs their kind, which is unknown here.
+	fn import(&self, import: &Import, cx: &mut Context) -> Result<String, GenError> {
+		match import {
+			Import::Module(location) => Ok(format!("import {}\n", location)),
+			Import::Members(..) => Err(cx.unsupported("importing members")),
+		}
+	}
+
+	fn println(&self, value: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("print({});", value.render(cx)?))
+	}
+
+	fn expression_statement(&self, expr: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("{};", expr.render(cx)?))
+	}
+
+	/// The `?` has to be surrounded by spaces, or it is parsed as optional
+	/// chaining.
+	fn ternary(
+		&self,
+		condition: &Expr,
+		if_true: &Expr,
+		if_false: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{} ? {} : {}",
+			condition.render(cx)?,
+			if_true.render(cx)?,
+			if_false.render(cx)?
+		))
+	}
+
+	fn primitive_type(&self, primitive: Primitive, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match primitive {
+			Primitive::Integer => "Int",
+			Primitive::Number | Primitive::Float => "Double",
+			Primitive::Boolean => "Bool",
+		}))
+	}
+
+	/// Futures that are not returned from a function are tasks that cannot
+	/// fail.
+	fn future_type(&self, value: &Type, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("Task<{},Never>", value.render(cx)?))
+	}
+
+	fn precedence(&self, operator: Operator) -> (u8, Associativity) {
+		operator.flat_precedence()
+	}
+
+	fn string(&self, text: &str, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(escape::quote(text, '"', "", escape::braced))
+	}
+
+	/// Characters are written like strings.
+	fn character(&self, c: char, cx: &mut Context) -> Result<String, GenError> {
+		self.string(&c.to_string(), cx)
+	}
+
+	fn null(&self, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from("nil"))
+	}
+
+	/// The memberwise initializer, whose arguments are labeled and in the
+	/// order the fields were declared in.
+	fn struct_literal(
+		&self,
+		name: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let mut values = Vec::new();
+		for (field, value) in cx.declared_order(name.as_str(), fields) {
+			values.push(format!(
+				"{}:{}",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}({})",
+			cx.type_name(name.as_str())?,
+			cx.list(&values)?
+		))
+	}
+}
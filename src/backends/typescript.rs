@@ -0,0 +1,645 @@
+use itertools::Itertools;
+
+use crate::{
+	backends::{c_like, label, prepend, receiver, Context, Counter},
+	block::{Conditional, ForEach, ForRange, Match, While},
+	ir::{Expr, Operator, Type},
+	statement::VariableInit,
+	types::Primitive,
+	Backend, Case, Class, Comment, Constructor, Enum, Function, GenError, Ident, Import, Interface,
+	Method, NameKind, Parameter, Receiver, Scope, Signature, Struct, ToCode, Visibility,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct TypeScript;
+
+const KEYWORDS: &[&str] = &[
+	"arguments",
+	"await",
+	"break",
+	"case",
+	"catch",
+	"class",
+	"const",
+	"continue",
+	"debugger",
+	"default",
+	"delete",
+	"do",
+	"else",
+	"enum",
+	"eval",
+	"export",
+	"extends",
+	"false",
+	"finally",
+	"for",
+	"function",
+	"if",
+	"implements",
+	"import",
+	"in",
+	"instanceof",
+	"interface",
+	"let",
+	"new",
+	"null",
+	"package",
+	"private",
+	"protected",
+	"public",
+	"return",
+	"static",
+	"super",
+	"switch",
+	"this",
+	"throw",
+	"true",
+	"try",
+	"typeof",
+	"var",
+	"void",
+	"while",
+	"with",
+	"yield",
+];
+
+/// Renders `function`, with type annotations only when `typed` so that
+/// [`super::JavaScript`] can share it.
+pub(super) fn function(
+	function: &Function,
+	typed: bool,
+	cx: &mut Context,
+) -> Result<String, GenError> {
+	let visibility = function.visibility.render(cx)?;
+	let params = cx.list(&function.params)?;
+	let body = function.scope.render(cx)?;
+	Ok(format!(
+		"{}{}function {}({}){}{}",
+		visibility,
+		if visibility.is_empty() { "" } else { " " },
+		function.name.render(cx)?,
+		params,
+		match function.return_type {
+			Some(ref typ) if typed => format!(":{}", typ.render(cx)?),
+			None if typed => String::from(":void"),
+			_ => String::new(),
+		},
+		cx.braces(&body)
+	))
+}
+
+/// Renders `constructor`, see [`function`].
+pub(super) fn constructor(constructor: &Constructor, cx: &mut Context) -> Result<String, GenError> {
+	let params = cx.list(&constructor.params)?;
+	let mut fields = Vec::new();
+	for (field, value) in &constructor.fields {
+		fields.push(format!(
+			"this.{}={};",
+			cx.field_name(field.as_str())?,
+			value.render(cx)?
+		));
+	}
+	Ok(format!(
+		"constructor({}){}",
+		params,
+		cx.braces(&cx.statements(fields))
+	))
+}
+
+/// Renders `method`, see [`function`].
+pub(super) fn method(method: &Method, typed: bool, cx: &mut Context) -> Result<String, GenError> {
+	let function = &method.function;
+	let params = cx.list(&function.params)?;
+	let body = function.scope.render(cx)?;
+	Ok(format!(
+		"{}{}({}){}{}",
+		match method.receiver {
+			Receiver::Static => "static ",
+			_ => "",
+		},
+		function.name.render(cx)?,
+		params,
+		match function.return_type {
+			Some(ref typ) if typed => format!(":{}", typ.render(cx)?),
+			None if typed => String::from(":void"),
+			_ => String::new(),
+		},
+		cx.braces(&body)
+	))
+}
+
+/// Renders `init`, see [`function`].
+pub(super) fn variable_init(
+	init: &VariableInit,
+	typed: bool,
+	cx: &mut Context,
+) -> Result<String, GenError> {
+	let VariableInit {
+		name,
+		mutable,
+		typ,
+		value,
+	} = init;
+	Ok(format!(
+		"{} {}{}={};",
+		match mutable {
+			Some(true) => "let",
+			Some(false) | None => "const",
+		},
+		name.render(cx)?,
+		match typ {
+			Some(typ) if typed => format!(":{}", typ.render(cx)?),
+			_ => String::new(),
+		},
+		value.render(cx)?
+	))
+}
+
+/// Whether `structure` is declared as an `interface` rather than a `class`,
+/// which it is unless it has private fields.
+fn is_interface(structure: &Struct) -> bool {
+	structure
+		.fields
+		.iter()
+		.all(|field| field.visibility == Visibility::Public)
+}
+
+/// `new name(...)` with the value of each field, see
+/// [`Context::declared_order`].
+pub(super) fn construct(
+	name: &Ident,
+	fields: &[(Ident, Expr)],
+	cx: &mut Context,
+) -> Result<String, GenError> {
+	let values = cx
+		.declared_order(name.as_str(), fields)
+		.into_iter()
+		.map(|(_, value)| value)
+		.collect::<Vec<_>>();
+	Ok(format!(
+		"new {}({})",
+		cx.type_name(name.as_str())?,
+		cx.list(values)?
+	))
+}
+
+impl Backend for TypeScript {
+	fn name(&self) -> &'static str {
+		"TypeScript"
+	}
+
+	fn extension(&self) -> &'static str {
+		"ts"
+	}
+
+	fn trailing_commas(&self) -> bool {
+		true
+	}
+
+	fn keywords(&self) -> &'static [&'static str] {
+		KEYWORDS
+	}
+
+	fn naming_convention(&self, _kind: NameKind) -> Option<Case> {
+		Some(Case::Camel)
+	}
+
+	fn scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		let children = cx.children(scope)?;
+		Ok(cx.statements(children))
+	}
+
+	fn run_scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		scope.render(cx)
+	}
+
+	fn function(&self, function: &Function, cx: &mut Context) -> Result<String, GenError> {
+		self::function(function, true, cx)
+	}
+
+	fn parameter(&self, parameter: &Parameter, cx: &mut Context) -> Result<String, GenError> {
+		let Parameter(name, typ) = parameter;
+		Ok(match typ {
+			Some(typ) => format!("{}:{}", name.render(cx)?, typ.render(cx)?),
+			None => format!("{}:any", name.render(cx)?),
+		})
+	}
+
+	fn visibility(&self, visibility: Visibility, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match visibility {
+			Visibility::Public => "export",
+			Visibility::Private => "",
+		}))
+	}
+
+	/// An `interface`, or a `class` whose constructor declares the fields if
+	/// any of them are private.
+	fn structure(&self, structure: &Struct, cx: &mut Context) -> Result<String, GenError> {
+		let export = match structure.visibility {
+			Visibility::Public => "export ",
+			Visibility::Private => "",
+		};
+		let name = cx.type_name(structure.name.as_str())?;
+		if is_interface(structure) {
+			let fields = structure
+				.fields
+				.iter()
+				.map(|field| Ok(format!("{};", field.render(cx)?)))
+				.collect::<Result<Vec<_>, GenError>>()?;
+			return Ok(format!(
+				"{}interface {}{}",
+				export,
+				name,
+				cx.braces(&cx.statements(fields))
+			));
+		}
+		let mut params = Vec::new();
+		for field in &structure.fields {
+			params.push(format!(
+				"{} {}",
+				match field.visibility {
+					Visibility::Public => "public",
+					Visibility::Private => "private",
+				},
+				field.render(cx)?
+			));
+		}
+		let constructor = format!("constructor({}){{}}", cx.list(&params)?);
+		Ok(format!(
+			"{}class {}{}",
+			export,
+			name,
+			cx.braces(&constructor)
+		))
+	}
+
+	fn class(&self, class: &Class, cx: &mut Context) -> Result<String, GenError> {
+		let mut members = Vec::new();
+		for (idx, (visibility, member)) in class.members(cx)?.into_iter().enumerate() {
+			members.push(format!(
+				"{}{}{}",
+				match visibility {
+					Visibility::Public => "",
+					Visibility::Private => "private ",
+				},
+				member,
+				if idx < class.fields.len() { ";" } else { "" }
+			));
+		}
+		let name = cx.type_name(class.name.as_str())?;
+		let mut interfaces = Vec::new();
+		for interface in &class.interfaces {
+			interfaces.push(cx.type_name(interface.as_str())?);
+		}
+		Ok(format!(
+			"{}class {}{}{}",
+			match class.visibility {
+				Visibility::Public => "export ",
+				Visibility::Private => "",
+			},
+			name,
+			if interfaces.is_empty() {
+				String::new()
+			} else {
+				format!(" implements {}", cx.list(&interfaces)?)
+			},
+			cx.braces(&cx.statements(members))
+		))
+	}
+
+	fn interface(&self, interface: &Interface, cx: &mut Context) -> Result<String, GenError> {
+		let methods = interface
+			.methods
+			.iter()
+			.map(|method| method.render(cx))
+			.collect::<Result<Vec<_>, _>>()?;
+		Ok(format!(
+			"{}interface {}{}",
+			match interface.visibility {
+				Visibility::Public => "export ",
+				Visibility::Private => "",
+			},
+			cx.type_name(interface.name.as_str())?,
+			cx.braces(&cx.statements(methods))
+		))
+	}
+
+	/// Interfaces describe instances, so they cannot require static methods.
+	fn signature(&self, signature: &Signature, cx: &mut Context) -> Result<String, GenError> {
+		if signature.receiver == Receiver::Static {
+			return Err(cx.unsupported("static methods of interfaces"));
+		}
+		Ok(format!(
+			"{}({}):{};",
+			signature.name.render(cx)?,
+			cx.list(&signature.params)?,
+			match signature.return_type {
+				Some(ref typ) => typ.render(cx)?,
+				None => String::from("void"),
+			}
+		))
+	}
+
+	fn constructor(
+		&self,
+		_class: &Class,
+		constructor: &Constructor,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		self::constructor(constructor, cx)
+	}
+
+	fn method(&self, method: &Method, cx: &mut Context) -> Result<String, GenError> {
+		self::method(method, true, cx)
+	}
+
+	/// An `enum` if it is C-like, otherwise a union of object types that are
+	/// told apart by their `kind`.
+	fn enumeration(&self, enumeration: &Enum, cx: &mut Context) -> Result<String, GenError> {
+		let export = match enumeration.visibility {
+			Visibility::Public => "export ",
+			Visibility::Private => "",
+		};
+		let name = cx.type_name(enumeration.name.as_str())?;
+		if enumeration.is_c_like() {
+			let mut variants = Vec::new();
+			for variant in &enumeration.variants {
+				variants.push(cx.type_name(variant.name.as_str())?);
+			}
+			return Ok(format!(
+				"{}enum {}{{{}}}",
+				export,
+				name,
+				cx.list(&variants)?
+			));
+		}
+		let mut variants = Vec::new();
+		for variant in &enumeration.variants {
+			let mut fields = vec![format!("kind:\"{}\"", variant.name.as_str())];
+			for field in &variant.fields {
+				fields.push(field.render(cx)?);
+			}
+			variants.push(format!("{{{}}}", cx.list(&fields)?));
+		}
+		Ok(format!("{}type {}={};", export, name, variants.join("|")))
+	}
+
+	fn conditional(&self, conditional: &Conditional, cx: &mut Context) -> Result<String, GenError> {
+		let mut branches = Vec::new();
+		for (condition, scope) in &conditional.branches {
+			let condition = condition.render(cx)?;
+			let body = scope.render(cx)?;
+			branches.push(format!("if({}){}", condition, cx.braces(&body)));
+		}
+		Ok(format!(
+			"{}{}",
+			branches.join(&cx.continuation("else ")),
+			match conditional.otherwise {
+				Some(ref last) => {
+					let body = last.render(cx)?;
+					format!("{}{}", cx.continuation("else"), cx.braces(&body))
+				}
+				None => String::new(),
+			}
+		))
+	}
+
+	/// A `switch` over the enum, or over the `kind` of a tagged variant.
+	/// Fields are bound by reading them from the subject.
+	fn match_block(
+		&self,
+		match_block: &Match,
+		enumeration: &Enum,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let c_like = enumeration.is_c_like();
+		let name = cx.type_name(enumeration.name.as_str())?;
+		let subject = receiver(&match_block.subject, cx)?;
+		let mut cases = Vec::new();
+		for arm in &match_block.arms {
+			let mut bindings = Vec::new();
+			for binding in &arm.bindings {
+				bindings.push(format!(
+					"const {}={}.{};",
+					binding.render(cx)?,
+					subject,
+					cx.field_name(binding.as_str())?
+				));
+			}
+			let body = prepend(&arm.scope, bindings).render(cx)?;
+			cases.push(format!(
+				"case {}:{}break;",
+				if c_like {
+					format!("{}.{}", name, cx.type_name(arm.variant.as_str())?)
+				} else {
+					format!("\"{}\"", arm.variant.as_str())
+				},
+				cx.braces(&body)
+			));
+		}
+		if let Some(ref otherwise) = match_block.otherwise {
+			let body = otherwise.render(cx)?;
+			cases.push(format!("default:{}", cx.braces(&body)));
+		}
+		Ok(format!(
+			"switch({}{}){}",
+			subject,
+			if c_like { "" } else { ".kind" },
+			cx.braces(&cx.statements(cases))
+		))
+	}
+
+	fn while_loop(&self, while_loop: &While, cx: &mut Context) -> Result<String, GenError> {
+		let condition = while_loop.condition.render(cx)?;
+		let body = while_loop.scope.render(cx)?;
+		Ok(format!(
+			"{}while({}){}",
+			label(while_loop.label.as_deref(), ":"),
+			condition,
+			cx.braces(&body)
+		))
+	}
+
+	fn for_range(&self, for_range: &ForRange, cx: &mut Context) -> Result<String, GenError> {
+		let counter = Counter::new(for_range, cx)?;
+		let body = for_range.scope.render(cx)?;
+		Ok(format!(
+			"{}for(let {}={};{};{}){}",
+			label(for_range.label.as_deref(), ":"),
+			counter.var,
+			counter.start,
+			counter.condition,
+			counter.increment(),
+			cx.braces(&body)
+		))
+	}
+
+	fn for_each(&self, for_each: &ForEach, cx: &mut Context) -> Result<String, GenError> {
+		let var = for_each.var.render(cx)?;
+		let iterable = for_each.iterable.render(cx)?;
+		let body = for_each.scope.render(cx)?;
+		Ok(format!(
+			"{}for(const {} of {}){}",
+			label(for_each.label.as_deref(), ":"),
+			var,
+			iterable,
+			cx.braces(&body)
+		))
+	}
+
+	fn break_statement(&self, label: Option<&str>, cx: &mut Context) -> Result<String, GenError> {
+		cx.loop_target(label, "statement::Break")?;
+		Ok(match label {
+			Some(label) => format!("break {};", label),
+			None => String::from("break;"),
+		})
+	}
+
+	fn continue_statement(
+		&self,
+		label: Option<&str>,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		cx.loop_target(label, "statement::Continue")?;
+		Ok(match label {
+			Some(label) => format!("continue {};", label),
+			None => String::from("continue;"),
+		})
+	}
+
+	fn variable_init(&self, init: &VariableInit, cx: &mut Context) -> Result<String, GenError> {
+		self::variable_init(init, true, cx)
+	}
+
+	fn assign_variable(
+		&self,
+		name: &Expr,
+		value: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}={};", name.render(cx)?, value.render(cx)?))
+	}
+
+	fn return_statement(&self, value: Option<&Expr>, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match value {
+			Some(expr) => format!("return {};", expr.render(cx)?),
+			None => String::from("return;"),
+		})
+	}
+
+	fn comment(&self, comment: &Comment, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match comment {
+			Comment::Regular(content) => format!("//{}\n", content),
+			Comment::MultiLine(content) => format!("/*{}*/", content),
+			Comment::Doc(content) => format!("/**{}*/", content),
+		})
+	}
+
+	fn import(&self, import: &Import, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match import {
+			Import::Module(location) => format!("import \"{}\";", location),
+			Import::Members(location, members) => format!(
+				"import {{{}}} from \"{}\";",
+				members.iter().join(","),
+				location
+			),
+		})
+	}
+
+	fn println(&self, value: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("console.log({});", value.render(cx)?))
+	}
+
+	fn expression_statement(&self, expr: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("{};", expr.render(cx)?))
+	}
+
+	fn ternary(
+		&self,
+		condition: &Expr,
+		if_true: &Expr,
+		if_false: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"{}?{}:{}",
+			condition.render(cx)?,
+			if_true.render(cx)?,
+			if_false.render(cx)?
+		))
+	}
+
+	fn primitive_type(&self, primitive: Primitive, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match primitive {
+			Primitive::Integer | Primitive::Number | Primitive::Float => "number",
+			Primitive::Boolean => "boolean",
+		}))
+	}
+
+	fn future_type(&self, value: &Type, cx: &mut Context) -> Result<String, GenError> {
+		self.generic_type("Promise", std::slice::from_ref(value), cx)
+	}
+
+	fn operator(&self, operator: Operator) -> &'static str {
+		match operator {
+			Operator::Equal => "===",
+			Operator::NotEqual => "!==",
+			_ => operator.symbol(),
+		}
+	}
+
+	fn character(&self, c: char, cx: &mut Context) -> Result<String, GenError> {
+		self.string(&c.to_string(), cx)
+	}
+
+	/// An object literal for an interface.
+	fn struct_literal(
+		&self,
+		name: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		if cx
+			.structure(name.as_str())
+			.is_some_and(|itm| !is_interface(itm))
+		{
+			return construct(name, fields, cx);
+		}
+		let mut values = Vec::new();
+		for (field, value) in fields {
+			values.push(format!(
+				"{}:{}",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!("{{{}}}", cx.list(&values)?))
+	}
+
+	/// A tagged variant is an object literal with its `kind`.
+	fn variant(
+		&self,
+		enumeration: &Ident,
+		variant: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		if c_like(enumeration, fields, cx) {
+			return Ok(format!(
+				"{}.{}",
+				cx.type_name(enumeration.as_str())?,
+				cx.type_name(variant.as_str())?
+			));
+		}
+		let mut values = vec![format!("kind:\"{}\"", variant.as_str())];
+		for (field, value) in fields {
+			values.push(format!(
+				"{}:{}",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!("{{{}}}", cx.list(&values)?))
+	}
+}
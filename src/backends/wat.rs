@@ -0,0 +1,518 @@
+use itertools::Itertools;
+
+use crate::{
+	backends::{escape, Context},
+	block::{Conditional, ForEach, ForRange, While},
+	ir::{Expr, Operator, Stmt, Type},
+	statement::VariableInit,
+	types::Primitive,
+	Backend, Comment, Function, GenError, Import, Literal, Parameter, Scope, ToCode, Value,
+	Visibility,
+};
+
+/// The WebAssembly text format, where every value is an `i32` or an `f64`.
+#[derive(Debug, Clone, Copy)]
+pub struct WAT;
+
+/// The value type of a [`Primitive`], as a prefix of its instructions.
+fn value_type(primitive: Primitive) -> &'static str {
+	match primitive {
+		Primitive::Integer | Primitive::Boolean => "i32",
+		Primitive::Number | Primitive::Float => "f64",
+	}
+}
+
+/// The value type that `expr` evaluates to.
+fn type_of(expr: &Expr, construct: &str, cx: &Context) -> Result<&'static str, GenError> {
+	match cx.type_of(expr) {
+		Some(Type::Primitive(primitive)) => Ok(value_type(primitive)),
+		Some(_) => Err(cx.unsupported("non-primitive values")),
+		None => Err(cx.missing_type(construct)),
+	}
+}
+
+/// The value type of a declared type, or `""` if it is not a primitive.
+fn expected(typ: Option<&Type>) -> &'static str {
+	match typ {
+		Some(Type::Primitive(primitive)) => value_type(*primitive),
+		_ => "",
+	}
+}
+
+/// Renders `expr` where a value of type `typ` is expected, converting
+/// integers to floats because WAT never does so implicitly.
+fn convert(expr: &Expr, typ: &str, cx: &mut Context) -> Result<String, GenError> {
+	if typ != "f64" {
+		return expr.render(cx);
+	}
+	match expr {
+		Expr::Int(value) | Expr::Literal(Literal::Int { value, .. }) => {
+			Ok(format!("(f64.const {})", value))
+		}
+		_ => match type_of(expr, "operand", cx) {
+			Ok("i32") => Ok(format!("(f64.convert_i32_s {})", expr.render(cx)?)),
+			_ => expr.render(cx),
+		},
+	}
+}
+
+/// Every variable in `scope`, which have to be declared as locals at the
+/// start of a function.
+fn locals(scope: &Scope, locals: &mut Vec<String>, cx: &mut Context) -> Result<(), GenError> {
+	for (idx, child) in scope.children().iter().enumerate() {
+		cx.within(format!("[{}]", idx), |cx| match child {
+			Stmt::VariableInit(VariableInit { name, typ, .. }) => cx.within("variable", |cx| {
+				let local = match typ {
+					Some(typ) => format!("(local ${} {})", name.as_str(), typ.render(cx)?),
+					None => return Err(cx.missing_type("variable")),
+				};
+				if !locals.contains(&local) {
+					locals.push(local);
+				}
+				Ok(())
+			}),
+			Stmt::Conditional(conditional) => cx.within("if", |cx| {
+				for (_, scope) in &conditional.branches {
+					self::locals(scope, locals, cx)?;
+				}
+				match conditional.otherwise {
+					Some(ref last) => self::locals(last, locals, cx),
+					None => Ok(()),
+				}
+			}),
+			Stmt::While(while_loop) => {
+				cx.within("while", |cx| self::locals(&while_loop.scope, locals, cx))
+			}
+			Stmt::ForRange(for_range) => cx.within("for", |cx| {
+				let local = format!("(local ${} i32)", for_range.var.as_str());
+				if !locals.contains(&local) {
+					locals.push(local);
+				}
+				self::locals(&for_range.scope, locals, cx)
+			}),
+			_ => Ok(()),
+		})?;
+	}
+	Ok(())
+}
+
+/// The innermost loop, which is left once `exit` is true. `$break` leaves it
+/// and `$continue` skips the rest of `body` to `update`, each numbered by how
+/// deeply the loop is nested.
+fn looped(exit: &str, body: &str, update: &str, cx: &Context) -> String {
+	format!(
+		"(block $break{0} (loop $loop{0} (br_if $break{0} {1}) (block $continue{0} {2}){3} (br $loop{0})))",
+		cx.loops().len() - 1,
+		exit,
+		body,
+		update
+	)
+}
+
+/// Nests each `else if` in the `else` of the branch before it.
+fn branches(
+	branches: &[(Expr, Scope)],
+	otherwise: Option<&Scope>,
+	cx: &mut Context,
+) -> Result<String, GenError> {
+	let ((condition, scope), rest) = match branches.split_first() {
+		Some(split) => split,
+		None => return otherwise.map_or(Ok(String::new()), |scope| scope.render(cx)),
+	};
+	let condition = condition.render(cx)?;
+	let then = scope.render(cx)?;
+	let otherwise = if rest.is_empty() {
+		match otherwise {
+			Some(scope) => Some(scope.render(cx)?),
+			None => None,
+		}
+	} else {
+		Some(self::branches(rest, otherwise, cx)?)
+	};
+	Ok(format!(
+		"(if {} (then {}){})",
+		condition,
+		then,
+		match otherwise {
+			Some(code) => format!(" (else {})", code),
+			None => String::new(),
+		}
+	))
+}
+
+/// A function, which is exported under `export` if it is given.
+fn func(
+	name: &str,
+	export: Option<&str>,
+	params: &[Parameter],
+	result: Option<&Type>,
+	scope: &Scope,
+	cx: &mut Context,
+) -> Result<String, GenError> {
+	let mut header = vec![format!("func ${}", name)];
+	if let Some(export) = export {
+		header.push(format!("(export \"{}\")", export));
+	}
+	for param in params {
+		header.push(param.render(cx)?);
+	}
+	if let Some(typ) = result {
+		header.push(format!("(result {})", typ.render(cx)?));
+	}
+	locals(scope, &mut header, cx)?;
+	Ok(format!("({} {})", header.join(" "), scope.render(cx)?))
+}
+
+impl Backend for WAT {
+	fn name(&self) -> &'static str {
+		"WAT"
+	}
+
+	fn extension(&self) -> &'static str {
+		"wat"
+	}
+
+	/// The outermost scope is a module, which starts with the host functions
+	/// that were required.
+	fn scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		let code = cx.children(scope)?.join(" ");
+		if cx.depth() != 1 {
+			return Ok(code);
+		}
+		Ok(format!(
+			"(module {})",
+			cx.requirements()
+				.iter()
+				.map(|typ| format!(
+					"(import \"env\" \"log\" (func $log_{} (param {})))",
+					typ, typ
+				))
+				.chain(std::iter::once(code))
+				.join(" ")
+		))
+	}
+
+	fn run_scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		func("main", Some("main"), &[], None, scope, cx)
+	}
+
+	fn function(&self, function: &Function, cx: &mut Context) -> Result<String, GenError> {
+		func(
+			function.name.as_str(),
+			match function.visibility {
+				Visibility::Public => Some(function.name.as_str()),
+				Visibility::Private => None,
+			},
+			&function.params,
+			function.return_type.as_ref(),
+			&function.scope,
+			cx,
+		)
+	}
+
+	fn parameter(&self, parameter: &Parameter, cx: &mut Context) -> Result<String, GenError> {
+		let Parameter(name, typ) = parameter;
+		match typ {
+			Some(typ) => Ok(format!("(param ${} {})", name.as_str(), typ.render(cx)?)),
+			None => Err(cx.missing_type("parameter")),
+		}
+	}
+
+	/// Functions are exported with their own name instead.
+	fn visibility(&self, _visibility: Visibility, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::new())
+	}
+
+	fn conditional(&self, conditional: &Conditional, cx: &mut Context) -> Result<String, GenError> {
+		branches(&conditional.branches, conditional.otherwise.as_ref(), cx)
+	}
+
+	fn while_loop(&self, while_loop: &While, cx: &mut Context) -> Result<String, GenError> {
+		let exit = format!("(i32.eqz {})", while_loop.condition.render(cx)?);
+		let body = while_loop.scope.render(cx)?;
+		Ok(looped(&exit, &body, "", cx))
+	}
+
+	fn for_range(&self, for_range: &ForRange, cx: &mut Context) -> Result<String, GenError> {
+		let var = Expr::from(for_range.var.clone());
+		let exit = Expr::Binary(
+			if for_range.step > 0 {
+				Operator::GreaterThanOrEq
+			} else {
+				Operator::LessThanOrEq
+			},
+			Box::new(var.clone()),
+			Box::new(for_range.end.clone()),
+		);
+		let next = Expr::Binary(
+			Operator::Add,
+			Box::new(var),
+			Box::new(Expr::Int(for_range.step)),
+		);
+		let exit = exit.render(cx)?;
+		let update = format!(
+			" (local.set ${} {})",
+			for_range.var.as_str(),
+			next.render(cx)?
+		);
+		let body = for_range.scope.render(cx)?;
+		Ok(format!(
+			"(local.set ${} {}) {}",
+			for_range.var.as_str(),
+			for_range.start.render(cx)?,
+			looped(&exit, &body, &update, cx)
+		))
+	}
+
+	/// There are no collections to iterate over.
+	fn for_each(&self, _for_each: &ForEach, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("block::ForEach"))
+	}
+
+	fn break_statement(&self, label: Option<&str>, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"(br $break{})",
+			cx.loop_target(label, "statement::Break")?
+		))
+	}
+
+	fn continue_statement(
+		&self,
+		label: Option<&str>,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"(br $continue{})",
+			cx.loop_target(label, "statement::Continue")?
+		))
+	}
+
+	/// Locals are declared by the function, so this only sets the value.
+	fn variable_init(&self, init: &VariableInit, cx: &mut Context) -> Result<String, GenError> {
+		let VariableInit {
+			name, typ, value, ..
+		} = init;
+		Ok(format!(
+			"(local.set ${} {})",
+			name.as_str(),
+			convert(value, expected(typ.as_ref()), cx)?
+		))
+	}
+
+	fn assign_variable(
+		&self,
+		name: &Expr,
+		value: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		match name {
+			Expr::Ident(ident) => {
+				let typ = type_of(name, "variable", cx).unwrap_or("");
+				Ok(format!(
+					"(local.set ${} {})",
+					ident.as_str(),
+					convert(value, typ, cx)?
+				))
+			}
+			_ => Err(cx.unsupported("assigning to expressions")),
+		}
+	}
+
+	fn return_statement(&self, value: Option<&Expr>, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match value {
+			Some(expr) => {
+				let typ = expected(cx.return_type());
+				format!("(return {})", convert(expr, typ, cx)?)
+			}
+			None => String::from("(return)"),
+		})
+	}
+
+	fn comment(&self, comment: &Comment, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match comment {
+			Comment::Regular(content) | Comment::Doc(content) => format!(";;{}\n", content),
+			Comment::MultiLine(content) => format!("(;{};)", content),
+		})
+	}
+
+	/// Imports are host functions, whose signature is unknown.
+	fn import(&self, _import: &Import, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("Import"))
+	}
+
+	/// Calls a `log` function imported from the host, with an import for each
+	/// value type.
+	fn println(&self, value: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		let typ = type_of(value, "printed value", cx)?;
+		cx.require(typ);
+		Ok(format!("(call $log_{} {})", typ, value.render(cx)?))
+	}
+
+	fn expression_statement(&self, expr: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match cx.type_of(expr) {
+			Some(_) => format!("(drop {})", expr.render(cx)?),
+			None => expr.render(cx)?,
+		})
+	}
+
+	/// Both values are evaluated, and an integer is converted when the other
+	/// value is a float.
+	fn ternary(
+		&self,
+		condition: &Expr,
+		if_true: &Expr,
+		if_false: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let typ = match (
+			type_of(if_true, "value", cx),
+			type_of(if_false, "value", cx),
+		) {
+			(Ok("f64"), _) | (_, Ok("f64")) => "f64",
+			_ => "",
+		};
+		Ok(format!(
+			"(select {} {} {})",
+			convert(if_true, typ, cx)?,
+			convert(if_false, typ, cx)?,
+			condition.render(cx)?
+		))
+	}
+
+	fn primitive_type(&self, primitive: Primitive, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(value_type(primitive)))
+	}
+
+	fn generic_type(
+		&self,
+		_name: &str,
+		_args: &[Type],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Err(cx.unsupported("types::Generic"))
+	}
+
+	fn future_type(&self, _value: &Type, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("types::Future"))
+	}
+
+	fn integer(&self, value: i64, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("(i32.const {})", value))
+	}
+
+	fn identifier(&self, name: &str, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("(local.get ${})", name))
+	}
+
+	fn function_call(
+		&self,
+		name: &Expr,
+		args: &[Expr],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let name = match name {
+			Expr::Ident(name) => name.as_str(),
+			_ => return Err(cx.unsupported("calling expressions")),
+		};
+		// Each argument is converted to the type of its parameter.
+		let types = match cx.parameters(name) {
+			Some(params) => params
+				.iter()
+				.map(|Parameter(_, typ)| expected(typ.as_ref()))
+				.collect(),
+			None => Vec::new(),
+		};
+		Ok(format!(
+			"(call ${}{})",
+			name,
+			args.iter()
+				.enumerate()
+				.map(|(idx, arg)| {
+					let typ = types.get(idx).copied().unwrap_or("");
+					Ok(format!(" {}", convert(arg, typ, cx)?))
+				})
+				.collect::<Result<String, GenError>>()?
+		))
+	}
+
+	/// The name of the instruction, without the value type.
+	fn operator(&self, operator: Operator) -> &'static str {
+		match operator {
+			Operator::Add => "add",
+			Operator::Subtract => "sub",
+			Operator::Multiply => "mul",
+			Operator::Divide => "div",
+			Operator::Mod => "rem",
+			Operator::Equal => "eq",
+			Operator::NotEqual => "ne",
+			Operator::GreaterThan => "gt",
+			Operator::LessThan => "lt",
+			Operator::GreaterThanOrEq => "ge",
+			Operator::LessThanOrEq => "le",
+			Operator::And => "and",
+			Operator::Or => "or",
+		}
+	}
+
+	/// Integers are signed, which some instructions have to be told.
+	fn binary(
+		&self,
+		operator: Operator,
+		lhs: &Expr,
+		rhs: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		// An integer operand is converted when the other one is a float.
+		let typ = match (type_of(lhs, "operand", cx), type_of(rhs, "operand", cx)) {
+			(Ok("f64"), _) | (_, Ok("f64")) => "f64",
+			(Ok(typ), _) => typ,
+			(Err(_), rhs) => rhs?,
+		};
+		let signed = match operator {
+			Operator::Divide
+			| Operator::Mod
+			| Operator::GreaterThan
+			| Operator::LessThan
+			| Operator::GreaterThanOrEq
+			| Operator::LessThanOrEq
+				if typ == "i32" =>
+			{
+				"_s"
+			}
+			Operator::Mod => return Err(cx.unsupported("operation::Mod on floats")),
+			_ => "",
+		};
+		Ok(format!(
+			"({}.{}{} {} {})",
+			typ,
+			self.operator(operator),
+			signed,
+			convert(lhs, typ, cx)?,
+			convert(rhs, typ, cx)?
+		))
+	}
+
+	fn value(&self, value: &Value, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match value {
+			Value::True => "(i32.const 1)",
+			Value::False => "(i32.const 0)",
+		}))
+	}
+
+	fn float(&self, value: f64, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("(f64.const {})", escape::float(value, cx)?))
+	}
+
+	fn string(&self, _text: &str, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("string literals"))
+	}
+
+	/// The code point of the character.
+	fn character(&self, c: char, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("(i32.const {})", c as u32))
+	}
+
+	fn null(&self, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("null"))
+	}
+}
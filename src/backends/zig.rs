@@ -0,0 +1,457 @@
+use itertools::Itertools;
+
+use crate::{
+	backends::{escape, label, Context, Counter},
+	block::{Conditional, ForEach, ForRange, While},
+	ir::{Associativity, Expr, Operator, Type},
+	statement::VariableInit,
+	types::Primitive,
+	Backend, Case, Comment, Function, GenError, Ident, Import, Literal, NameKind, Parameter, Scope,
+	Struct, ToCode, Visibility,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Zig;
+
+const KEYWORDS: &[&str] = &[
+	"addrspace",
+	"align",
+	"allowzero",
+	"and",
+	"anyframe",
+	"anytype",
+	"asm",
+	"async",
+	"await",
+	"break",
+	"callconv",
+	"catch",
+	"comptime",
+	"const",
+	"continue",
+	"defer",
+	"else",
+	"enum",
+	"errdefer",
+	"error",
+	"export",
+	"extern",
+	"false",
+	"fn",
+	"for",
+	"if",
+	"inline",
+	"linksection",
+	"noalias",
+	"noinline",
+	"nosuspend",
+	"null",
+	"opaque",
+	"or",
+	"orelse",
+	"packed",
+	"pub",
+	"resume",
+	"return",
+	"struct",
+	"suspend",
+	"switch",
+	"test",
+	"threadlocal",
+	"true",
+	"try",
+	"undefined",
+	"union",
+	"unreachable",
+	"usingnamespace",
+	"var",
+	"volatile",
+	"while",
+];
+
+/// The name a module is bound to when it is imported, i.e. its file stem or
+/// the last segment of its path.
+fn module_name(location: &str) -> &str {
+	let file = location.rsplit('/').next().unwrap_or(location);
+	let module = file.strip_suffix(".zig").unwrap_or(file);
+	module.rsplit('.').next().unwrap_or(module)
+}
+
+impl Backend for Zig {
+	fn name(&self) -> &'static str {
+		"Zig"
+	}
+
+	fn extension(&self) -> &'static str {
+		"zig"
+	}
+
+	fn trailing_commas(&self) -> bool {
+		true
+	}
+
+	fn keywords(&self) -> &'static [&'static str] {
+		KEYWORDS
+	}
+
+	/// Functions are `camelCase` and variables `snake_case`.
+	fn naming_convention(&self, kind: NameKind) -> Option<Case> {
+		Some(match kind {
+			NameKind::Function => Case::Camel,
+			NameKind::Variable | NameKind::Field => Case::Snake,
+		})
+	}
+
+	fn escape_keyword(&self, name: &str) -> String {
+		format!("@\"{}\"", name)
+	}
+
+	/// Imports every required module under its own name.
+	fn finish(&self, code: String, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}{}",
+			cx.requirements()
+				.iter()
+				.map(|itm| format!("const {}=@import(\"{}\");\n", module_name(itm), itm))
+				.join(""),
+			code
+		))
+	}
+
+	fn scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		Ok(cx.children(scope)?.join(""))
+	}
+
+	fn run_scope(&self, scope: &Scope, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!("pub fn main() void{{{}}}", scope.render(cx)?))
+	}
+
+	fn function(&self, function: &Function, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}fn {}({}) {}{{{}}}",
+			match function.visibility {
+				Visibility::Public => "pub ",
+				Visibility::Private => "",
+			},
+			function.name.render(cx)?,
+			cx.join(&function.params, ",")?,
+			match function.return_type {
+				Some(ref typ) => typ.render(cx)?,
+				None => String::from("void"),
+			},
+			function.scope.render(cx)?
+		))
+	}
+
+	fn parameter(&self, parameter: &Parameter, cx: &mut Context) -> Result<String, GenError> {
+		let Parameter(name, typ) = parameter;
+		match typ {
+			Some(typ) => Ok(format!("{}:{}", name.render(cx)?, typ.render(cx)?)),
+			None => Err(cx.missing_type("parameter")),
+		}
+	}
+
+	fn visibility(&self, visibility: Visibility, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match visibility {
+			Visibility::Public => "pub",
+			Visibility::Private => "",
+		}))
+	}
+
+	/// Zig has no private fields.
+	fn structure(&self, structure: &Struct, cx: &mut Context) -> Result<String, GenError> {
+		let mut fields = String::new();
+		for field in &structure.fields {
+			fields.push_str(&format!("{},", field.render(cx)?));
+		}
+		Ok(format!(
+			"{}const {}=struct{{{}}};",
+			match structure.visibility {
+				Visibility::Public => "pub ",
+				Visibility::Private => "",
+			},
+			cx.type_name(structure.name.as_str())?,
+			fields
+		))
+	}
+
+	fn conditional(&self, conditional: &Conditional, cx: &mut Context) -> Result<String, GenError> {
+		let mut branches = Vec::new();
+		for (condition, scope) in &conditional.branches {
+			branches.push(format!(
+				"if({}){{{}}}",
+				condition.render(cx)?,
+				scope.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}{}",
+			branches.iter().join("else "),
+			match conditional.otherwise {
+				Some(ref last) => format!("else{{{}}}", last.render(cx)?),
+				None => String::new(),
+			}
+		))
+	}
+
+	fn while_loop(&self, while_loop: &While, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}while({}){{{}}}",
+			label(while_loop.label.as_deref(), ":"),
+			while_loop.condition.render(cx)?,
+			while_loop.scope.render(cx)?
+		))
+	}
+
+	/// A `while` loop with a continue expression, in a block of its own that
+	/// declares the counter.
+	fn for_range(&self, for_range: &ForRange, cx: &mut Context) -> Result<String, GenError> {
+		let typ = Type::Primitive(Primitive::Integer).render(cx)?;
+		let counter = Counter::new(for_range, cx)?;
+		Ok(format!(
+			"{{var {}:{}={};{}while({}):({}){{{}}}}}",
+			counter.var,
+			typ,
+			counter.start,
+			label(for_range.label.as_deref(), ":"),
+			counter.condition,
+			counter.update,
+			for_range.scope.render(cx)?
+		))
+	}
+
+	fn for_each(&self, for_each: &ForEach, cx: &mut Context) -> Result<String, GenError> {
+		Ok(format!(
+			"{}for({})|{}|{{{}}}",
+			label(for_each.label.as_deref(), ":"),
+			for_each.iterable.render(cx)?,
+			for_each.var.render(cx)?,
+			for_each.scope.render(cx)?
+		))
+	}
+
+	fn break_statement(&self, label: Option<&str>, cx: &mut Context) -> Result<String, GenError> {
+		cx.loop_target(label, "statement::Break")?;
+		Ok(match label {
+			Some(label) => format!("break :{};", label),
+			None => String::from("break;"),
+		})
+	}
+
+	fn continue_statement(
+		&self,
+		label: Option<&str>,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		cx.loop_target(label, "statement::Continue")?;
+		Ok(match label {
+			Some(label) => format!("continue :{};", label),
+			None => String::from("continue;"),
+		})
+	}
+
+	/// Variables are `const` unless they are mutable, and a `var` cannot hold
+	/// a comptime-known literal, so it is given the type of its value.
+	fn variable_init(&self, init: &VariableInit, cx: &mut Context) -> Result<String, GenError> {
+		let VariableInit {
+			name,
+			mutable,
+			typ,
+			value,
+		} = init;
+		let mutable = *mutable == Some(true);
+		let typ = match typ {
+			Some(typ) => Some(typ.clone()),
+			None if mutable => match cx.type_of(value) {
+				Some(typ) => Some(typ),
+				None => return Err(cx.missing_type("mutable variable")),
+			},
+			None => None,
+		};
+		Ok(format!(
+			"{} {}{}={};",
+			if mutable { "var" } else { "const" },
+			name.render(cx)?,
+			match typ {
+				Some(typ) => format!(":{}", typ.render(cx)?),
+				None => String::new(),
+			},
+			value.render(cx)?
+		))
+	}
+
+	fn assign_variable(
+		&self,
+		name: &Expr,
+		value: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}={};", name.render(cx)?, value.render(cx)?))
+	}
+
+	fn return_statement(&self, value: Option<&Expr>, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match value {
+			Some(expr) => format!("return {};", expr.render(cx)?),
+			None => String::from("return;"),
+		})
+	}
+
+	/// Zig only has line comments.
+	fn comment(&self, comment: &Comment, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(match comment {
+			Comment::Regular(content) => format!("//{}\n", content),
+			Comment::MultiLine(content) => {
+				content.lines().map(|line| format!("//{}\n", line)).join("")
+			}
+			Comment::Doc(content) => format!("///{}\n", content),
+		})
+	}
+
+	/// Modules are required rather than imported here, as binding `std`
+	/// twice would redeclare it.
+	fn import(&self, import: &Import, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match import {
+			Import::Module(location) => {
+				cx.require(location.as_str());
+				String::new()
+			}
+			Import::Members(location, members) => members
+				.iter()
+				.map(|member| format!("const {}=@import(\"{}\").{};", member, location, member))
+				.join(""),
+		})
+	}
+
+	/// Strings and characters need their own format specifiers. Values of
+	/// an unknown type are assumed to be strings, as only they have no type.
+	fn println(&self, value: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		cx.require("std");
+		let specifier = match value {
+			Expr::Literal(Literal::Str(_)) => "s",
+			Expr::Literal(Literal::Char(_)) => "u",
+			_ if cx.type_of(value).is_none() => "s",
+			_ => "",
+		};
+		Ok(format!(
+			"std.debug.print(\"{{{}}}\\n\",.{{{}}});",
+			specifier,
+			value.render(cx)?
+		))
+	}
+
+	/// Values cannot be silently discarded.
+	fn expression_statement(&self, expr: &Expr, cx: &mut Context) -> Result<String, GenError> {
+		Ok(match cx.type_of(expr) {
+			Some(_) => format!("_={};", expr.render(cx)?),
+			None => format!("{};", expr.render(cx)?),
+		})
+	}
+
+	/// `if` is an expression in Zig.
+	fn ternary(
+		&self,
+		condition: &Expr,
+		if_true: &Expr,
+		if_false: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!(
+			"if({}) {} else {}",
+			condition.render(cx)?,
+			if_true.render(cx)?,
+			if_false.render(cx)?
+		))
+	}
+
+	fn primitive_type(&self, primitive: Primitive, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(String::from(match primitive {
+			Primitive::Integer => "i32",
+			Primitive::Number | Primitive::Float => "f64",
+			Primitive::Boolean => "bool",
+		}))
+	}
+
+	/// Generic types are functions that return a type.
+	fn generic_type(
+		&self,
+		name: &str,
+		args: &[Type],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		Ok(format!("{}({})", name, cx.join(args, ",")?))
+	}
+
+	fn future_type(&self, _value: &Type, cx: &mut Context) -> Result<String, GenError> {
+		Err(cx.unsupported("types::Future"))
+	}
+
+	/// Comparisons cannot be chained.
+	fn precedence(&self, operator: Operator) -> (u8, Associativity) {
+		operator.flat_precedence()
+	}
+
+	fn operator(&self, operator: Operator) -> &'static str {
+		match operator {
+			Operator::And => " and ",
+			Operator::Or => " or ",
+			_ => operator.symbol(),
+		}
+	}
+
+	/// Signed integers have to be divided with a builtin that says how to
+	/// round.
+	fn binary(
+		&self,
+		operator: Operator,
+		lhs: &Expr,
+		rhs: &Expr,
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let integer = Some(Type::Primitive(Primitive::Integer));
+		let builtin = match operator {
+			Operator::Divide => "@divTrunc",
+			Operator::Mod => "@mod",
+			_ => "",
+		};
+		if !builtin.is_empty() && cx.type_of(lhs) == integer && cx.type_of(rhs) == integer {
+			return Ok(format!(
+				"{}({},{})",
+				builtin,
+				lhs.render(cx)?,
+				rhs.render(cx)?
+			));
+		}
+		let lhs = cx.operand(operator, lhs, false)?;
+		let rhs = cx.operand(operator, rhs, true)?;
+		Ok(format!("{}{}{}", lhs, self.operator(operator), rhs))
+	}
+
+	fn string(&self, text: &str, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(escape::quote(text, '"', "", escape::braced))
+	}
+
+	fn character(&self, c: char, _cx: &mut Context) -> Result<String, GenError> {
+		Ok(escape::quote(&c.to_string(), '\'', "", escape::braced))
+	}
+
+	fn struct_literal(
+		&self,
+		name: &Ident,
+		fields: &[(Ident, Expr)],
+		cx: &mut Context,
+	) -> Result<String, GenError> {
+		let mut values = Vec::new();
+		for (field, value) in fields {
+			values.push(format!(
+				".{}={}",
+				cx.field_name(field.as_str())?,
+				value.render(cx)?
+			));
+		}
+		Ok(format!(
+			"{}{{{}}}",
+			cx.type_name(name.as_str())?,
+			cx.list(&values)?
+		))
+	}
+}
@@ -0,0 +1,81 @@
+use std::fmt;
+
+use thiserror::Error;
+
+/// The location of a node in the tree being rendered, from the outermost node
+/// inwards.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodePath(pub Vec<String>);
+
+impl fmt::Display for NodePath {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.0.is_empty() {
+			return f.write_str("<root>");
+		}
+		f.write_str(&self.0.join(" > "))
+	}
+}
+
+/// A node that could not be rendered in the requested language.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum GenError {
+	#[error("{path}: {language} requires a type for every {construct}")]
+	MissingType {
+		construct: String,
+		language: &'static str,
+		path: NodePath,
+	},
+	#[error("{path}: {construct} is not supported in {language}")]
+	UnsupportedConstruct {
+		construct: String,
+		language: &'static str,
+		path: NodePath,
+	},
+	#[error("{path}: `{first}` and `{second}` are both written as `{name}` in {language}")]
+	NameCollision {
+		first: String,
+		second: String,
+		name: String,
+		language: &'static str,
+		path: NodePath,
+	},
+	#[error("{path}: {construct} is not inside of {target}")]
+	NotInLoop {
+		construct: String,
+		/// `a loop`, or the loop with the label that was asked for.
+		target: String,
+		path: NodePath,
+	},
+	#[error("{path}: {construct} is not inside of a method with an instance")]
+	NotInMethod { construct: String, path: NodePath },
+	#[error("{path}: `{name}` has not been declared")]
+	UndeclaredName { name: String, path: NodePath },
+	#[error("{path}: block::Match does not cover {missing} of `{enumeration}`")]
+	NonExhaustiveMatch {
+		enumeration: String,
+		/// The variants without an arm, e.g. ``` `A`, `B` ```.
+		missing: String,
+		path: NodePath,
+	},
+	#[error(
+		"{path}: `{method}` is not implemented by a public method with the same receiver and \
+		 parameters"
+	)]
+	Unimplemented { method: String, path: NodePath },
+}
+
+impl GenError {
+	/// Where in the tree the error occurred.
+	pub fn path(&self) -> &NodePath {
+		match self {
+			Self::MissingType { path, .. }
+			| Self::UnsupportedConstruct { path, .. }
+			| Self::NameCollision { path, .. }
+			| Self::NotInLoop { path, .. }
+			| Self::NotInMethod { path, .. }
+			| Self::UndeclaredName { path, .. }
+			| Self::NonExhaustiveMatch { path, .. }
+			| Self::Unimplemented { path, .. } => path,
+		}
+	}
+}
@@ -0,0 +1,352 @@
+//! The typed tree that every builder lowers into.
+//!
+//! Unlike the builders, these nodes can be inspected, compared, cloned and
+//! rewritten before they are rendered with [`ToCode`].
+
+use crate::{
+	backends::Context,
+	block::{Conditional, ForEach, ForRange, Match, While},
+	statement::VariableInit,
+	types::Primitive,
+	Class, Comment, Enum, Function, GenError, Ident, Import, Interface, Literal, Receiver, Scope,
+	Struct, ToCode, Value,
+};
+
+/// A binary operator produced by [`crate::operation`] and [`crate::condition`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+	Add,
+	Subtract,
+	Multiply,
+	Divide,
+	Mod,
+	Equal,
+	NotEqual,
+	GreaterThan,
+	LessThan,
+	GreaterThanOrEq,
+	LessThanOrEq,
+	And,
+	Or,
+}
+
+/// How a chain of operators with the same precedence is grouped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Associativity {
+	/// `a - b - c` is `(a - b) - c`.
+	Left,
+	/// `a = b = c` is `a = (b = c)`.
+	Right,
+	/// A chain is not allowed, or does not mean what it looks like.
+	None,
+}
+
+impl Operator {
+	/// The C-style spelling of the operator, which most targets share.
+	pub fn symbol(self) -> &'static str {
+		match self {
+			Self::Add => "+",
+			Self::Subtract => "-",
+			Self::Multiply => "*",
+			Self::Divide => "/",
+			Self::Mod => "%",
+			Self::Equal => "==",
+			Self::NotEqual => "!=",
+			Self::GreaterThan => ">",
+			Self::LessThan => "<",
+			Self::GreaterThanOrEq => ">=",
+			Self::LessThanOrEq => "<=",
+			Self::And => "&&",
+			Self::Or => "||",
+		}
+	}
+
+	/// The precedence of the operator in C, where a higher level binds
+	/// tighter.
+	pub fn precedence(self) -> (u8, Associativity) {
+		match self {
+			Self::Multiply | Self::Divide | Self::Mod => (10, Associativity::Left),
+			Self::Add | Self::Subtract => (9, Associativity::Left),
+			Self::GreaterThan | Self::LessThan | Self::GreaterThanOrEq | Self::LessThanOrEq => {
+				(7, Associativity::None)
+			}
+			Self::Equal | Self::NotEqual => (6, Associativity::None),
+			Self::And => (3, Associativity::Left),
+			Self::Or => (2, Associativity::Left),
+		}
+	}
+
+	/// [`Operator::precedence`] for languages where every comparison has the
+	/// same precedence, e.g. Rust and Python.
+	pub fn flat_precedence(self) -> (u8, Associativity) {
+		match self {
+			Self::Equal | Self::NotEqual => (7, Associativity::None),
+			_ => self.precedence(),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+	Primitive(Primitive),
+	Generic(String, Vec<Type>),
+	Future(Box<Type>),
+	/// A type declared in the generated code, e.g. a [`crate::Struct`].
+	Named(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+	/// A reference to a function, parameter or variable.
+	Ident(Ident),
+	Int(i64),
+	Literal(Literal),
+	Value(Value),
+	Binary(Operator, Box<Expr>, Box<Expr>),
+	Call(Box<Expr>, Vec<Expr>),
+	Ternary(Box<Expr>, Box<Expr>, Box<Expr>),
+	/// An instance of a [`crate::Struct`], with the value of each field.
+	StructLiteral(Ident, Vec<(Ident, Expr)>),
+	/// A field of a struct or class.
+	Field(Box<Expr>, Ident),
+	/// The instance inside of a [`crate::Method`].
+	This,
+	/// A call of the method with the given name on an instance.
+	MethodCall(Box<Expr>, Ident, Vec<Expr>),
+	/// A call of a static method of a [`crate::Class`].
+	StaticCall(Ident, Ident, Vec<Expr>),
+	/// A call of the [`crate::Constructor`] of a class.
+	New(Ident, Vec<Expr>),
+	/// A variant of a [`crate::Enum`], with the value of each of its fields.
+	Variant(Ident, Ident, Vec<(Ident, Expr)>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Stmt {
+	Item(Item),
+	VariableInit(VariableInit),
+	Assign(Expr, Expr),
+	Return(Option<Expr>),
+	Conditional(Conditional),
+	While(While),
+	ForRange(ForRange),
+	ForEach(ForEach),
+	Match(Match),
+	/// Leaves the innermost loop, or the loop with the given label.
+	Break(Option<String>),
+	/// Skips to the next iteration of the innermost loop, or of the loop with
+	/// the given label.
+	Continue(Option<String>),
+	Println(Expr),
+	Comment(Comment),
+	/// An expression evaluated for its side effects.
+	Expr(Expr),
+	/// Code that is inserted without being rendered.
+	Raw(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+	Function(Function),
+	Struct(Struct),
+	Class(Class),
+	Enum(Enum),
+	Interface(Interface),
+	Import(Import),
+	/// The entry point of a program, see [`crate::RunScope`].
+	RunScope(Scope),
+}
+
+impl ToCode for Type {
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		match self {
+			Self::Primitive(primitive) => cx.backend().primitive_type(*primitive, cx),
+			Self::Generic(name, args) => cx.backend().generic_type(name, args, cx),
+			Self::Future(value) => {
+				cx.within("types::Future", |cx| cx.backend().future_type(value, cx))
+			}
+			Self::Named(name) => cx.backend().named_type(name, cx),
+		}
+	}
+}
+
+impl ToCode for Expr {
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		match self {
+			Self::Ident(name) => name.render(cx),
+			Self::Int(value) => cx.backend().integer(*value, cx),
+			Self::Literal(literal) => literal.render(cx),
+			Self::Value(value) => cx.backend().value(value, cx),
+			Self::Binary(operator, lhs, rhs) => cx.backend().binary(*operator, lhs, rhs, cx),
+			Self::Call(name, args) => cx.backend().function_call(name, args, cx),
+			Self::Ternary(condition, if_true, if_false) => {
+				cx.backend().ternary(condition, if_true, if_false, cx)
+			}
+			Self::StructLiteral(name, fields) => cx.backend().struct_literal(name, fields, cx),
+			Self::Field(object, field) => cx.backend().field_access(object, field, cx),
+			Self::This => match cx.receiver() {
+				Receiver::Static => Err(GenError::NotInMethod {
+					construct: String::from("expression::This"),
+					path: cx.path(),
+				}),
+				_ => cx.backend().this(cx),
+			},
+			Self::MethodCall(object, name, args) => {
+				cx.backend().method_call(object, name, args, cx)
+			}
+			Self::StaticCall(class, name, args) => cx.backend().static_call(class, name, args, cx),
+			Self::New(class, args) => cx.backend().new_instance(class, args, cx),
+			Self::Variant(enumeration, variant, fields) => {
+				cx.backend().variant(enumeration, variant, fields, cx)
+			}
+		}
+	}
+}
+
+impl ToCode for Stmt {
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		match self {
+			Self::Item(item) => item.render(cx),
+			Self::VariableInit(init) => init.render(cx),
+			Self::Assign(name, value) => cx.within("assignment", |cx| {
+				cx.backend().assign_variable(name, value, cx)
+			}),
+			Self::Return(value) => cx.within("return", |cx| {
+				cx.backend().return_statement(value.as_ref(), cx)
+			}),
+			Self::Conditional(conditional) => conditional.render(cx),
+			Self::While(while_loop) => while_loop.render(cx),
+			Self::ForRange(for_range) => for_range.render(cx),
+			Self::ForEach(for_each) => for_each.render(cx),
+			Self::Match(match_block) => match_block.render(cx),
+			Self::Break(label) => cx.within("break", |cx| {
+				cx.backend().break_statement(label.as_deref(), cx)
+			}),
+			Self::Continue(label) => cx.within("continue", |cx| {
+				cx.backend().continue_statement(label.as_deref(), cx)
+			}),
+			Self::Println(value) => cx.within("println", |cx| cx.backend().println(value, cx)),
+			Self::Comment(comment) => cx.backend().comment(comment, cx),
+			Self::Expr(expr) => cx.backend().expression_statement(expr, cx),
+			Self::Raw(code) => Ok(code.clone()),
+		}
+	}
+}
+
+impl ToCode for Item {
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		match self {
+			Self::Function(function) => function.render(cx),
+			Self::Struct(structure) => structure.render(cx),
+			Self::Class(class) => class.render(cx),
+			Self::Enum(enumeration) => enumeration.render(cx),
+			Self::Interface(interface) => interface.render(cx),
+			Self::Import(import) => import.render(cx),
+			Self::RunScope(scope) => {
+				cx.mark_entry_point();
+				cx.within("run scope", |cx| {
+					cx.nested(|cx| cx.backend().run_scope(scope, cx))
+				})
+			}
+		}
+	}
+}
+
+impl From<Primitive> for Type {
+	#[inline]
+	fn from(primitive: Primitive) -> Self {
+		Self::Primitive(primitive)
+	}
+}
+
+impl From<&str> for Expr {
+	#[inline]
+	fn from(name: &str) -> Self {
+		Self::Ident(Ident::from(name))
+	}
+}
+
+impl From<String> for Expr {
+	#[inline]
+	fn from(name: String) -> Self {
+		Self::Ident(Ident::from(name))
+	}
+}
+
+impl From<Ident> for Expr {
+	#[inline]
+	fn from(name: Ident) -> Self {
+		Self::Ident(name)
+	}
+}
+
+impl From<Literal> for Expr {
+	#[inline]
+	fn from(literal: Literal) -> Self {
+		Self::Literal(literal)
+	}
+}
+
+impl From<f64> for Expr {
+	#[inline]
+	fn from(value: f64) -> Self {
+		Self::Literal(Literal::Float(value))
+	}
+}
+
+impl From<char> for Expr {
+	#[inline]
+	fn from(c: char) -> Self {
+		Self::Literal(Literal::Char(c))
+	}
+}
+
+impl From<Value> for Expr {
+	#[inline]
+	fn from(value: Value) -> Self {
+		Self::Value(value)
+	}
+}
+
+macro_rules! impl_int_expr {
+	($($int:ty),*) => {
+		$(
+			impl From<$int> for Expr {
+				#[inline]
+				fn from(value: $int) -> Self {
+					Self::Int(value as i64)
+				}
+			}
+		)*
+	};
+}
+
+impl_int_expr!(i32, u32, isize, usize);
+
+impl From<Expr> for Stmt {
+	#[inline]
+	fn from(expr: Expr) -> Self {
+		Self::Expr(expr)
+	}
+}
+
+impl From<Item> for Stmt {
+	#[inline]
+	fn from(item: Item) -> Self {
+		Self::Item(item)
+	}
+}
+
+impl From<&str> for Stmt {
+	#[inline]
+	fn from(code: &str) -> Self {
+		Self::Raw(code.to_owned())
+	}
+}
+
+impl From<String> for Stmt {
+	#[inline]
+	fn from(code: String) -> Self {
+		Self::Raw(code)
+	}
+}
@@ -1,8 +1,12 @@
 #[macro_use]
 pub(crate) mod macros;
+pub mod backends;
+mod error;
+pub mod ir;
 mod models;
 
-pub(crate) use models::utils;
+pub use backends::Backend;
+pub use error::{GenError, NodePath};
 pub use models::*;
 
 #[cfg(test)]
@@ -16,8 +20,8 @@ mod tests {
 				.name("add")
 				.with_visibility(Visibility::Public)
 				.with_return_type(types::Integer)
-				.with_param(Parameter(Box::new("a"), Some(Box::new(types::Integer))))
-				.with_param(Parameter(Box::new("b"), Some(Box::new(types::Integer))))
+				.with_param(Parameter(Ident::from("a"), Some(types::Integer.into())))
+				.with_param(Parameter(Ident::from("b"), Some(types::Integer.into())))
 				.with_scope(
 					Scope::default().with(statement::Return(Some(operation::Add("a", "b")))),
 				)
@@ -27,26 +31,1732 @@ mod tests {
 
 		assert_eq!(
 			scope.to_code(Language::Rust),
-			"pub fn add(a:i32,b:i32)->i32{return (a+b);}"
+			"pub fn add(a:i32,b:i32)->i32{return a+b;}"
 		);
 
 		assert_eq!(
 			scope.to_code(Language::TypeScript),
-			"export function add(a:number,b:number):number{return (a+b);}"
+			"export function add(a:number,b:number):number{return a+b;}"
 		);
 
 		assert_eq!(
 			scope.to_code(Language::CPP),
-			"int add(int a,int b){return (a+b);}"
+			"int add(int a,int b){return a+b;}"
 		);
 
 		assert_eq!(
 			scope.to_code(Language::Python {
 				include_types: true,
-				indent_level: None,
-				indent_type: IndentType::Tab
+				version: (3, 10)
 			}),
-			"\ndef add(a:int,b:int)->int:\n\treturn (a+b)"
+			"def add(a:int,b:int)->int:\n\treturn a+b"
+		);
+	}
+
+	#[test]
+	fn missing_type_error() {
+		let scope = Scope::default().with(
+			FunctionBuilder::new()
+				.name("add")
+				.with_param(Parameter(Ident::from("a"), None))
+				.with_scope(Scope::default())
+				.build()
+				.unwrap(),
+		);
+
+		let err = scope.try_to_code(Language::CPP).unwrap_err();
+		assert_eq!(
+			err,
+			GenError::MissingType {
+				construct: String::from("parameter"),
+				language: "C++",
+				path: NodePath(vec![
+					String::from("[0]"),
+					String::from("function `add`"),
+					String::from("parameter"),
+				]),
+			}
+		);
+		assert_eq!(
+			err.to_string(),
+			"[0] > function `add` > parameter: C++ requires a type for every parameter"
+		);
+	}
+
+	#[test]
+	fn lowers_into_ir() {
+		use ir::{Expr, Operator, Stmt};
+
+		let mut scope = Scope::default().with(statement::Return(Some(operation::Add("a", 1))));
+		assert_eq!(
+			scope.children(),
+			[Stmt::Return(Some(Expr::Binary(
+				Operator::Add,
+				Box::new(Expr::Ident(Ident::from("a"))),
+				Box::new(Expr::Int(1)),
+			)))]
+		);
+
+		if let Stmt::Return(Some(Expr::Binary(operator, ..))) = &mut scope.children_mut()[0] {
+			*operator = Operator::Multiply;
+		}
+		assert_eq!(scope.to_code(Language::Rust), "return a*1;");
+	}
+
+	#[test]
+	fn parenthesizes_by_precedence() {
+		let scope = Scope::default()
+			.with(statement::Return(Some(operation::Subtract(
+				"a",
+				operation::Subtract(operation::Multiply(operation::Add("b", 1), -2), "c"),
+			))))
+			.with(statement::Return(Some(condition::Or(
+				condition::And(condition::Equal(condition::LessThan("a", "b"), "c"), "d"),
+				condition::And("e", condition::Or("f", "g")),
+			))));
+
+		assert_eq!(
+			scope.to_code(Language::Rust),
+			"return a-((b+1)*(-2)-c);return (a<b)==c&&d||e&&(f||g);"
+		);
+		assert_eq!(
+			scope.to_code(Language::CPP),
+			"return a-((b+1)*(-2)-c);return a<b==c&&d||e&&(f||g);"
+		);
+		assert_eq!(
+			scope.to_code(Language::Python {
+				include_types: false,
+				version: (3, 10)
+			}),
+			"return a-((b+1)*(-2)-c)\nreturn (a<b)==c and d or e and (f or g)"
+		);
+	}
+
+	#[test]
+	fn literals() {
+		let scope = Scope::default()
+			.with(Literal::Str(String::from("say \"hi\" {x}")))
+			.with(Literal::Str(String::from("é\n\u{1b}1")))
+			.with(Literal::Char('\''))
+			.with(Literal::Int {
+				value: 1,
+				suffix: Some(types::Primitive::Float),
+			})
+			.with(Literal::Float(1.0))
+			.with(Literal::Null);
+
+		assert_eq!(
+			scope.to_code(Language::Rust),
+			"r#\"say \"hi\" {x}\"#;\"é\\n\\u{1b}1\";'\\'';1f32;1.0;None;"
+		);
+		assert_eq!(
+			scope.to_code(Language::Python {
+				include_types: false,
+				version: (3, 10)
+			}),
+			"\"say \\\"hi\\\" {x}\"\n\"é\\n\\x1b1\"\n\"'\"\n1.0\n1.0\nNone"
+		);
+		assert_eq!(
+			scope.to_code(Language::CPP),
+			"\"say \\\"hi\\\" {x}\";u8\"é\\n\\0331\";'\\'';1.0;1.0;nullptr;"
+		);
+		assert_eq!(
+			Literal::Str(String::from("é")).to_code(Language::Java { class_name: "Main" }),
+			"\"\\u00e9\""
+		);
+
+		// Layout tokens in strings are escaped instead of laid out.
+		let layout = Scope::default()
+			.with(Literal::Str(String::from("\"a\u{E000}b\u{E001}")))
+			.with(Literal::Char('\u{E002}'));
+		assert_eq!(
+			layout.to_code(Language::Python {
+				include_types: false,
+				version: (3, 10)
+			}),
+			"\"\\\"a\\ue000b\\ue001\"\n\"\\ue002\""
+		);
+		assert_eq!(
+			layout.to_code(Language::Rust),
+			"\"\\\"a\\u{e000}b\\u{e001}\";'\\u{e002}';"
+		);
+		assert_eq!(
+			layout.to_code(Language::CPP),
+			"u8\"\\\"a\\ue000b\\ue001\";U'\\ue002';"
+		);
+		assert_eq!(
+			layout.to_code(Language::Lua),
+			"\"\\\"a\\u{e000}b\\u{e001}\";\"\\u{e002}\";"
+		);
+		assert_eq!(
+			layout.to_code(Language::Zig),
+			"\"\\\"a\\u{e000}b\\u{e001}\";'\\u{e002}';"
+		);
+	}
+
+	#[test]
+	fn escapes_keywords() {
+		let scope = Scope::default().with(
+			FunctionBuilder::new()
+				.name("type")
+				.with_return_type(types::Integer)
+				.with_param(Parameter(Ident::from("class"), Some(types::Integer.into())))
+				.with_scope(
+					Scope::default()
+						.with(
+							statement::VariableInit::default()
+								.with_name("def")
+								.with_type(types::Integer)
+								.with_value(expression::FunctionCall("type", vec!["class".into()])),
+						)
+						.with(statement::Return(Some("def"))),
+				)
+				.build()
+				.unwrap(),
+		);
+
+		assert_eq!(
+			scope.to_code(Language::Rust),
+			"fn r#type(class:i32)->i32{let def:i32=r#type(class);return def;}"
+		);
+		assert_eq!(
+			scope.to_code(Language::Python {
+				include_types: false,
+				version: (3, 10)
+			}),
+			"def type_(class_):\n\tdef_=type_(class_)\n\treturn def_"
+		);
+		assert_eq!(
+			scope.to_code(Language::CPP).split('{').next(),
+			Some("int type(int _class)")
+		);
+	}
+
+	#[test]
+	fn naming_conventions() {
+		let function = |variable: &str| {
+			FunctionBuilder::new()
+				.name("fetchUserName")
+				.with_visibility(Visibility::Public)
+				.with_return_type(types::Integer)
+				.with_param(Parameter(
+					Ident::from("user_id"),
+					Some(types::Integer.into()),
+				))
+				.with_scope(
+					Scope::default()
+						.with(
+							statement::VariableInit::default()
+								.with_name(variable)
+								.with_type(types::Integer)
+								.with_value("user_id"),
+						)
+						.with(statement::Return(Some(variable))),
+				)
+				.build()
+				.unwrap()
+		};
+		let options = RenderOptions {
+			naming: NamingPolicy::Conventional,
+			..RenderOptions::default()
+		};
+
+		let scope = Scope::default().with(function("FullName"));
+		assert_eq!(
+			scope.to_code_with(Language::Rust, options),
+			"pub fn fetch_user_name(user_id:i32)->i32{let full_name:i32=user_id;return full_name;}"
+		);
+		assert_eq!(
+			scope.to_code_with(
+				Language::CSharp {
+					top_level_statements: false
+				},
+				options
+			),
+			"class Program{public static int FetchUserName(int userId){int \
+			 fullName=userId;return fullName;}}"
+		);
+		assert_eq!(
+			scope.to_code_with(Language::Go, options),
+			"package lib\nfunc FetchUserName(userId int) int{var fullName int=userId;return \
+			 fullName;}\n"
+		);
+		assert_eq!(
+			scope.to_code(Language::Rust),
+			"pub fn fetchUserName(user_id:i32)->i32{let FullName:i32=user_id;return FullName;}"
+		);
+
+		let err = Scope::default()
+			.with(function("userId"))
+			.try_to_code_with(Language::Rust, options)
+			.unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			"[0] > function `fetchUserName` > [0] > variable: `user_id` and `userId` are both \
+			 written as `user_id` in Rust"
+		);
+
+		let helper = |name: &str, param: &str| {
+			FunctionBuilder::new()
+				.name(name)
+				.with_param(Parameter(Ident::from(param), Some(types::Integer.into())))
+				.with_scope(Scope::default().with(expression::FunctionCall(
+					"HashMap::new",
+					vec![expression::FunctionCall("parseInt", vec![param.into()]).into()],
+				)))
+				.build()
+				.unwrap()
+		};
+		assert_eq!(
+			Scope::default()
+				.with(helper("a", "user_id"))
+				.with(helper("b", "userId"))
+				.to_code_with(Language::Rust, options),
+			"fn a(user_id:i32){HashMap::new(parseInt(user_id));}fn \
+			 b(user_id:i32){HashMap::new(parseInt(user_id));}"
+		);
+	}
+
+	#[test]
+	fn loops() {
+		let scope = Scope::default().with(RunScope(
+			Scope::default()
+				.with(
+					block::ForRange::new(
+						"i",
+						0,
+						10,
+						Scope::default().with(
+							block::ForEach::new(
+								"x",
+								"xs",
+								Scope::default().with(statement::Continue(Some("rows".into()))),
+							)
+							.with_label("rows"),
+						),
+					)
+					.with_step(2),
+				)
+				.with(block::While::new(
+					Value::True,
+					Scope::default().with(statement::Break(None)),
+				)),
+		));
+
+		assert_eq!(
+			scope.to_code(Language::Rust),
+			"fn main(){for i in (0..10).step_by(2){'rows:for x in xs{continue 'rows;}}while \
+			 true{break;}}"
+		);
+		assert_eq!(
+			scope.to_code(Language::TypeScript),
+			"for(let i=0;i<10;i+=2){rows:for(const x of xs){continue rows;}}while(true){break;}"
+		);
+		assert_eq!(
+			scope.to_code(Language::Python {
+				include_types: false,
+				version: (3, 10)
+			}),
+			"for i in range(0,10,2):\n\tfor x in xs:\n\t\tcontinue\nwhile True:\n\tbreak"
+		);
+		assert_eq!(
+			scope.to_code(Language::CPP).split("while").next(),
+			Some("int main(){for(int i=0;i<10;i+=2){for(auto x:xs){continue;}}")
+		);
+
+		let outer = Scope::default().with(
+			block::While::new(
+				Value::True,
+				Scope::default().with(block::ForRange::new(
+					"i",
+					0,
+					3,
+					Scope::default().with(statement::Break(Some("outer".into()))),
+				)),
+			)
+			.with_label("outer"),
+		);
+		assert_eq!(
+			outer.to_code(Language::TypeScript),
+			"outer:while(true){for(let i=0;i<3;i++){break outer;}}"
+		);
+		assert_eq!(
+			outer.to_code(Language::Go),
+			"package lib\nouter:for true{for i:=0;i<3;i++{break outer;};};"
+		);
+		assert_eq!(
+			Scope::default()
+				.with(
+					block::While::new(Value::True, Scope::default().with(statement::Break(None)),)
+						.with_label("outer"),
+				)
+				.to_code(Language::Go),
+			"package lib\nfor true{break;};"
+		);
+		assert_eq!(
+			outer.try_to_code(Language::PHP).unwrap(),
+			"<?php\nwhile(true){for($i=0;$i<3;$i++){break 2;}}"
+		);
+		assert_eq!(
+			outer.try_to_code(Language::C).unwrap_err().to_string(),
+			"[0] > while > [0] > for > [0] > break: statement::Break out of an outer loop is not \
+			 supported in C"
+		);
+		assert_eq!(
+			Scope::default()
+				.with(statement::Break(Some("outer".into())))
+				.try_to_code(Language::Rust)
+				.unwrap_err()
+				.to_string(),
+			"[0] > break: statement::Break is not inside of a loop labeled `outer`"
+		);
+	}
+
+	#[test]
+	fn structs() {
+		let point = StructBuilder::new()
+			.name("Point")
+			.with_visibility(Visibility::Public)
+			.with_derive("Debug")
+			.with_field(Field::new("x", types::Integer))
+			.with_field(Field::new("y", types::Integer))
+			.build()
+			.unwrap();
+		let scope = Scope::default()
+			.with(point.clone())
+			.with(statement::VariableInit {
+				name: "p".into(),
+				mutable: None,
+				typ: Some(types::Named("Point").into()),
+				value: expression::StructLiteral(
+					"Point",
+					vec![("y".into(), 2.into()), ("x".into(), 1.into())],
+				)
+				.into(),
+			});
+		let sum = print::Println(operation::Add(
+			expression::FieldAccess("p", "x"),
+			expression::FieldAccess("p", "y"),
+		));
+
+		assert_eq!(
+			scope.clone().with(sum).to_code(Language::Rust),
+			"#[derive(Debug)]pub struct Point{pub x:i32,pub y:i32,}let \
+			 p:Point=Point{y:2,x:1};println!(\"{:?}\",p.x+p.y);"
+		);
+		assert_eq!(
+			scope.to_code(Language::CPP),
+			"struct Point{int x;int y;};Point p=Point{1,2};"
+		);
+		assert_eq!(
+			scope.to_code(Language::Python {
+				include_types: true,
+				version: (3, 10)
+			}),
+			"from dataclasses import dataclass\n@dataclass\nclass Point:\n\tx:int\n\ty:int\n\
+			 p:Point=Point(y=2,x=1)"
+		);
+
+		let secret = StructBuilder::new()
+			.name("Secret")
+			.with_field(Field::new("id", types::Integer))
+			.with_field(Field::new("key", types::Integer).with_visibility(Visibility::Private))
+			.build()
+			.unwrap();
+		let scope = Scope::default()
+			.with(secret)
+			.with(expression::StructLiteral(
+				"Secret",
+				vec![("key".into(), 2.into()), ("id".into(), 1.into())],
+			));
+		assert_eq!(
+			scope.to_code(Language::TypeScript),
+			"class Secret{constructor(public id:number,private key:number){}}new Secret(1,2);"
+		);
+		assert_eq!(
+			scope.to_code(Language::CPP),
+			"struct Secret{int id;private:int key;};Secret{1,2};"
+		);
+		assert_eq!(
+			Scope::default()
+				.with(point)
+				.try_to_code(Language::Lua)
+				.unwrap_err()
+				.to_string(),
+			"[0] > struct `Point`: StructBuilder is not supported in Lua"
+		);
+	}
+
+	#[test]
+	fn classes() {
+		let counter = ClassBuilder::new()
+			.name("Counter")
+			.with_field(Field::new("count", types::Integer).with_visibility(Visibility::Private))
+			.with_constructor(Constructor::default().with_field("count", 0))
+			.with_method(Method::new(
+				FunctionBuilder::new()
+					.name("tick")
+					.with_visibility(Visibility::Public)
+					.with_scope(Scope::default().with(statement::AssignVariable(
+						expression::FieldAccess(expression::This, "count"),
+						operation::Add(expression::FieldAccess(expression::This, "count"), 1),
+					)))
+					.build()
+					.unwrap(),
+				Receiver::Mutable,
+			))
+			.with_method(Method::new(
+				FunctionBuilder::new()
+					.name("get")
+					.with_visibility(Visibility::Public)
+					.with_return_type(types::Integer)
+					.with_scope(Scope::default().with(statement::Return(Some(
+						expression::FieldAccess(expression::This, "count"),
+					))))
+					.build()
+					.unwrap(),
+				Receiver::Shared,
+			))
+			.build()
+			.unwrap();
+		let scope = Scope::default().with(counter.clone());
+
+		assert_eq!(
+			scope.to_code(Language::Rust),
+			"struct Counter{count:i32,}impl Counter{pub fn new()->Self{Self{count:0}}pub fn \
+			 tick(&mut self){self.count=self.count+1;}pub fn get(&self)->i32{return self.count;}}"
+		);
+		assert_eq!(
+			scope.to_code(Language::TypeScript),
+			"class Counter{private count:number;constructor(){this.count=0;}tick():void{this.\
+			 count=this.count+1;}get():number{return this.count;}}"
+		);
+		assert_eq!(
+			scope.to_code(Language::CPP),
+			"class Counter{int count;public:Counter():count(0){}void \
+			 tick(){this->count=this->count+1;}int get()const{return this->count;}};"
+		);
+		assert_eq!(
+			scope.to_code(Language::Python {
+				include_types: false,
+				version: (3, 10)
+			}),
+			"class Counter:\n\tdef __init__(self):\n\t\tself.count=0\n\tdef \
+			 tick(self):\n\t\tself.count=self.count+1\n\tdef get(self):\n\t\treturn self.count"
+		);
+		assert!(Scope::default()
+			.with(counter)
+			.with(statement::VariableInit {
+				name: "c".into(),
+				mutable: None,
+				typ: None,
+				value: expression::New("Counter", vec![]).into(),
+			})
+			.with(print::Println(expression::MethodCall("c", "get", vec![])))
+			.to_code(Language::Python {
+				include_types: false,
+				version: (3, 10)
+			})
+			.ends_with("return self.count\nc=Counter()\nprint(c.get())"));
+
+		let origin = ClassBuilder::new()
+			.name("Origin")
+			.with_method(Method::new(
+				FunctionBuilder::new()
+					.name("make")
+					.with_scope(Scope::default().with(statement::Return(Some(expression::This))))
+					.build()
+					.unwrap(),
+				Receiver::Static,
+			))
+			.build()
+			.unwrap();
+		assert_eq!(
+			Scope::default()
+				.with(origin)
+				.try_to_code(Language::Rust)
+				.unwrap_err()
+				.to_string(),
+			"[0] > class `Origin` > method `make` > [0] > return: expression::This is not \
+			 inside of a method with an instance"
+		);
+
+		let acc = ClassBuilder::new()
+			.name("Acc")
+			.with_field(Field::new("total", types::Integer))
+			.with_constructor(Constructor::default().with_field("total", 0))
+			.with_method(Method::new(
+				FunctionBuilder::new()
+					.name("zero")
+					.with_visibility(Visibility::Public)
+					.with_return_type(types::Named("Acc"))
+					.with_scope(
+						Scope::default()
+							.with(statement::Return(Some(expression::New("Acc", vec![])))),
+					)
+					.build()
+					.unwrap(),
+				Receiver::Static,
+			))
+			.build()
+			.unwrap();
+		let java = Language::Java { class_name: "Main" };
+		let total = |object: &str| {
+			Scope::default()
+				.with(acc.clone())
+				.with(
+					statement::VariableInit::default()
+						.with_name("a")
+						.with_value(expression::StaticCall("Acc", "zero", vec![])),
+				)
+				.with(print::Println(expression::FieldAccess(object, "total")))
+		};
+		assert!(total("a")
+			.to_code(java)
+			.ends_with("final var a=Acc.zero();System.out.println(a.total);}"));
+		assert_eq!(
+			total("b").try_to_code(java).unwrap_err().to_string(),
+			"[2] > println: Java requires a type for every object of a field access"
+		);
+	}
+
+	#[test]
+	fn enums() {
+		let shape = EnumBuilder::new()
+			.name("Shape")
+			.with_variant(Variant::new("Circle").with_field("radius", types::Integer))
+			.with_variant(Variant::new("Empty"))
+			.build()
+			.unwrap();
+		let area = FunctionBuilder::new()
+			.name("area")
+			.with_param(Parameter(
+				Ident::from("s"),
+				Some(types::Named("Shape").into()),
+			))
+			.with_return_type(types::Integer)
+			.with_scope(
+				Scope::default().with(
+					block::Match::new("s", "Shape")
+						.with_arm(
+							block::Arm::new(
+								"Circle",
+								Scope::default().with(statement::Return(Some(
+									operation::Multiply("radius", "radius"),
+								))),
+							)
+							.with_binding("radius"),
+						)
+						.with_arm(block::Arm::new(
+							"Empty",
+							Scope::default().with(statement::Return(Some(0))),
+						)),
+				),
+			)
+			.build()
+			.unwrap();
+		let scope =
+			Scope::default()
+				.with(shape)
+				.with(area)
+				.with(print::Println(expression::FunctionCall(
+					"area",
+					vec![expression::VariantLiteral(
+						"Shape",
+						"Circle",
+						vec![("radius".into(), 3.into())],
+					)
+					.into()],
+				)));
+
+		assert_eq!(
+			scope.to_code(Language::Rust),
+			"enum Shape{Circle{radius:i32},Empty,}fn area(s:Shape)->i32{match \
+			 s{Shape::Circle{radius}=>{return radius*radius;}Shape::Empty=>{return \
+			 0;}}}println!(\"{:?}\",area(Shape::Circle{radius:3}));"
+		);
+		assert_eq!(
+			scope.to_code(Language::TypeScript),
+			"type Shape={kind:\"Circle\",radius:number}|{kind:\"Empty\"};function \
+			 area(s:Shape):number{switch(s.kind){case \"Circle\":{const \
+			 radius=s.radius;return radius*radius;}break;case \"Empty\":{return \
+			 0;}break;}}console.log(area({kind:\"Circle\",radius:3}));"
+		);
+		assert_eq!(
+			scope.to_code(Language::CPP),
+			"#include <variant>\nstruct Circle{int radius;};struct Empty{};using \
+			 Shape=std::variant<Circle,Empty>;int area(Shape \
+			 s){if(std::holds_alternative<Circle>(s)){auto \
+			 radius=std::get<Circle>(s).radius;return radius*radius;}else \
+			 if(std::holds_alternative<Empty>(s)){return 0;}}cout << area(Shape{Circle{3}});"
+		);
+		assert_eq!(
+			scope.to_code(Language::Python {
+				include_types: false,
+				version: (3, 10)
+			}),
+			"from dataclasses import dataclass\nclass Shape:\n\tpass\n@dataclass\nclass \
+			 Circle(Shape):\n\tradius:int\n@dataclass\nclass Empty(Shape):\n\tpass\n\
+			 def area(s):\n\tmatch s:\n\t\tcase Circle(radius=radius):\n\t\t\treturn \
+			 radius*radius\n\t\tcase Empty():\n\t\t\treturn 0\nprint(area(Circle(radius=3)))"
+		);
+		assert_eq!(
+			scope.to_code(Language::Python {
+				include_types: false,
+				version: (3, 8)
+			}),
+			"from dataclasses import dataclass\nclass Shape:\n\tpass\n@dataclass\nclass \
+			 Circle(Shape):\n\tradius:int\n@dataclass\nclass Empty(Shape):\n\tpass\n\
+			 def area(s):\n\tif isinstance(s,Circle):\n\t\tradius=s.radius\n\t\treturn \
+			 radius*radius\n\telif isinstance(s,Empty):\n\t\treturn 0\nprint(area(Circle(radius=3)))"
+		);
+
+		let color = EnumBuilder::new()
+			.name("Color")
+			.with_visibility(Visibility::Public)
+			.with_variant(Variant::new("Red"))
+			.with_variant(Variant::new("Green"))
+			.build()
+			.unwrap();
+		let red = block::Arm::new("Red", Scope::default().with(print::Println(1)));
+		let scope = Scope::default().with(color.clone()).with(
+			block::Match::new("c", "Color")
+				.with_arm(red.clone())
+				.with_otherwise(Scope::default().with(print::Println(2))),
+		);
+		assert_eq!(
+			scope.to_code(Language::CPP),
+			"enum class Color{Red,Green};switch(c){case Color::Red:{cout << 1;}break;\
+			 default:{cout << 2;}}"
+		);
+		assert_eq!(
+			scope.to_code(Language::TypeScript),
+			"export enum Color{Red,Green}switch(c){case Color.Red:{console.log(1);}\
+			 break;default:{console.log(2);}}"
+		);
+		assert_eq!(
+			Scope::default()
+				.with(color)
+				.with(block::Match::new("c", "Color").with_arm(red))
+				.try_to_code(Language::Rust)
+				.unwrap_err()
+				.to_string(),
+			"[1] > match: block::Match does not cover `Green` of `Color`"
+		);
+		assert_eq!(
+			Scope::default()
+				.with(block::Match::new("c", "Size"))
+				.try_to_code(Language::Rust)
+				.unwrap_err()
+				.to_string(),
+			"[0] > match: `Size` has not been declared"
+		);
+
+		let empty = |name: &str| {
+			EnumBuilder::new()
+				.name(name)
+				.with_variant(Variant::new("Empty"))
+				.with_variant(Variant::new("Full").with_field("size", types::Integer))
+				.build()
+				.unwrap()
+		};
+		let scope = Scope::default().with(empty("Bag")).with(empty("Box"));
+		assert!(scope.try_to_code(Language::Rust).is_ok());
+		assert_eq!(
+			scope.try_to_code(Language::CPP).unwrap_err().to_string(),
+			"[1] > enum `Box`: `Bag::Empty` and `Box::Empty` are both written as `Empty` in C++"
+		);
+		assert_eq!(
+			scope
+				.try_to_code(Language::Python {
+					include_types: false,
+					version: (3, 10)
+				})
+				.unwrap_err()
+				.to_string(),
+			"[1] > enum `Box`: `Bag::Empty` and `Box::Empty` are both written as `Empty` in Python"
+		);
+	}
+
+	#[test]
+	fn interfaces() {
+		let shape = InterfaceBuilder::new()
+			.name("Shape")
+			.with_visibility(Visibility::Public)
+			.with_method(Signature::new("area", Receiver::Shared).with_return_type(types::Integer))
+			.build()
+			.unwrap();
+		let area = |visibility| {
+			Method::new(
+				FunctionBuilder::new()
+					.name("area")
+					.with_visibility(visibility)
+					.with_return_type(types::Integer)
+					.with_scope(Scope::default().with(statement::Return(Some(
+						expression::FieldAccess(expression::This, "side"),
+					))))
+					.build()
+					.unwrap(),
+				Receiver::Shared,
+			)
+		};
+		let square = ClassBuilder::new()
+			.name("Square")
+			.with_interface("Shape")
+			.with_field(Field::new("side", types::Integer).with_visibility(Visibility::Private))
+			.with_method(area(Visibility::Public))
+			.build()
+			.unwrap();
+		let scope = Scope::default().with(shape.clone()).with(square);
+
+		assert_eq!(
+			scope.to_code(Language::Rust),
+			"pub trait Shape{fn area(&self)->i32;}struct Square{side:i32,}impl Shape for \
+			 Square{fn area(&self)->i32{return self.side;}}"
+		);
+		assert_eq!(
+			scope.to_code(Language::TypeScript),
+			"export interface Shape{area():number;}class Square implements Shape{private \
+			 side:number;area():number{return this.side;}}"
+		);
+		assert_eq!(
+			scope.to_code(Language::CPP),
+			"class Shape{public:virtual ~Shape()=default;virtual int area()const=0;\
+			 };class Square:public Shape{int side;public:int area()const override{return \
+			 this->side;}};"
+		);
+		assert_eq!(
+			scope.to_code(Language::Python {
+				include_types: true,
+				version: (3, 10)
+			}),
+			"from __future__ import annotations\nfrom abc import ABC\nfrom abc import \
+			 abstractmethod\nclass Shape(ABC):\n\t@abstractmethod\n\tdef area(self)->int:\n\
+			 \t\tpass\nclass Square(Shape):\n\tside:int\n\tdef area(self)->int:\n\t\treturn \
+			 self.side"
+		);
+		assert_eq!(
+			Scope::default()
+				.with(shape)
+				.with(
+					ClassBuilder::new()
+						.name("Circle")
+						.with_interface("Shape")
+						.with_method(area(Visibility::Private))
+						.build()
+						.unwrap()
+				)
+				.try_to_code(Language::Rust)
+				.unwrap_err()
+				.to_string(),
+			"[1] > class `Circle`: `Shape::area` is not implemented by a public method with the \
+			 same receiver and parameters"
+		);
+	}
+
+	#[test]
+	fn go_program() {
+		let scope = Scope::default()
+			.with(
+				FunctionBuilder::new()
+					.name("max")
+					.with_visibility(Visibility::Public)
+					.with_return_type(types::Integer)
+					.with_param(Parameter(Ident::from("a"), Some(types::Integer.into())))
+					.with_param(Parameter(Ident::from("b"), Some(types::Integer.into())))
+					.with_scope(Scope::default().with(statement::Return(Some(
+						expression::Ternary(condition::GreaterThan("a", "b"), "a", "b"),
+					))))
+					.build()
+					.unwrap(),
+			)
+			.with(RunScope(
+				Scope::default()
+					.with(
+						statement::VariableInit::default()
+							.with_name("x")
+							.with_value(1),
+					)
+					.with(print::Println(expression::FunctionCall(
+						"max",
+						vec!["x".into(), 2.into()],
+					))),
+			));
+
+		assert_eq!(
+			scope.to_code(Language::Go),
+			"package main\nimport \"fmt\"\nfunc Max(a int,b int) int{return func() int{if \
+			 a>b{return a};return b}();}\nfunc main(){x:=1;fmt.Println(Max(x,2));}\n"
+		);
+		assert!(scope
+			.with(Import::Module(String::from("fmt")))
+			.with(Import::Module(String::from("strings")))
+			.to_code(Language::Go)
+			.starts_with("package main\nimport \"fmt\"\nimport \"strings\"\nfunc Max("));
+
+		// Types are forgotten with the function that declared them, and when a
+		// name is declared again without one.
+		let half = |name: &str, typ: Option<ir::Type>| {
+			FunctionBuilder::new()
+				.name(name)
+				.with_param(Parameter(Ident::from("a"), typ))
+				.with_scope(
+					Scope::default().with(statement::Return(Some(operation::Divide("a", 2)))),
+				)
+				.build()
+				.unwrap()
+		};
+		let scope = Scope::default()
+			.with(half("f", Some(types::Integer.into())))
+			.with(half("g", None))
+			.with(
+				statement::VariableInit::default()
+					.with_name("x")
+					.with_type(types::Integer)
+					.with_value(1),
+			)
+			.with(
+				statement::VariableInit::default()
+					.with_name("x")
+					.with_value("y"),
+			)
+			.with(print::Println(operation::Divide("x", 2)));
+		assert_eq!(
+			scope.to_code(Language::Lua),
+			"local f,g;function f(a) return a//2;end;function g(a) return \
+			 a/2;end;x=1;x=y;print(x/2);"
+		);
+		assert_eq!(
+			Scope::default()
+				.with(half("f", Some(types::Integer.into())))
+				.with(half("g", None))
+				.to_code(Language::Haskell),
+			"f a = a `div` 2\ng a = a/2\n"
+		);
+	}
+
+	#[test]
+	fn java_program() {
+		let scope = Scope::default()
+			.with(Import::Members(
+				String::from("java.util"),
+				vec![String::from("List")],
+			))
+			.with(
+				FunctionBuilder::new()
+					.name("first")
+					.with_visibility(Visibility::Public)
+					.with_return_type(types::Integer)
+					.with_param(Parameter(
+						Ident::from("items"),
+						Some(
+							types::Generic(String::from("List"), vec![types::Integer.into()])
+								.into(),
+						),
+					))
+					.with_scope(Scope::default().with(statement::Return(Some(
+						expression::FunctionCall("items.get", vec![0.into()]),
+					))))
+					.build()
+					.unwrap(),
+			)
+			.with(RunScope(
+				Scope::default()
+					.with(
+						statement::VariableInit::default()
+							.with_name("x")
+							.with_value(expression::FunctionCall(
+								"List.of",
+								vec![1.into(), 2.into()],
+							)),
+					)
+					.with(print::Println(expression::FunctionCall(
+						"first",
+						vec!["x".into()],
+					))),
+			));
+
+		assert_eq!(
+			scope.to_code(Language::Java { class_name: "Main" }),
+			"import java.util.List;public class Main{public static int first(List<Integer> \
+			 items){return items.get(0);}public static void main(String[] args){final var \
+			 x=List.of(1,2);System.out.println(first(x));}}"
+		);
+	}
+
+	#[test]
+	fn csharp_entry_points() {
+		let scope = Scope::default()
+			.with(Comment::Doc(String::from("Adds two numbers.")))
+			.with(
+				FunctionBuilder::new()
+					.name("Add")
+					.with_visibility(Visibility::Public)
+					.with_return_type(types::Integer)
+					.with_param(Parameter(Ident::from("a"), Some(types::Integer.into())))
+					.with_param(Parameter(Ident::from("b"), Some(types::Integer.into())))
+					.with_scope(
+						Scope::default().with(statement::Return(Some(operation::Add("a", "b")))),
+					)
+					.build()
+					.unwrap(),
+			)
+			.with(RunScope(
+				Scope::default()
+					.with(
+						statement::VariableInit::default()
+							.with_name("x")
+							.with_value(1),
+					)
+					.with(print::Println(expression::FunctionCall(
+						"Add",
+						vec!["x".into(), 2.into()],
+					))),
+			));
+
+		assert_eq!(
+			scope.to_code(Language::CSharp {
+				top_level_statements: false
+			}),
+			"using System;class Program{/// <summary>Adds two numbers.</summary>\npublic static \
+			 int Add(int a,int b){return a+b;}static void Main(string[] args){var \
+			 x=1;Console.WriteLine(Add(x,2));}}"
+		);
+		assert_eq!(
+			scope.to_code(Language::CSharp {
+				top_level_statements: true
+			}),
+			"using System;var x=1;Console.WriteLine(Add(x,2));partial class Program{/// \
+			 <summary>Adds two numbers.</summary>\npublic static int Add(int a,int b){return \
+			 a+b;}}"
+		);
+
+		let local = Scope::default().with(RunScope(
+			Scope::default()
+				.with(
+					FunctionBuilder::new()
+						.name("Helper")
+						.with_visibility(Visibility::Public)
+						.with_return_type(types::Integer)
+						.with_param(Parameter(Ident::from("a"), Some(types::Integer.into())))
+						.with_scope(Scope::default().with(statement::Return(Some("a"))))
+						.build()
+						.unwrap(),
+				)
+				.with(print::Println(expression::FunctionCall(
+					"Helper",
+					vec![3.into()],
+				))),
+		));
+		assert_eq!(
+			local.to_code(Language::CSharp {
+				top_level_statements: false
+			}),
+			"using System;class Program{static void Main(string[] args){int Helper(int a){return \
+			 a;}Console.WriteLine(Helper(3));}}"
+		);
+		assert_eq!(
+			local.to_code(Language::CSharp {
+				top_level_statements: true
+			}),
+			"using System;int Helper(int a){return a;}Console.WriteLine(Helper(3));partial class \
+			 Program{}"
+		);
+		assert_eq!(
+			Comment::Doc(String::from("Returns a<b && b>c.")).to_code(Language::CSharp {
+				top_level_statements: false
+			}),
+			"/// <summary>Returns a&lt;b &amp;&amp; b&gt;c.</summary>\n"
+		);
+	}
+
+	#[test]
+	fn c_program() {
+		let scope = Scope::default()
+			.with(
+				FunctionBuilder::new()
+					.name("positive")
+					.with_visibility(Visibility::Public)
+					.with_return_type(types::Boolean)
+					.with_param(Parameter(Ident::from("a"), Some(types::Integer.into())))
+					.with_scope(
+						Scope::default()
+							.with(statement::Return(Some(condition::GreaterThan("a", 0)))),
+					)
+					.build()
+					.unwrap(),
+			)
+			.with(RunScope(
+				Scope::default()
+					.with(
+						statement::VariableInit::default()
+							.with_name("x")
+							.with_value(2),
+					)
+					.with(print::Println("x"))
+					.with(print::Println(expression::FunctionCall(
+						"positive",
+						vec!["x".into()],
+					))),
+			));
+
+		assert_eq!(
+			scope.to_code(Language::C),
+			"#include <stdbool.h>\n#include <stdio.h>\nbool positive(int a){return \
+			 a>0;}int main(void){int x=2;printf(\"%d\\n\",x);printf(\"%s\\n\",positive(x)?\"true\":\"false\");return \
+			 0;}"
+		);
+
+		let err = Parameter(
+			Ident::from("items"),
+			Some(types::Generic(String::from("List"), vec![types::Integer.into()]).into()),
+		)
+		.try_to_code(Language::C)
+		.unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			"parameter: types::Generic is not supported in C"
+		);
+	}
+
+	#[test]
+	fn kotlin_and_swift() {
+		let scope = Scope::default()
+			.with(Import::Module(String::from("Foundation")))
+			.with(Comment::Doc(String::from("The larger number.")))
+			.with(
+				FunctionBuilder::new()
+					.name("max")
+					.with_visibility(Visibility::Public)
+					.with_return_type(types::Integer)
+					.with_param(Parameter(Ident::from("a"), Some(types::Integer.into())))
+					.with_param(Parameter(Ident::from("b"), Some(types::Integer.into())))
+					.with_scope(Scope::default().with(statement::Return(Some(
+						expression::Ternary(condition::GreaterThan("a", "b"), "a", "b"),
+					))))
+					.build()
+					.unwrap(),
+			)
+			.with(RunScope(
+				Scope::default()
+					.with(
+						statement::VariableInit::default()
+							.with_name("x")
+							.with_value(1),
+					)
+					.with(print::Println(expression::FunctionCall(
+						"max",
+						vec!["x".into(), 2.into()],
+					))),
+			));
+
+		assert_eq!(
+			scope.to_code(Language::Kotlin),
+			"import Foundation.*\n/**The larger number.*/public fun \
+			 max(a:Int,b:Int):Int{return if(a>b) a else b;}fun main(){val \
+			 x=1;println(max(x,2));}"
+		);
+		assert_eq!(
+			scope.to_code(Language::Swift),
+			"import Foundation\n///The larger number.\npublic func max(_ a:Int,_ \
+			 b:Int)->Int{return a>b ? a : b;}let x=1;print(max(x,2));"
+		);
+		assert!(scope
+			.with(Import::Module(String::from("kotlin.math")))
+			.to_code(Language::Kotlin)
+			.starts_with("import Foundation.*\nimport kotlin.math.*\n/**"));
+
+		let fetch = FunctionBuilder::new()
+			.name("fetch")
+			.with_return_type(types::Future(types::Integer))
+			.with_scope(Scope::default().with(statement::Return(Some(1))))
+			.build()
+			.unwrap();
+		assert_eq!(
+			fetch.to_code(Language::Kotlin),
+			"import kotlinx.coroutines.Deferred\nprivate fun fetch():Deferred<Int>{return 1;}"
+		);
+		assert_eq!(
+			fetch.to_code(Language::Swift),
+			"private func fetch() async->Int{return 1;}"
+		);
+	}
+
+	#[test]
+	fn lua_module() {
+		let scope = Scope::default()
+			.with(
+				FunctionBuilder::new()
+					.name("half")
+					.with_visibility(Visibility::Public)
+					.with_return_type(types::Integer)
+					.with_param(Parameter(Ident::from("a"), Some(types::Integer.into())))
+					.with_scope(
+						Scope::default().with(statement::Return(Some(operation::Divide("a", 2)))),
+					)
+					.build()
+					.unwrap(),
+			)
+			.with(
+				FunctionBuilder::new()
+					.name("describe")
+					.with_param(Parameter(Ident::from("a"), Some(types::Integer.into())))
+					.with_scope(
+						Scope::default().with(
+							block::ConditionalBuilder::default()
+								.with_if(
+									block::If::default()
+										.with_condition(condition::NotEqual("a", 0))
+										.with_scope(Scope::default().with(print::Println(
+											expression::Ternary(
+												condition::GreaterThan("a", 0),
+												Value::True,
+												Value::False,
+											),
+										))),
+								)
+								.with_else(block::Else::default().with_scope(
+									Scope::default().with(print::Println(
+										expression::FunctionCall("half", vec!["a".into()]),
+									)),
+								)),
+						),
+					)
+					.build()
+					.unwrap(),
+			);
+
+		assert_eq!(
+			scope.to_code(Language::Lua),
+			"local M={};local describe;function M.half(a) return a//2;end;function describe(a) \
+			 if a~=0 then print((function() if a>0 then return true;else return \
+			 false;end;end)());else print(M.half(a));end;end;return M;"
+		);
+
+		let scope = Scope::default()
+			.with(
+				FunctionBuilder::new()
+					.name("pubf")
+					.with_visibility(Visibility::Public)
+					.with_scope(
+						Scope::default()
+							.with(expression::FunctionCall("privf", vec![]))
+							.with(statement::Return(Some(1)))
+							.with(print::Println(2)),
+					)
+					.build()
+					.unwrap(),
+			)
+			.with(
+				FunctionBuilder::new()
+					.name("privf")
+					.with_scope(Scope::default())
+					.build()
+					.unwrap(),
+			);
+		assert_eq!(
+			scope.to_code(Language::Lua),
+			"local M={};local privf;function M.pubf() privf();do return 1;end;print(2);end;function \
+			 privf() end;return M;"
+		);
+		assert_eq!(
+			FunctionBuilder::new()
+				.name("privf")
+				.with_scope(Scope::default())
+				.build()
+				.unwrap()
+				.to_code(Language::Lua),
+			"local function privf() end;"
+		);
+
+		// Only the outermost scope has a module table.
+		let nested = Scope::default().with(RunScope(
+			Scope::default()
+				.with(
+					FunctionBuilder::new()
+						.name("helper")
+						.with_visibility(Visibility::Public)
+						.with_param(Parameter(Ident::from("a"), None))
+						.with_scope(Scope::default().with(statement::Return(Some("a"))))
+						.build()
+						.unwrap(),
+				)
+				.with(print::Println(expression::FunctionCall(
+					"helper",
+					vec![3.into()],
+				))),
+		));
+		assert_eq!(
+			nested.to_code(Language::Lua),
+			"local helper;function helper(a) return a;end;print(helper(3));"
+		);
+	}
+
+	#[test]
+	fn ruby_and_php() {
+		let scope = Scope::default()
+			.with(
+				FunctionBuilder::new()
+					.name("sign")
+					.with_return_type(types::Integer)
+					.with_param(Parameter(Ident::from("a"), Some(types::Integer.into())))
+					.with_scope(
+						Scope::default()
+							.with(
+								block::ConditionalBuilder::default()
+									.with_if(
+										block::If::default()
+											.with_condition(condition::Equal("a", 0))
+											.with_scope(
+												Scope::default().with(statement::Return(Some(0))),
+											),
+									)
+									.with_else_if(
+										block::If::default()
+											.with_condition(condition::LessThan("a", 0))
+											.with_scope(Scope::default().with(statement::Return(
+												Some(operation::Subtract(0, 1)),
+											))),
+									),
+							)
+							.with(statement::Return(Some(1))),
+					)
+					.build()
+					.unwrap(),
+			)
+			.with(RunScope(
+				Scope::default()
+					.with(
+						statement::VariableInit::default()
+							.with_name("x")
+							.with_value(expression::FunctionCall("sign", vec![2.into()])),
+					)
+					.with(print::Println("x")),
+			));
+
+		assert_eq!(
+			scope.to_code(Language::Ruby),
+			"def sign(a);if a==0;return 0;elsif a<0;return 0-1;end;1;end;x=sign(2);puts(x);"
+		);
+		assert_eq!(
+			scope.to_code(Language::PHP),
+			"<?php\nfunction sign(int $a):int{if($a===0){return 0;}elseif($a<0){return \
+			 0-1;}return 1;}$x=sign(2);echo $x,PHP_EOL;"
+		);
+
+		let shadowed = |name: &str, param: &str, scope: Scope| {
+			FunctionBuilder::new()
+				.name(name)
+				.with_param(Parameter(Ident::from(param), None))
+				.with_scope(scope)
+				.build()
+				.unwrap()
+		};
+		let count = Scope::default().with(statement::Return(Some(expression::FunctionCall(
+			"count",
+			vec!["count".into()],
+		))));
+		assert_eq!(
+			Scope::default()
+				.with(shadowed("count", "count", count))
+				.with(shadowed("clamp", "max", Scope::default()))
+				.with(print::Println(expression::FunctionCall(
+					"max",
+					vec![1.into(), 2.into()]
+				)))
+				.to_code(Language::PHP),
+			"<?php\nfunction count($count){return count($count);}function clamp($max){}echo \
+			 max(1,2),PHP_EOL;"
+		);
+	}
+
+	#[test]
+	fn zig_program() {
+		let scope = Scope::default()
+			.with(
+				FunctionBuilder::new()
+					.name("half")
+					.with_visibility(Visibility::Public)
+					.with_return_type(types::Integer)
+					.with_param(Parameter(Ident::from("a"), Some(types::Integer.into())))
+					.with_scope(
+						Scope::default().with(statement::Return(Some(operation::Divide("a", 2)))),
+					)
+					.build()
+					.unwrap(),
+			)
+			.with(RunScope(
+				Scope::default()
+					.with(statement::VariableInit {
+						name: Ident::from("x"),
+						mutable: Some(true),
+						typ: None,
+						value: 4.into(),
+					})
+					.with(statement::AssignVariable(
+						"x",
+						expression::FunctionCall("half", vec!["x".into()]),
+					))
+					.with(print::Println("x")),
+			));
+
+		assert_eq!(
+			scope.to_code(Language::Zig),
+			"const std=@import(\"std\");\npub fn half(a:i32) i32{return \
+			 @divTrunc(a,2);}pub fn main() void{var x:i32=4;x=half(x);std.debug.print(\"{}\\n\",.\
+			 {x});}"
+		);
+		assert!(scope
+			.with(Import::Module(String::from("std")))
+			.to_code(Language::Zig)
+			.starts_with("const std=@import(\"std\");\npub fn half("));
+
+		let scope = Scope::default()
+			.with(Import::Module(String::from("a.b")))
+			.with(RunScope(
+				Scope::default()
+					.with(
+						statement::VariableInit::default()
+							.with_name("s")
+							.with_value(expression::FunctionCall("name", vec![])),
+					)
+					.with(print::Println("s")),
+			));
+		assert_eq!(
+			scope.to_code(Language::Zig),
+			"const b=@import(\"a.b\");\nconst std=@import(\"std\");\npub fn main() void{const \
+			 s=name();std.debug.print(\"{s}\\n\",.{s});}"
+		);
+	}
+
+	#[test]
+	fn javascript_modules() {
+		let scope = Scope::default()
+			.with(Import::Members(
+				String::from("./math"),
+				vec![String::from("sub")],
+			))
+			.with(Comment::Doc(String::from("Adds two numbers.")))
+			.with(
+				FunctionBuilder::new()
+					.name("add")
+					.with_visibility(Visibility::Public)
+					.with_return_type(types::Integer)
+					.with_param(Parameter(Ident::from("a"), Some(types::Integer.into())))
+					.with_param(Parameter(Ident::from("b"), Some(types::Integer.into())))
+					.with_scope(
+						Scope::default().with(statement::Return(Some(operation::Add("a", "b")))),
+					)
+					.build()
+					.unwrap(),
+			);
+
+		assert_eq!(
+			scope.to_code(Language::JavaScript {
+				module_system: ModuleSystem::ESM
+			}),
+			"import {sub} from \"./math\";/**Adds two numbers.\n * @param {number} a\n * \
+			 @param {number} b\n * @returns {number}\n */export function add(a,b){return a+b;}"
+		);
+		assert_eq!(
+			scope.to_code(Language::JavaScript {
+				module_system: ModuleSystem::CommonJS
+			}),
+			"const {sub}=require(\"./math\");/**Adds two numbers.\n * @param {number} a\n * \
+			 @param {number} b\n * @returns {number}\n */function add(a,b){return \
+			 a+b;}module.exports.add=add;"
+		);
+	}
+
+	#[test]
+	fn haskell_program() {
+		let scope = Scope::default()
+			.with(
+				FunctionBuilder::new()
+					.name("clamp")
+					.with_return_type(types::Integer)
+					.with_param(Parameter(Ident::from("a"), Some(types::Integer.into())))
+					.with_scope(
+						Scope::default()
+							.with(
+								statement::VariableInit::default()
+									.with_name("limit")
+									.with_value(10),
+							)
+							.with(
+								block::ConditionalBuilder::default().with_if(
+									block::If::default()
+										.with_condition(condition::GreaterThan("a", "limit"))
+										.with_scope(
+											Scope::default().with(statement::Return(Some("limit"))),
+										),
+								),
+							)
+							.with(statement::Return(Some("a"))),
+					)
+					.build()
+					.unwrap(),
+			)
+			.with(RunScope(Scope::default().with(print::Println(
+				expression::FunctionCall("clamp", vec![12.into()]),
+			))));
+
+		assert_eq!(
+			scope.to_code(Language::Haskell),
+			"clamp :: Int -> Int\nclamp a = let {limit = 10} in if a>limit then limit else \
+			 a\nmain :: IO ()\nmain = do {print (clamp 12)}\n"
+		);
+
+		let err = Scope::default()
+			.with(RunScope(
+				Scope::default().with(statement::AssignVariable("x", 1)),
+			))
+			.try_to_code(Language::Haskell)
+			.unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			"[0] > run scope > [0] > assignment: statement::AssignVariable is not supported in Haskell"
+		);
+	}
+
+	#[test]
+	fn wat_module() {
+		let scope = Scope::default()
+			.with(
+				FunctionBuilder::new()
+					.name("max")
+					.with_visibility(Visibility::Public)
+					.with_return_type(types::Integer)
+					.with_param(Parameter(Ident::from("a"), Some(types::Integer.into())))
+					.with_param(Parameter(Ident::from("b"), Some(types::Integer.into())))
+					.with_scope(
+						Scope::default()
+							.with(
+								block::ConditionalBuilder::default().with_if(
+									block::If::default()
+										.with_condition(condition::LessThan("a", "b"))
+										.with_scope(
+											Scope::default().with(statement::Return(Some("b"))),
+										),
+								),
+							)
+							.with(statement::Return(Some("a"))),
+					)
+					.build()
+					.unwrap(),
+			)
+			.with(RunScope(
+				Scope::default()
+					.with(
+						statement::VariableInit::default()
+							.with_name("x")
+							.with_type(types::Integer)
+							.with_value(expression::FunctionCall("max", vec![1.into(), 2.into()])),
+					)
+					.with(print::Println("x")),
+			));
+
+		assert_eq!(
+			scope.to_code(Language::WAT),
+			"(module (import \"env\" \"log\" (func $log_i32 (param i32))) (func $max (export \
+			 \"max\") (param $a i32) (param $b i32) (result i32) (if (i32.lt_s (local.get $a) \
+			 (local.get $b)) (then (return (local.get $b)))) (return (local.get $a))) (func $main \
+			 (export \"main\") (local $x i32) (local.set $x (call $max (i32.const 1) (i32.const \
+			 2))) (call $log_i32 (local.get $x))))"
+		);
+
+		let floats = Scope::default().with(RunScope(
+			Scope::default()
+				.with(
+					statement::VariableInit::default()
+						.with_name("f")
+						.with_type(types::Float)
+						.with_value(1),
+				)
+				.with(statement::AssignVariable("f", operation::Add("f", 1)))
+				.with(statement::AssignVariable("f", 2)),
+		));
+		let conversions = Scope::default()
+			.with(
+				FunctionBuilder::new()
+					.name("half")
+					.with_return_type(types::Float)
+					.with_param(Parameter(Ident::from("x"), Some(types::Float.into())))
+					.with_scope(Scope::default().with(statement::Return(Some(0))))
+					.build()
+					.unwrap(),
+			)
+			.with(RunScope(
+				Scope::default()
+					.with(
+						statement::VariableInit::default()
+							.with_name("h")
+							.with_type(types::Float)
+							.with_value(expression::FunctionCall("half", vec![3.into()])),
+					)
+					.with(statement::AssignVariable(
+						"h",
+						expression::Ternary(condition::GreaterThan("h", 1.5), "h", 1),
+					)),
+			));
+		assert_eq!(
+			conversions.to_code(Language::WAT),
+			"(module (func $half (param $x f64) (result f64) (return (f64.const 0))) (func $main \
+			 (export \"main\") (local $h f64) (local.set $h (call $half (f64.const 3))) (local.set \
+			 $h (select (local.get $h) (f64.const 1) (f64.gt (local.get $h) (f64.const 1.5))))))"
+		);
+		assert_eq!(
+			floats.to_code(Language::WAT),
+			"(module (func $main (export \"main\") (local $f f64) (local.set $f (f64.const 1)) \
+			 (local.set $f (f64.add (local.get $f) (f64.const 1))) (local.set $f (f64.const 2))))"
+		);
+
+		let err = Scope::default()
+			.with(RunScope(
+				Scope::default().with(
+					statement::VariableInit::default()
+						.with_name("x")
+						.with_value(1),
+				),
+			))
+			.try_to_code(Language::WAT)
+			.unwrap_err();
+		assert_eq!(
+			err.to_string(),
+			"[0] > run scope > [0] > variable: WAT requires a type for every variable"
+		);
+	}
+
+	#[test]
+	fn python_nested_blocks() {
+		let program = Scope::default()
+			.with(Import::Module(String::from("math")))
+			.with(RunScope(
+				Scope::default()
+					.with(
+						FunctionBuilder::new()
+							.name("sign")
+							.with_param(Parameter(Ident::from("a"), None))
+							.with_scope(
+								Scope::default().with(
+									block::ConditionalBuilder::default()
+										.with_if(
+											block::If::default()
+												.with_condition(condition::LessThan("a", 0))
+												.with_scope(
+													Scope::default().with(
+														block::ConditionalBuilder::default()
+															.with_if(
+																block::If::default()
+																	.with_condition(
+																		condition::LessThan(
+																			"a", 10,
+																		),
+																	)
+																	.with_scope(Scope::default()),
+															),
+													),
+												),
+										)
+										.with_else(block::Else::default().with_scope(
+											Scope::default().with(statement::Return(Some(1))),
+										)),
+								),
+							)
+							.build()
+							.unwrap(),
+					)
+					.with(print::Println(expression::FunctionCall(
+						"sign",
+						vec![2.into()],
+					))),
+			));
+
+		assert_eq!(
+			program.to_code_with(
+				Language::Python {
+					include_types: false,
+					version: (3, 10)
+				},
+				RenderOptions {
+					indent_type: IndentType::Space(4),
+					..RenderOptions::default()
+				}
+			),
+			"import math\ndef sign(a):\n    if a<0:\n        if a<10:\n            \
+			 pass\n    else:\n        return 1\nprint(sign(2))"
+		);
+		assert_eq!(
+			program
+				.try_to_code(Language::Java { class_name: "Main" })
+				.unwrap_err()
+				.to_string(),
+			"[1] > run scope > [0] > function `sign`: nested functions is not supported in Java"
+		);
+	}
+
+	#[test]
+	fn pretty_printing() {
+		let scope = Scope::default().with(
+			FunctionBuilder::new()
+				.name("max")
+				.with_return_type(types::Integer)
+				.with_param(Parameter(Ident::from("a"), Some(types::Integer.into())))
+				.with_param(Parameter(Ident::from("b"), Some(types::Integer.into())))
+				.with_scope(
+					Scope::default()
+						.with(
+							block::ConditionalBuilder::default()
+								.with_if(
+									block::If::default()
+										.with_condition(condition::GreaterThan("a", "b"))
+										.with_scope(
+											Scope::default().with(statement::Return(Some("a"))),
+										),
+								)
+								.with_else(block::Else::default().with_scope(Scope::default())),
+						)
+						.with(statement::Return(Some("b"))),
+				)
+				.build()
+				.unwrap(),
+		);
+
+		assert_eq!(
+			scope.to_code_with(Language::CPP, RenderOptions::pretty()),
+			"int max(int a, int b) {\n\tif(a > b) {\n\t\treturn a;\n\t} else {}\n\treturn \
+			 b;\n}"
+		);
+		assert_eq!(
+			scope.to_code_with(
+				Language::TypeScript,
+				RenderOptions {
+					brace_style: BraceStyle::Allman,
+					indent_type: IndentType::Space(2),
+					max_width: Some(10),
+					..RenderOptions::pretty()
+				}
+			),
+			"function max(\n  a:number,\n  b:number,\n):number\n{\n  if(a > b)\n  {\n    \
+			 return a;\n  }\n  else\n  {}\n  return b;\n}"
+		);
+		let narrow = RenderOptions {
+			max_width: Some(10),
+			..RenderOptions::pretty()
+		};
+		assert_eq!(
+			scope.to_code_with(Language::CPP, narrow),
+			"int max(\n\tint a,\n\tint b\n) {\n\tif(a > b) {\n\t\treturn a;\n\t} else \
+			 {}\n\treturn b;\n}"
+		);
+		assert_eq!(
+			scope.to_code_with(
+				Language::JavaScript {
+					module_system: ModuleSystem::ESM
+				},
+				RenderOptions::pretty()
+			),
+			"function max(a, b) {\n\tif(a > b) {\n\t\treturn a;\n\t} else {}\n\treturn b;\n}"
 		);
 	}
 }
@@ -1,18 +1,20 @@
 #[macro_export]
 macro_rules! new_comparator {
 	($name:ident, $operator:expr) => {
-		#[derive(Debug)]
-		pub struct $name<T: ToCode, F: ToCode>(pub T, pub F);
+		#[derive(Debug, Clone)]
+		pub struct $name<T, F>(pub T, pub F);
 
-		impl<T: ToCode + std::fmt::Debug, F: ToCode + std::fmt::Debug> ToCode for $name<T, F> {
-			fn to_code(&self, language: Language) -> String {
-				let Self(a, b) = self;
-				format!(
-					"({}{}{})",
-					a.to_code(language),
-					$operator,
-					b.to_code(language)
-				)
+		impl<T: Into<Expr>, F: Into<Expr>> From<$name<T, F>> for Expr {
+			fn from(value: $name<T, F>) -> Self {
+				let $name(a, b) = value;
+				Expr::Binary($operator, Box::new(a.into()), Box::new(b.into()))
+			}
+		}
+
+		impl<T: Into<Expr>, F: Into<Expr>> From<$name<T, F>> for Stmt {
+			#[inline]
+			fn from(value: $name<T, F>) -> Self {
+				Stmt::Expr(value.into())
 			}
 		}
 	};
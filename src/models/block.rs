@@ -1,8 +1,26 @@
-use itertools::Itertools;
+use crate::{
+	backends::Context,
+	ir::{Expr, Stmt, Type},
+	types::Primitive,
+	GenError, Ident, Scope, ToCode,
+};
 
-use crate::{Language, Scope, ToCode};
+/// An `if`/`else if`/`else` chain, built with [`ConditionalBuilder`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conditional {
+	/// The condition and scope of the `if` block followed by every `else if`
+	/// block.
+	pub branches: Vec<(Expr, Scope)>,
+	pub otherwise: Option<Scope>,
+}
 
-#[derive(Debug)]
+impl ToCode for Conditional {
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.within("if", |cx| cx.backend().conditional(self, cx))
+	}
+}
+
+#[derive(Debug, Default)]
 pub struct ConditionalBuilder {
 	first: If,
 	middle: Vec<If>,
@@ -26,111 +44,43 @@ impl ConditionalBuilder {
 	}
 }
 
-impl ToCode for ConditionalBuilder {
-	fn to_code(&self, language: Language) -> String {
-		let Self {
-			first,
-			middle,
-			last,
-		} = self;
-		match language {
-			Language::Rust | Language::TypeScript | Language::CPP => {
-				format!(
-					"{}{}{}{}",
-					first.to_code(language),
-					middle
-						.iter()
-						.map(|itm| format!("else {}", itm.to_code(language)))
-						.join(""),
-					match last {
-						Some(last) => format!("else{{{}}}", last.scope.to_code(language)),
-						None => String::new(),
-					},
-					match language {
-						Language::Rust => ";",
-						_ => "",
-					}
-				)
-			}
-			Language::Python { .. } => Scope::default()
-				.with(self.first.to_code(language))
-				.with(
-					self.middle
-						.iter()
-						.map(|itm| format!("el{}", itm.to_code(language)))
-						.join(""),
-				)
-				.with(match self.last {
-					Some(ref val) => val.to_code(language),
-					_ => String::new(),
-				})
-				.to_code(language),
+impl From<ConditionalBuilder> for Conditional {
+	fn from(builder: ConditionalBuilder) -> Self {
+		Self {
+			branches: std::iter::once(builder.first)
+				.chain(builder.middle)
+				.map(|itm| (itm.condition, itm.scope))
+				.collect(),
+			otherwise: builder.last.map(|itm| itm.scope),
 		}
 	}
 }
 
-impl Default for ConditionalBuilder {
-	fn default() -> Self {
-		Self {
-			first: If::default(),
-			middle: Vec::new(),
-			last: None,
-		}
+impl From<ConditionalBuilder> for Stmt {
+	#[inline]
+	fn from(builder: ConditionalBuilder) -> Self {
+		Self::Conditional(builder.into())
 	}
 }
 
 #[derive(Debug)]
 pub struct If {
-	condition: Box<dyn ToCode>,
+	condition: Expr,
 	scope: Scope,
 }
 
-impl ToCode for If {
-	fn to_code(&self, language: Language) -> String {
-		let Self { condition, scope } = self;
-		match language {
-			Language::Rust => format!(
-				"if {}{{{}}}",
-				condition.to_code(language),
-				scope.to_code(language)
-			),
-			Language::TypeScript | Language::CPP => format!(
-				"if({}){{{}}}",
-				condition.to_code(language),
-				scope.to_code(language)
-			),
-			Language::Python {
-				include_types,
-				indent_level,
-				indent_type,
-			} => format!(
-				"if {}:{}",
-				condition.to_code(language),
-				scope.to_code(Language::Python {
-					include_types,
-					indent_level: Some(match indent_level {
-						Some(lvl) => lvl + 1,
-						None => 1,
-					}),
-					indent_type
-				})
-			),
-		}
-	}
-}
-
 impl Default for If {
 	fn default() -> Self {
 		Self {
-			condition: Box::new("1"),
+			condition: Expr::Int(1),
 			scope: Scope::default(),
 		}
 	}
 }
 
 impl If {
-	pub fn with_condition(mut self, condition: impl ToCode + 'static) -> Self {
-		self.condition = Box::new(condition);
+	pub fn with_condition(mut self, condition: impl Into<Expr>) -> Self {
+		self.condition = condition.into();
 		self
 	}
 
@@ -140,49 +90,270 @@ impl If {
 	}
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct Else {
 	scope: Scope,
 }
 
-impl ToCode for Else {
-	fn to_code(&self, language: Language) -> String {
-		match language {
-			Language::Rust | Language::TypeScript | Language::CPP => {
-				format!("else{{{}}}", self.scope.to_code(language))
-			}
-			Language::Python {
-				include_types,
-				indent_level,
-				indent_type,
-			} => {
-				format!(
-					"else:{}",
-					self.scope.to_code(Language::Python {
-						include_types,
-						indent_level: Some(match indent_level {
-							Some(lvl) => lvl + 1,
-							None => 1,
-						}),
-						indent_type
-					})
-				)
+impl Else {
+	pub fn with_scope(mut self, scope: Scope) -> Self {
+		self.scope = scope;
+		self
+	}
+}
+
+/// A loop that runs `scope` for as long as `condition` holds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct While {
+	/// The name that [`crate::statement::Break`] and
+	/// [`crate::statement::Continue`] refer to the loop by.
+	pub label: Option<String>,
+	pub condition: Expr,
+	pub scope: Scope,
+}
+
+impl While {
+	pub fn new(condition: impl Into<Expr>, scope: Scope) -> Self {
+		Self {
+			label: None,
+			condition: condition.into(),
+			scope,
+		}
+	}
+
+	pub fn with_label(mut self, label: impl Into<String>) -> Self {
+		self.label = Some(label.into());
+		self
+	}
+}
+
+impl ToCode for While {
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.within("while", |cx| {
+			cx.looping(self.label.as_deref(), |cx| {
+				cx.backend().while_loop(self, cx)
+			})
+		})
+	}
+}
+
+impl From<While> for Stmt {
+	#[inline]
+	fn from(while_loop: While) -> Self {
+		Self::While(while_loop)
+	}
+}
+
+/// A loop that counts the integer `var` from `start` up to, but not including,
+/// `end`. A negative `step` counts down to `end` instead.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForRange {
+	/// See [`While::label`].
+	pub label: Option<String>,
+	pub var: Ident,
+	pub start: Expr,
+	pub end: Expr,
+	pub step: i64,
+	pub scope: Scope,
+}
+
+impl ForRange {
+	pub fn new(
+		var: impl Into<Ident>,
+		start: impl Into<Expr>,
+		end: impl Into<Expr>,
+		scope: Scope,
+	) -> Self {
+		Self {
+			label: None,
+			var: var.into(),
+			start: start.into(),
+			end: end.into(),
+			step: 1,
+			scope,
+		}
+	}
+
+	pub fn with_step(mut self, step: i64) -> Self {
+		self.step = step;
+		self
+	}
+
+	pub fn with_label(mut self, label: impl Into<String>) -> Self {
+		self.label = Some(label.into());
+		self
+	}
+}
+
+impl ToCode for ForRange {
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.within("for", |cx| {
+			if self.step == 0 {
+				return Err(cx.unsupported("block::ForRange with a step of zero"));
 			}
+			cx.declare_variable(self.var.as_str());
+			cx.declare(self.var.as_str(), Type::Primitive(Primitive::Integer));
+			cx.looping(self.label.as_deref(), |cx| cx.backend().for_range(self, cx))
+		})
+	}
+}
+
+impl From<ForRange> for Stmt {
+	#[inline]
+	fn from(for_range: ForRange) -> Self {
+		Self::ForRange(for_range)
+	}
+}
+
+/// A loop that binds `var` to each element of `iterable` in turn.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForEach {
+	/// See [`While::label`].
+	pub label: Option<String>,
+	pub var: Ident,
+	pub iterable: Expr,
+	pub scope: Scope,
+}
+
+impl ForEach {
+	pub fn new(var: impl Into<Ident>, iterable: impl Into<Expr>, scope: Scope) -> Self {
+		Self {
+			label: None,
+			var: var.into(),
+			iterable: iterable.into(),
+			scope,
 		}
 	}
+
+	pub fn with_label(mut self, label: impl Into<String>) -> Self {
+		self.label = Some(label.into());
+		self
+	}
 }
 
-impl Default for Else {
-	fn default() -> Self {
+impl ToCode for ForEach {
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.within("for", |cx| {
+			cx.declare_variable(self.var.as_str());
+			cx.looping(self.label.as_deref(), |cx| cx.backend().for_each(self, cx))
+		})
+	}
+}
+
+impl From<ForEach> for Stmt {
+	#[inline]
+	fn from(for_each: ForEach) -> Self {
+		Self::ForEach(for_each)
+	}
+}
+
+/// The branch of a [`Match`] that is taken for one variant.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Arm {
+	pub variant: Ident,
+	/// Fields of the variant that are bound to variables of the same name.
+	pub bindings: Vec<Ident>,
+	pub scope: Scope,
+}
+
+impl Arm {
+	pub fn new(variant: impl Into<Ident>, scope: Scope) -> Self {
 		Self {
-			scope: Scope::default(),
+			variant: variant.into(),
+			bindings: Vec::new(),
+			scope,
 		}
 	}
+
+	pub fn with_binding(mut self, field: impl Into<Ident>) -> Self {
+		self.bindings.push(field.into());
+		self
+	}
 }
 
-impl Else {
-	pub fn with_scope(mut self, scope: Scope) -> Self {
-		self.scope = scope;
+/// Branches on the variant of `subject`, whose type is the enum
+/// `enumeration`. Every variant has to have an arm unless there is an
+/// `otherwise` scope.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Match {
+	pub subject: Expr,
+	pub enumeration: Ident,
+	pub arms: Vec<Arm>,
+	pub otherwise: Option<Scope>,
+}
+
+impl Match {
+	pub fn new(subject: impl Into<Expr>, enumeration: impl Into<Ident>) -> Self {
+		Self {
+			subject: subject.into(),
+			enumeration: enumeration.into(),
+			arms: Vec::new(),
+			otherwise: None,
+		}
+	}
+
+	pub fn with_arm(mut self, arm: Arm) -> Self {
+		self.arms.push(arm);
+		self
+	}
+
+	pub fn with_otherwise(mut self, scope: Scope) -> Self {
+		self.otherwise = Some(scope);
 		self
 	}
 }
+
+impl ToCode for Match {
+	/// Checks the arms against the declaration of the enum, which has to be
+	/// rendered first.
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.within("match", |cx| {
+			let name = self.enumeration.as_str();
+			let enumeration =
+				cx.enumeration(name)
+					.cloned()
+					.ok_or_else(|| GenError::UndeclaredName {
+						name: name.to_owned(),
+						path: cx.path(),
+					})?;
+			for arm in &self.arms {
+				let path = cx.path();
+				let undeclared = |member: &str| GenError::UndeclaredName {
+					name: format!("{}::{}", name, member),
+					path: path.clone(),
+				};
+				let variant = enumeration
+					.variant(arm.variant.as_str())
+					.ok_or_else(|| undeclared(arm.variant.as_str()))?;
+				for binding in &arm.bindings {
+					let field = variant.field(binding.as_str()).ok_or_else(|| {
+						undeclared(&format!("{}::{}", arm.variant.as_str(), binding.as_str()))
+					})?;
+					cx.declare_variable(binding.as_str());
+					cx.declare(binding.as_str(), field.typ.clone());
+				}
+			}
+			let missing = enumeration
+				.variants
+				.iter()
+				.filter(|variant| !self.arms.iter().any(|arm| arm.variant == variant.name))
+				.map(|variant| format!("`{}`", variant.name.as_str()))
+				.collect::<Vec<_>>();
+			if !missing.is_empty() && self.otherwise.is_none() {
+				return Err(GenError::NonExhaustiveMatch {
+					enumeration: name.to_owned(),
+					missing: missing.join(", "),
+					path: cx.path(),
+				});
+			}
+			cx.backend().match_block(self, &enumeration, cx)
+		})
+	}
+}
+
+impl From<Match> for Stmt {
+	#[inline]
+	fn from(match_block: Match) -> Self {
+		Self::Match(match_block)
+	}
+}
@@ -0,0 +1,237 @@
+use crate::{
+	backends::Context,
+	ir::{Expr, Item, Stmt},
+	Field, Function, GenError, Ident, Parameter, ToCode, Visibility,
+};
+
+/// How a [`Method`] gets hold of the instance it is called on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Receiver {
+	/// No instance, e.g. a `static` method.
+	Static,
+	/// An instance that is only read, e.g. `&self` in Rust or a `const`
+	/// method in C++.
+	Shared,
+	/// An instance whose fields may be assigned, e.g. `&mut self` in Rust.
+	Mutable,
+}
+
+/// A [`Function`] that belongs to a [`Class`]. [`crate::expression::This`]
+/// refers to the instance inside of methods that have one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Method {
+	pub function: Function,
+	pub receiver: Receiver,
+}
+
+impl Method {
+	#[inline]
+	pub fn new(function: Function, receiver: Receiver) -> Self {
+		Self { function, receiver }
+	}
+}
+
+impl ToCode for Method {
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		let function = &self.function;
+		cx.within(format!("method `{}`", function.name.as_str()), |cx| {
+			cx.nested(|cx| cx.receiving(self.receiver, |cx| cx.backend().method(self, cx)))
+		})
+	}
+}
+
+/// Creates an instance of a [`Class`] by giving each field a value that is
+/// computed from the parameters. The instance does not exist yet, so
+/// [`crate::expression::This`] cannot be used.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Constructor {
+	pub visibility: Visibility,
+	pub params: Vec<Parameter>,
+	pub fields: Vec<(Ident, Expr)>,
+}
+
+impl Default for Constructor {
+	fn default() -> Self {
+		Self {
+			visibility: Visibility::Public,
+			params: Vec::new(),
+			fields: Vec::new(),
+		}
+	}
+}
+
+impl Constructor {
+	pub fn with_visibility(mut self, visibility: Visibility) -> Self {
+		self.visibility = visibility;
+		self
+	}
+
+	pub fn with_param(mut self, param: Parameter) -> Self {
+		self.params.push(param);
+		self
+	}
+
+	/// Sets the field `name` to `value`.
+	pub fn with_field(mut self, name: impl Into<Ident>, value: impl Into<Expr>) -> Self {
+		self.fields.push((name.into(), value.into()));
+		self
+	}
+}
+
+/// A data type with methods, built with [`ClassBuilder`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Class {
+	pub name: Ident,
+	pub visibility: Visibility,
+	pub fields: Vec<Field>,
+	pub constructor: Option<Constructor>,
+	pub methods: Vec<Method>,
+	/// The names of the [`crate::Interface`]s that the methods implement.
+	pub interfaces: Vec<Ident>,
+}
+
+impl Class {
+	/// The field called `name`.
+	pub fn field(&self, name: &str) -> Option<&Field> {
+		self.fields.iter().find(|itm| itm.name.as_str() == name)
+	}
+
+	/// The method called `name`.
+	pub fn method(&self, name: &str) -> Option<&Method> {
+		self.methods
+			.iter()
+			.find(|itm| itm.function.name.as_str() == name)
+	}
+
+	/// Checks that every method of the interfaces, which have to be rendered
+	/// first, has a public method with the same receiver and parameters.
+	fn check_interfaces(&self, cx: &Context) -> Result<(), GenError> {
+		for name in &self.interfaces {
+			let interface =
+				cx.interface(name.as_str())
+					.ok_or_else(|| GenError::UndeclaredName {
+						name: name.as_str().to_owned(),
+						path: cx.path(),
+					})?;
+			for signature in &interface.methods {
+				let implemented = self.method(signature.name.as_str()).is_some_and(|method| {
+					method.function.visibility == Visibility::Public
+						&& method.receiver == signature.receiver
+						&& method.function.params.len() == signature.params.len()
+				});
+				if !implemented {
+					return Err(GenError::Unimplemented {
+						method: format!("{}::{}", name.as_str(), signature.name.as_str()),
+						path: cx.path(),
+					});
+				}
+			}
+		}
+		Ok(())
+	}
+
+	/// Renders the fields, the constructor and the methods in that order, each
+	/// with its visibility.
+	pub fn members(&self, cx: &mut Context) -> Result<Vec<(Visibility, String)>, GenError> {
+		let mut members = Vec::new();
+		for field in &self.fields {
+			members.push((field.visibility, field.render(cx)?));
+		}
+		if let Some(ref constructor) = self.constructor {
+			let code = cx.within("constructor", |cx| {
+				cx.nested(|cx| {
+					cx.receiving(Receiver::Static, |cx| {
+						cx.backend().constructor(self, constructor, cx)
+					})
+				})
+			})?;
+			members.push((constructor.visibility, code));
+		}
+		for method in &self.methods {
+			members.push((method.function.visibility, method.render(cx)?));
+		}
+		Ok(members)
+	}
+}
+
+#[derive(Debug, Default)]
+pub struct ClassBuilder {
+	pub name: Option<String>,
+	pub visibility: Option<Visibility>,
+	pub fields: Vec<Field>,
+	pub constructor: Option<Constructor>,
+	pub methods: Vec<Method>,
+	pub interfaces: Vec<Ident>,
+}
+
+impl ClassBuilder {
+	#[inline]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	#[inline]
+	pub fn name<T: AsRef<str>>(mut self, name: T) -> Self {
+		self.name = Some(name.as_ref().to_owned());
+		self
+	}
+
+	#[inline]
+	pub fn with_visibility(mut self, visibility: Visibility) -> Self {
+		self.visibility = Some(visibility);
+		self
+	}
+
+	#[inline]
+	pub fn with_field(mut self, field: Field) -> Self {
+		self.fields.push(field);
+		self
+	}
+
+	#[inline]
+	pub fn with_constructor(mut self, constructor: Constructor) -> Self {
+		self.constructor = Some(constructor);
+		self
+	}
+
+	#[inline]
+	pub fn with_method(mut self, method: Method) -> Self {
+		self.methods.push(method);
+		self
+	}
+
+	/// Implements the interface `name` with the methods of the class.
+	#[inline]
+	pub fn with_interface(mut self, name: impl Into<Ident>) -> Self {
+		self.interfaces.push(name.into());
+		self
+	}
+
+	pub fn build(self) -> Option<Class> {
+		Some(Class {
+			name: Ident::new(self.name?),
+			visibility: self.visibility.unwrap_or(Visibility::Private),
+			fields: self.fields,
+			constructor: self.constructor,
+			methods: self.methods,
+			interfaces: self.interfaces,
+		})
+	}
+}
+
+impl ToCode for Class {
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.declare_class(self.clone());
+		cx.within(format!("class `{}`", self.name.as_str()), |cx| {
+			self.check_interfaces(cx)?;
+			cx.inside_class(self.name.as_str(), |cx| cx.backend().class(self, cx))
+		})
+	}
+}
+
+impl From<Class> for Stmt {
+	#[inline]
+	fn from(class: Class) -> Self {
+		Self::Item(Item::Class(class))
+	}
+}
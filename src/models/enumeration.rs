@@ -0,0 +1,112 @@
+use crate::{
+	backends::Context,
+	ir::{Item, Stmt, Type},
+	Field, GenError, Ident, ToCode, Visibility,
+};
+
+/// One of the cases of an [`Enum`], with the fields it carries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant {
+	pub name: Ident,
+	/// The payload of the variant. Their visibility is ignored, as the
+	/// variant decides who can see them.
+	pub fields: Vec<Field>,
+}
+
+impl Variant {
+	#[inline]
+	pub fn new(name: impl Into<Ident>) -> Self {
+		Self {
+			name: name.into(),
+			fields: Vec::new(),
+		}
+	}
+
+	pub fn with_field(mut self, name: impl Into<Ident>, typ: impl Into<Type>) -> Self {
+		self.fields
+			.push(Field::new(name, typ).with_visibility(Visibility::Private));
+		self
+	}
+
+	/// The field called `name`.
+	pub fn field(&self, name: &str) -> Option<&Field> {
+		self.fields.iter().find(|itm| itm.name.as_str() == name)
+	}
+}
+
+/// A type whose values are one of its variants, built with [`EnumBuilder`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Enum {
+	pub name: Ident,
+	pub visibility: Visibility,
+	pub variants: Vec<Variant>,
+}
+
+impl Enum {
+	/// The variant called `name`.
+	pub fn variant(&self, name: &str) -> Option<&Variant> {
+		self.variants.iter().find(|itm| itm.name.as_str() == name)
+	}
+
+	/// Whether none of the variants carry fields, which most languages have a
+	/// simpler representation for.
+	pub fn is_c_like(&self) -> bool {
+		self.variants.iter().all(|itm| itm.fields.is_empty())
+	}
+}
+
+#[derive(Debug, Default)]
+pub struct EnumBuilder {
+	pub name: Option<String>,
+	pub visibility: Option<Visibility>,
+	pub variants: Vec<Variant>,
+}
+
+impl EnumBuilder {
+	#[inline]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	#[inline]
+	pub fn name<T: AsRef<str>>(mut self, name: T) -> Self {
+		self.name = Some(name.as_ref().to_owned());
+		self
+	}
+
+	#[inline]
+	pub fn with_visibility(mut self, visibility: Visibility) -> Self {
+		self.visibility = Some(visibility);
+		self
+	}
+
+	#[inline]
+	pub fn with_variant(mut self, variant: Variant) -> Self {
+		self.variants.push(variant);
+		self
+	}
+
+	pub fn build(self) -> Option<Enum> {
+		Some(Enum {
+			name: Ident::new(self.name?),
+			visibility: self.visibility.unwrap_or(Visibility::Private),
+			variants: self.variants,
+		})
+	}
+}
+
+impl ToCode for Enum {
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.declare_enum(self.clone());
+		cx.within(format!("enum `{}`", self.name.as_str()), |cx| {
+			cx.backend().enumeration(self, cx)
+		})
+	}
+}
+
+impl From<Enum> for Stmt {
+	#[inline]
+	fn from(enumeration: Enum) -> Self {
+		Self::Item(Item::Enum(enumeration))
+	}
+}
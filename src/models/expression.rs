@@ -1,56 +1,143 @@
-use itertools::Itertools;
-
-use crate::{Language, ToCode};
-
-#[derive(Debug)]
-pub struct FunctionCall<T: ToCode>(pub T, pub Vec<Box<dyn ToCode>>);
-
-impl<T: ToCode> ToCode for FunctionCall<T> {
-	fn to_code(&self, language: Language) -> String {
-		let Self(fn_name, args) = self;
-		match language {
-			Language::TypeScript | Language::Rust | Language::CPP | Language::Python { .. } => {
-				format!(
-					"{}({})",
-					fn_name.to_code(language),
-					args.iter().map(|itm| itm.to_code(language)).join(",")
-				)
-			}
-		}
-	}
-}
-
-#[derive(Debug)]
-pub struct Ternary<T: ToCode, F: ToCode, E: ToCode>(pub T, pub F, pub E);
-
-impl<T: ToCode, F: ToCode, E: ToCode> ToCode for Ternary<T, F, E> {
-	fn to_code(&self, language: Language) -> String {
-		let Self(condition, opt_if, opt_else) = self;
-		match language {
-			Language::Rust => {
-				format!(
-					"if {}{{{}}}else{{{}}}",
-					condition.to_code(language),
-					opt_if.to_code(language),
-					opt_else.to_code(language)
-				)
-			}
-			Language::TypeScript | Language::CPP => {
-				format!(
-					"{}?{}:{}",
-					condition.to_code(language),
-					opt_if.to_code(language),
-					opt_else.to_code(language)
-				)
-			}
-			Language::Python { .. } => {
-				format!(
-					"if {} {} else {}",
-					condition.to_code(language),
-					opt_if.to_code(language),
-					opt_else.to_code(language)
-				)
-			}
-		}
+use crate::{
+	ir::{Expr, Stmt},
+	Ident,
+};
+
+#[derive(Debug, Clone)]
+pub struct FunctionCall<T>(pub T, pub Vec<Expr>);
+
+impl<T: Into<Expr>> From<FunctionCall<T>> for Expr {
+	fn from(call: FunctionCall<T>) -> Self {
+		let FunctionCall(fn_name, args) = call;
+		Expr::Call(Box::new(fn_name.into()), args)
+	}
+}
+
+impl<T: Into<Expr>> From<FunctionCall<T>> for Stmt {
+	#[inline]
+	fn from(call: FunctionCall<T>) -> Self {
+		Stmt::Expr(call.into())
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct Ternary<T, F, E>(pub T, pub F, pub E);
+
+impl<T: Into<Expr>, F: Into<Expr>, E: Into<Expr>> From<Ternary<T, F, E>> for Expr {
+	fn from(ternary: Ternary<T, F, E>) -> Self {
+		let Ternary(condition, opt_if, opt_else) = ternary;
+		Expr::Ternary(
+			Box::new(condition.into()),
+			Box::new(opt_if.into()),
+			Box::new(opt_else.into()),
+		)
+	}
+}
+
+impl<T: Into<Expr>, F: Into<Expr>, E: Into<Expr>> From<Ternary<T, F, E>> for Stmt {
+	#[inline]
+	fn from(ternary: Ternary<T, F, E>) -> Self {
+		Stmt::Expr(ternary.into())
+	}
+}
+
+/// An instance of the struct `T`, with the value of each field.
+#[derive(Debug, Clone)]
+pub struct StructLiteral<T>(pub T, pub Vec<(Ident, Expr)>);
+
+impl<T: Into<Ident>> From<StructLiteral<T>> for Expr {
+	fn from(literal: StructLiteral<T>) -> Self {
+		let StructLiteral(name, fields) = literal;
+		Expr::StructLiteral(name.into(), fields)
+	}
+}
+
+impl<T: Into<Ident>> From<StructLiteral<T>> for Stmt {
+	fn from(literal: StructLiteral<T>) -> Self {
+		Stmt::Expr(literal.into())
+	}
+}
+
+/// The field `F` of the struct `T`.
+#[derive(Debug, Clone)]
+pub struct FieldAccess<T, F>(pub T, pub F);
+
+impl<T: Into<Expr>, F: Into<Ident>> From<FieldAccess<T, F>> for Expr {
+	fn from(access: FieldAccess<T, F>) -> Self {
+		let FieldAccess(object, field) = access;
+		Expr::Field(Box::new(object.into()), field.into())
+	}
+}
+
+impl<T: Into<Expr>, F: Into<Ident>> From<FieldAccess<T, F>> for Stmt {
+	fn from(access: FieldAccess<T, F>) -> Self {
+		Stmt::Expr(access.into())
+	}
+}
+
+/// The instance that a [`crate::Method`] was called on, e.g. `self` or `this`.
+#[derive(Debug, Clone, Copy)]
+pub struct This;
+
+impl From<This> for Expr {
+	#[inline]
+	fn from(_: This) -> Self {
+		Expr::This
+	}
+}
+
+/// A call of the method `F` on the instance `T`.
+#[derive(Debug, Clone)]
+pub struct MethodCall<T, F>(pub T, pub F, pub Vec<Expr>);
+
+impl<T: Into<Expr>, F: Into<Ident>> From<MethodCall<T, F>> for Expr {
+	fn from(call: MethodCall<T, F>) -> Self {
+		let MethodCall(object, name, args) = call;
+		Expr::MethodCall(Box::new(object.into()), name.into(), args)
+	}
+}
+
+impl<T: Into<Expr>, F: Into<Ident>> From<MethodCall<T, F>> for Stmt {
+	fn from(call: MethodCall<T, F>) -> Self {
+		Stmt::Expr(call.into())
+	}
+}
+
+/// A call of the static method `F` of the class `T`.
+#[derive(Debug, Clone)]
+pub struct StaticCall<T, F>(pub T, pub F, pub Vec<Expr>);
+
+impl<T: Into<Ident>, F: Into<Ident>> From<StaticCall<T, F>> for Expr {
+	fn from(call: StaticCall<T, F>) -> Self {
+		let StaticCall(class, name, args) = call;
+		Expr::StaticCall(class.into(), name.into(), args)
+	}
+}
+
+impl<T: Into<Ident>, F: Into<Ident>> From<StaticCall<T, F>> for Stmt {
+	fn from(call: StaticCall<T, F>) -> Self {
+		Stmt::Expr(call.into())
+	}
+}
+
+/// A new instance of the class `T`, created by its [`crate::Constructor`].
+#[derive(Debug, Clone)]
+pub struct New<T>(pub T, pub Vec<Expr>);
+
+impl<T: Into<Ident>> From<New<T>> for Expr {
+	fn from(new: New<T>) -> Self {
+		let New(class, args) = new;
+		Expr::New(class.into(), args)
+	}
+}
+
+/// The variant `F` of the enum `T`, with the value of each of its fields.
+#[derive(Debug, Clone)]
+pub struct VariantLiteral<T, F>(pub T, pub F, pub Vec<(Ident, Expr)>);
+
+impl<T: Into<Ident>, F: Into<Ident>> From<VariantLiteral<T, F>> for Expr {
+	fn from(literal: VariantLiteral<T, F>) -> Self {
+		let VariantLiteral(enumeration, variant, fields) = literal;
+		Expr::Variant(enumeration.into(), variant.into(), fields)
 	}
 }
@@ -1,8 +1,10 @@
-use itertools::Itertools;
+use crate::{
+	backends::Context,
+	ir::{Item, Stmt},
+	GenError, ToCode,
+};
 
-use crate::{Language, ToCode};
-
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Import {
 	/// A file path.
 	Module(String),
@@ -10,30 +12,14 @@ pub enum Import {
 }
 
 impl ToCode for Import {
-	fn to_code(&self, language: Language) -> String {
-		match self {
-			Self::Module(location) => match language {
-				Language::TypeScript => format!("import \"{}\";", location),
-				Language::Rust => format!("mod {};", location),
-				Language::CPP => format!("#include \"{}\"\n", location),
-				Language::Python { .. } => format!("import {}\n", location),
-			},
-			Self::Members(location, members) => match language {
-				Language::TypeScript => {
-					format!(
-						"import {{{}}} from \"{}\";",
-						members.iter().join(","),
-						location
-					)
-				}
-				Language::Rust => {
-					format!("use {}::{{{}}};", location, members.iter().join(","))
-				}
-				Language::Python { .. } => {
-					format!("from {} import {}\n", location, members.iter().join(","))
-				}
-				Language::CPP => unimplemented!(),
-			},
-		}
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.within("import", |cx| cx.backend().import(self, cx))
+	}
+}
+
+impl From<Import> for Stmt {
+	#[inline]
+	fn from(import: Import) -> Self {
+		Self::Item(Item::Import(import))
 	}
 }
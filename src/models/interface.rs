@@ -0,0 +1,116 @@
+use crate::{
+	backends::Context,
+	ir::{Item, Stmt, Type},
+	GenError, Ident, Parameter, Receiver, ToCode, Visibility,
+};
+
+/// A method without a body that an [`Interface`] requires.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+	pub name: Ident,
+	pub receiver: Receiver,
+	pub params: Vec<Parameter>,
+	pub return_type: Option<Type>,
+}
+
+impl Signature {
+	#[inline]
+	pub fn new(name: impl Into<Ident>, receiver: Receiver) -> Self {
+		Self {
+			name: name.into(),
+			receiver,
+			params: Vec::new(),
+			return_type: None,
+		}
+	}
+
+	pub fn with_param(mut self, param: Parameter) -> Self {
+		self.params.push(param);
+		self
+	}
+
+	pub fn with_return_type(mut self, typ: impl Into<Type>) -> Self {
+		self.return_type = Some(typ.into());
+		self
+	}
+}
+
+impl ToCode for Signature {
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.within(format!("method `{}`", self.name.as_str()), |cx| {
+			cx.nested(|cx| cx.receiving(self.receiver, |cx| cx.backend().signature(self, cx)))
+		})
+	}
+}
+
+/// The methods that a [`crate::Class`] promises to have by implementing it,
+/// built with [`InterfaceBuilder`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interface {
+	pub name: Ident,
+	pub visibility: Visibility,
+	pub methods: Vec<Signature>,
+}
+
+impl Interface {
+	/// The method called `name`.
+	pub fn method(&self, name: &str) -> Option<&Signature> {
+		self.methods.iter().find(|itm| itm.name.as_str() == name)
+	}
+}
+
+#[derive(Debug, Default)]
+pub struct InterfaceBuilder {
+	pub name: Option<String>,
+	pub visibility: Option<Visibility>,
+	pub methods: Vec<Signature>,
+}
+
+impl InterfaceBuilder {
+	#[inline]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	#[inline]
+	pub fn name<T: AsRef<str>>(mut self, name: T) -> Self {
+		self.name = Some(name.as_ref().to_owned());
+		self
+	}
+
+	#[inline]
+	pub fn with_visibility(mut self, visibility: Visibility) -> Self {
+		self.visibility = Some(visibility);
+		self
+	}
+
+	#[inline]
+	pub fn with_method(mut self, method: Signature) -> Self {
+		self.methods.push(method);
+		self
+	}
+
+	pub fn build(self) -> Option<Interface> {
+		Some(Interface {
+			name: Ident::new(self.name?),
+			visibility: self.visibility.unwrap_or(Visibility::Private),
+			methods: self.methods,
+		})
+	}
+}
+
+impl ToCode for Interface {
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.declare_interface(self.clone());
+		cx.within(format!("interface `{}`", self.name.as_str()), |cx| {
+			cx.backend().interface(self, cx)
+		})
+	}
+}
+
+impl From<Interface> for Stmt {
+	#[inline]
+	fn from(interface: Interface) -> Self {
+		Self::Item(Item::Interface(interface))
+	}
+}
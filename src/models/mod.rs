@@ -1,132 +1,486 @@
 pub mod block;
+mod class;
 pub mod condition;
+mod enumeration;
 pub mod expression;
 mod import;
+mod interface;
 pub mod operation;
 pub mod print;
 mod scope;
 pub mod statement;
+mod structure;
 pub mod types;
 pub(crate) mod utils;
 
+pub use class::{Class, ClassBuilder, Constructor, Method, Receiver};
+pub use enumeration::{Enum, EnumBuilder, Variant};
 pub use import::Import;
+pub use interface::{Interface, InterfaceBuilder, Signature};
 pub use scope::Scope;
 pub use statement::Comment;
+pub use structure::{Field, Struct, StructBuilder};
 pub use utils::RunScope;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Value {
 	False,
 	True,
 }
 
 impl ToCode for Value {
-	fn to_code(&self, language: Language) -> String {
-		String::from(match self {
-			Self::True => match language {
-				Language::Rust | Language::TypeScript | Language::CPP => "true",
-				Language::Python { .. } => "True",
-			},
-			Self::False => match language {
-				Language::Rust | Language::TypeScript | Language::CPP => "false",
-				Language::Python { .. } => "False",
-			},
-		})
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.backend().value(self, cx)
+	}
+}
+
+impl From<Value> for Stmt {
+	#[inline]
+	fn from(value: Value) -> Self {
+		Self::Expr(Expr::Value(value))
 	}
 }
 
-use std::fmt::Debug;
+/// A literal that is escaped for the language it is rendered in.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+	Str(String),
+	/// A character, or a string of one character in languages without them.
+	Char(char),
+	/// An integer, optionally typed as `suffix`, e.g. `1i32` or `1f32` in Rust.
+	/// Languages without suffixes write a float for fractional types.
+	Int {
+		value: i64,
+		suffix: Option<Primitive>,
+	},
+	Float(f64),
+	Null,
+}
 
-use itertools::{free::join, Itertools};
+impl ToCode for Literal {
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		match self {
+			Self::Str(text) => cx.backend().string(text, cx),
+			Self::Char(c) => cx.backend().character(*c, cx),
+			Self::Int {
+				value,
+				suffix: None,
+			} => cx.backend().integer(*value, cx),
+			Self::Int {
+				value,
+				suffix: Some(suffix),
+			} => cx.backend().suffixed_integer(*value, *suffix, cx),
+			Self::Float(value) => cx.backend().float(*value, cx),
+			Self::Null => cx.backend().null(cx),
+		}
+	}
+}
 
-#[derive(Debug, Clone, Copy)]
+impl From<Literal> for Stmt {
+	#[inline]
+	fn from(literal: Literal) -> Self {
+		Self::Expr(Expr::Literal(literal))
+	}
+}
+
+use crate::{
+	backends::{layout, Context},
+	ir::{Expr, Item, Stmt, Type},
+	types::Primitive,
+	Backend, GenError,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum IndentType {
 	Tab,
 	/// Include the amount of spaces for each indentation here.
 	Space(u16),
 }
 
-impl Into<String> for IndentType {
-	fn into(self) -> String {
-		match self {
-			Self::Tab => String::from("\t"),
-			Self::Space(times) => " ".repeat(times as usize),
+impl From<IndentType> for String {
+	fn from(indent_type: IndentType) -> Self {
+		match indent_type {
+			IndentType::Tab => String::from("\t"),
+			IndentType::Space(times) => " ".repeat(times as usize),
+		}
+	}
+}
+
+/// Where the opening brace of a block goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BraceStyle {
+	/// At the end of the line that starts the block.
+	KAndR,
+	/// On a line of its own.
+	Allman,
+}
+
+/// Whether the names of functions, parameters and variables are rewritten.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NamingPolicy {
+	/// Names are only changed when they are reserved in the target.
+	Preserve,
+	/// Declared functions, methods, parameters and variables follow the
+	/// convention of the target, see [`Backend::naming_convention`].
+	Conventional,
+}
+
+/// What a name refers to, as conventions often differ between them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameKind {
+	Function,
+	/// Variables and parameters.
+	Variable,
+	/// Fields of a [`Struct`].
+	Field,
+}
+
+/// A naming convention. Names are split into words at underscores, hyphens
+/// and changes of case, so `parseHTTPResponse`, `ParseHttpResponse` and
+/// `parse_http_response` are the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Case {
+	/// `parse_http_response`
+	Snake,
+	/// `parseHttpResponse`
+	Camel,
+	/// `ParseHttpResponse`
+	Pascal,
+}
+
+impl Case {
+	/// Writes `name` in this case, keeping its leading underscores.
+	pub fn apply(self, name: &str) -> String {
+		let trimmed = name.trim_start_matches('_');
+		let mut code = "_".repeat(name.len() - trimmed.len());
+		for (idx, word) in words(trimmed).iter().enumerate() {
+			let lower = word.to_lowercase();
+			match self {
+				Self::Snake => {
+					if idx > 0 {
+						code.push('_');
+					}
+					code.push_str(&lower);
+				}
+				Self::Camel if idx == 0 => code.push_str(&lower),
+				Self::Camel | Self::Pascal => {
+					let mut chars = lower.chars();
+					if let Some(first) = chars.next() {
+						code.extend(first.to_uppercase());
+						code.push_str(chars.as_str());
+					}
+				}
+			}
+		}
+		code
+	}
+}
+
+/// The words of `name`, see [`Case`].
+fn words(name: &str) -> Vec<String> {
+	let chars: Vec<char> = name.chars().collect();
+	let mut words = Vec::new();
+	let mut word = String::new();
+	for (idx, &c) in chars.iter().enumerate() {
+		if c == '_' || c == '-' {
+			if !word.is_empty() {
+				words.push(std::mem::take(&mut word));
+			}
+			continue;
+		}
+		if c.is_uppercase() && !word.is_empty() {
+			let previous = chars[idx - 1];
+			let next_is_lower = chars.get(idx + 1).is_some_and(|next| next.is_lowercase());
+			if !previous.is_uppercase() || next_is_lower {
+				words.push(std::mem::take(&mut word));
+			}
+		}
+		word.push(c);
+	}
+	if !word.is_empty() {
+		words.push(word);
+	}
+	words
+}
+
+/// How [`ToCode::to_code_with`] lays out code. The default minifies it.
+///
+/// Only the brace languages Rust, TypeScript, JavaScript and C++ are
+/// formatted so far. Python is always on multiple lines and only follows
+/// [`RenderOptions::indent_type`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderOptions {
+	pub indent_type: IndentType,
+	/// Puts every statement on a line of its own and indents blocks.
+	pub newlines: bool,
+	/// Surrounds binary operators with spaces and follows commas with one.
+	pub spaces: bool,
+	/// Only used with [`RenderOptions::newlines`].
+	pub brace_style: BraceStyle,
+	/// Argument and parameter lists longer than this are put on a line each.
+	/// Only used with [`RenderOptions::newlines`].
+	pub max_width: Option<usize>,
+	/// Applies to every language.
+	pub naming: NamingPolicy,
+}
+
+impl Default for RenderOptions {
+	fn default() -> Self {
+		Self {
+			indent_type: IndentType::Tab,
+			newlines: false,
+			spaces: false,
+			brace_style: BraceStyle::KAndR,
+			max_width: None,
+			naming: NamingPolicy::Preserve,
+		}
+	}
+}
+
+impl RenderOptions {
+	/// Readable output, indented with tabs.
+	pub fn pretty() -> Self {
+		Self {
+			newlines: true,
+			spaces: true,
+			max_width: Some(100),
+			..Self::default()
 		}
 	}
 }
 
+/// How [`Language::JavaScript`] exports and imports names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleSystem {
+	/// `export` and `import`.
+	ESM,
+	/// `module.exports` and `require`.
+	CommonJS,
+}
+
 #[derive(Debug, Clone, Copy)]
 pub enum Language {
 	Rust,
 	TypeScript,
+	JavaScript {
+		module_system: ModuleSystem,
+	},
+	/// C99, as opposed to [`Language::CPP`].
+	C,
 	CPP,
+	CSharp {
+		/// Emits the entry point as top-level statements instead of a
+		/// `static void Main` method.
+		top_level_statements: bool,
+	},
+	Go,
+	/// Functions are generated as static methods of a class named
+	/// `class_name`.
+	Java {
+		class_name: &'static str,
+	},
+	Kotlin,
+	/// Lua 5.3 or later.
+	Lua,
+	Swift,
+	Ruby,
+	PHP,
+	Zig,
+	/// Only programs without mutation.
+	Haskell,
+	/// The WebAssembly text format.
+	WAT,
+	/// Indented with [`RenderOptions::indent_type`].
 	Python {
 		include_types: bool,
-		/// **ALWAYS** set this to [`None`]
-		indent_level: Option<u32>,
-		indent_type: IndentType,
+		/// The oldest release the code has to run on, e.g. `(3, 10)`, which is
+		/// the first with `match` statements.
+		version: (u8, u8),
 	},
+	/// A target implemented outside of this crate.
+	Custom(&'static dyn Backend),
+}
+
+impl Language {
+	/// The [`Backend`] that renders this language.
+	pub fn backend(self) -> &'static dyn Backend {
+		match self {
+			Self::Rust => &crate::backends::Rust,
+			Self::TypeScript => &crate::backends::TypeScript,
+			Self::JavaScript { .. } => &crate::backends::JavaScript,
+			Self::C => &crate::backends::C,
+			Self::CPP => &crate::backends::CPP,
+			Self::CSharp { .. } => &crate::backends::CSharp,
+			Self::Go => &crate::backends::Go,
+			Self::Java { .. } => &crate::backends::Java,
+			Self::Kotlin => &crate::backends::Kotlin,
+			Self::Lua => &crate::backends::Lua,
+			Self::Swift => &crate::backends::Swift,
+			Self::Ruby => &crate::backends::Ruby,
+			Self::PHP => &crate::backends::PHP,
+			Self::Zig => &crate::backends::Zig,
+			Self::Haskell => &crate::backends::Haskell,
+			Self::WAT => &crate::backends::WAT,
+			Self::Python { .. } => &crate::backends::Python,
+			Self::Custom(backend) => backend,
+		}
+	}
 }
 
 pub trait ToCode: std::fmt::Debug {
-	fn to_code(&self, language: Language) -> String;
+	/// Renders the node with the backend of `cx`.
+	fn render(&self, cx: &mut Context) -> Result<String, GenError>;
+
+	/// Renders the node, reporting the constructs that `language` cannot
+	/// express.
+	fn try_to_code(&self, language: Language) -> Result<String, GenError> {
+		self.try_to_code_with(language, RenderOptions::default())
+	}
+
+	/// [`ToCode::try_to_code`], laid out according to `options`.
+	fn try_to_code_with(
+		&self,
+		language: Language,
+		options: RenderOptions,
+	) -> Result<String, GenError> {
+		let mut cx = Context::with_options(language, options);
+		let code = self.render(&mut cx)?;
+		let code = cx.backend().finish(code, &mut cx)?;
+		Ok(layout::layout(&code, options.indent_type))
+	}
+
+	/// ## Panics
+	/// * if the node cannot be rendered in `language`, see
+	///   [`ToCode::try_to_code`]
+	fn to_code(&self, language: Language) -> String {
+		self.try_to_code(language)
+			.unwrap_or_else(|err| panic!("{}", err))
+	}
+
+	/// ## Panics
+	/// * if the node cannot be rendered in `language`, see
+	///   [`ToCode::try_to_code_with`]
+	fn to_code_with(&self, language: Language, options: RenderOptions) -> String {
+		self.try_to_code_with(language, options)
+			.unwrap_or_else(|err| panic!("{}", err))
+	}
+}
+
+impl<T: ToCode + ?Sized> ToCode for Box<T> {
+	#[inline]
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		(**self).render(cx)
+	}
+}
+
+impl<T: ToCode + ?Sized> ToCode for &T {
+	#[inline]
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		(**self).render(cx)
+	}
 }
 
 impl ToCode for isize {
 	#[inline]
-	fn to_code(&self, _: Language) -> String {
-		self.to_string()
+	fn render(&self, _: &mut Context) -> Result<String, GenError> {
+		Ok(self.to_string())
 	}
 }
 
 impl ToCode for i32 {
 	#[inline]
-	fn to_code(&self, _: Language) -> String {
-		self.to_string()
+	fn render(&self, _: &mut Context) -> Result<String, GenError> {
+		Ok(self.to_string())
 	}
 }
 
 impl ToCode for usize {
 	#[inline]
-	fn to_code(&self, _: Language) -> String {
-		self.to_string()
+	fn render(&self, _: &mut Context) -> Result<String, GenError> {
+		Ok(self.to_string())
 	}
 }
 
 impl ToCode for u32 {
 	#[inline]
-	fn to_code(&self, _: Language) -> String {
-		self.to_string()
+	fn render(&self, _: &mut Context) -> Result<String, GenError> {
+		Ok(self.to_string())
 	}
 }
 
 impl ToCode for String {
 	#[inline]
-	fn to_code(&self, _: Language) -> String {
-		self.clone()
+	fn render(&self, _: &mut Context) -> Result<String, GenError> {
+		Ok(self.clone())
 	}
 }
 
 impl ToCode for &str {
 	#[inline]
-	fn to_code(&self, _: Language) -> String {
-		self.to_string()
+	fn render(&self, _: &mut Context) -> Result<String, GenError> {
+		Ok(self.to_string())
 	}
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Visibility {
 	Public,
 	Private,
 }
 
-#[derive(Debug)]
-pub struct Parameter(pub Box<dyn ToCode>, pub Option<Box<dyn ToCode>>);
+/// The name of a function, parameter or variable, which is escaped wherever it
+/// is rendered if it is reserved in the target, see [`Backend::keywords`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Ident(String);
 
-#[derive(Debug)]
+impl Ident {
+	#[inline]
+	pub fn new(name: impl Into<String>) -> Self {
+		Self(name.into())
+	}
+
+	/// The name as it was given, before it is escaped.
+	#[inline]
+	pub fn as_str(&self) -> &str {
+		&self.0
+	}
+}
+
+impl ToCode for Ident {
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.backend().identifier(&self.0, cx)
+	}
+}
+
+impl From<&str> for Ident {
+	#[inline]
+	fn from(name: &str) -> Self {
+		Self(name.to_owned())
+	}
+}
+
+impl From<String> for Ident {
+	#[inline]
+	fn from(name: String) -> Self {
+		Self(name)
+	}
+}
+
+impl AsRef<str> for Ident {
+	#[inline]
+	fn as_ref(&self) -> &str {
+		&self.0
+	}
+}
+
+/// A parameter name and its optional type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter(pub Ident, pub Option<Type>);
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Function {
-	pub name: String,
-	pub return_type: Option<Box<dyn ToCode>>,
+	pub name: Ident,
+	pub return_type: Option<Type>,
 	pub visibility: Visibility,
 	pub params: Vec<Parameter>,
 	pub scope: Scope,
@@ -135,12 +489,19 @@ pub struct Function {
 #[derive(Debug)]
 pub struct FunctionBuilder {
 	pub name: Option<String>,
-	pub return_type: Option<Box<dyn ToCode>>,
+	pub return_type: Option<Type>,
 	pub visibility: Option<Visibility>,
 	pub params: Vec<Parameter>,
 	pub scope: Option<Scope>,
 }
 
+impl Default for FunctionBuilder {
+	#[inline]
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
 impl FunctionBuilder {
 	#[inline]
 	pub fn new() -> Self {
@@ -160,8 +521,8 @@ impl FunctionBuilder {
 	}
 
 	#[inline]
-	pub fn with_return_type(mut self, return_type: impl ToCode + 'static) -> Self {
-		self.return_type = Some(Box::new(return_type));
+	pub fn with_return_type(mut self, return_type: impl Into<Type>) -> Self {
+		self.return_type = Some(return_type.into());
 		self
 	}
 
@@ -185,7 +546,7 @@ impl FunctionBuilder {
 
 	pub fn build(self) -> Option<Function> {
 		Some(Function {
-			name: self.name?,
+			name: Ident::new(self.name?),
 			visibility: self.visibility.unwrap_or(Visibility::Private),
 			return_type: self.return_type,
 			params: self.params,
@@ -195,149 +556,47 @@ impl FunctionBuilder {
 }
 
 impl ToCode for Parameter {
-	fn to_code(&self, language: Language) -> String {
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
 		let Self(name, typ) = self;
-		match language {
-			Language::Rust => {
-				format!(
-					"{}:{}",
-					name.to_code(language),
-					typ.as_ref().unwrap().to_code(language)
-				)
-			}
-			Language::TypeScript => match typ {
-				Some(typ) => {
-					format!("{}:{}", name.to_code(language), typ.to_code(language))
-				}
-				_ => format!("{}:any", name.to_code(language)),
-			},
-			Language::CPP => match typ {
-				Some(typ) => {
-					format!("{} {}", typ.to_code(language), name.to_code(language))
-				}
-				_ => panic!("A type is required for parameters in C++."),
-			},
-			Language::Python { include_types, .. } => format!(
-				"{}{}",
-				name.to_code(language),
-				if include_types && typ.is_some() {
-					format!(":{}", typ.as_ref().unwrap().to_code(language))
-				} else {
-					String::new()
-				}
-			),
+		cx.declare_variable(name.as_str());
+		if let Some(typ) = typ {
+			cx.declare(name.as_str(), typ.clone());
 		}
+		cx.within("parameter", |cx| cx.backend().parameter(self, cx))
 	}
 }
 
 impl ToCode for Function {
-	fn to_code(&self, language: Language) -> String {
-		match language {
-			Language::Rust => {
-				format!(
-					"{}{}fn {}({}){}{{{}}}",
-					self.visibility.to_code(language),
-					match self.visibility {
-						Visibility::Public => " ",
-						_ => "",
-					},
-					self.name.to_code(language),
-					self.params
-						.iter()
-						.map(|param| param.to_code(language))
-						.join(","),
-					match self.return_type {
-						Some(ref r_type) => format!("->{}", r_type.to_code(language)),
-						None => String::new(),
-					},
-					self.scope.to_code(language)
-				)
-			}
-			Language::TypeScript => {
-				format!(
-					"{}{}function {}({}){}{{{}}}",
-					self.visibility.to_code(language),
-					match self.visibility {
-						Visibility::Public => String::from(" "),
-						Visibility::Private => String::new(),
-					},
-					self.name.to_code(language),
-					self.params
-						.iter()
-						.map(|param| param.to_code(language))
-						.join(","),
-					match self.return_type {
-						Some(ref typ) => format!(":{}", typ.to_code(language)),
-						None => String::from(":void"),
-					},
-					self.scope.to_code(language)
-				)
-			}
-			Language::CPP => {
-				format!(
-					"{} {}({}){{{}}}",
-					match self.return_type {
-						Some(ref typ) => typ.to_code(language),
-						None => String::from("void"),
-					},
-					self.name.to_code(language),
-					self.params
-						.iter()
-						.map(|param| {
-							let Parameter(name, typ) = param;
-							let typ = typ.as_ref().expect("A parameter type is required");
-							format!("{} {}", typ.to_code(language), name.to_code(language))
-						})
-						.join(","),
-					self.scope.to_code(language)
-				)
-			}
-			Language::Python {
-				include_types,
-				indent_level,
-				indent_type,
-			} => {
-				format!(
-					"def {}({}){}:{}",
-					self.name.to_code(language),
-					self.params
-						.iter()
-						.map(|itm| itm.to_code(language))
-						.join(","),
-					if include_types && self.return_type.is_some() {
-						format!("->{}", self.return_type.as_ref().unwrap().to_code(language))
-					} else {
-						String::new()
-					},
-					self.scope.to_code(Language::Python {
-						include_types,
-						indent_level: Some(indent_level.unwrap_or(0) + 1),
-						indent_type
-					})
-				)
-			}
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.declare_function(self.name.as_str());
+		if let Some(ref typ) = self.return_type {
+			cx.declare(self.name.as_str(), typ.clone());
 		}
+		cx.within(format!("function `{}`", self.name.as_str()), |cx| {
+			cx.nested(|cx| {
+				cx.returning(self.return_type.clone(), |cx| {
+					cx.backend().function(self, cx)
+				})
+			})
+		})
+	}
+}
+
+impl From<Function> for Stmt {
+	#[inline]
+	fn from(function: Function) -> Self {
+		Self::Item(Item::Function(function))
 	}
 }
 
 impl ToCode for Visibility {
-	fn to_code(&self, language: Language) -> String {
-		match language {
-			Language::Rust => match self {
-				Visibility::Public => String::from("pub"),
-				Visibility::Private => String::new(),
-			},
-			Language::TypeScript => match self {
-				Visibility::Public => String::from("export"),
-				Visibility::Private => String::new(),
-			},
-			_ => unimplemented!(),
-		}
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.backend().visibility(*self, cx)
 	}
 }
 
 impl<T: ToCode> ToCode for Vec<T> {
-	fn to_code(&self, language: Language) -> String {
-		join(self.iter().map(|field| field.to_code(language)), "")
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.join(self, "")
 	}
 }
@@ -1,7 +1,7 @@
-use crate::{new_comparator, Language, ToCode};
+use crate::ir::{Expr, Operator, Stmt};
 
-new_comparator!(Add, "+");
-new_comparator!(Subtract, "-");
-new_comparator!(Multiply, "*");
-new_comparator!(Divide, "/");
-new_comparator!(Mod, "%");
+new_comparator!(Add, Operator::Add);
+new_comparator!(Subtract, Operator::Subtract);
+new_comparator!(Multiply, Operator::Multiply);
+new_comparator!(Divide, Operator::Divide);
+new_comparator!(Mod, Operator::Mod);
@@ -1,53 +1,30 @@
-use itertools::Itertools;
+use crate::{backends::Context, ir::Stmt, GenError, ToCode};
 
-use crate::{Language, ToCode};
-
-#[derive(Debug)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct Scope {
-	children: Vec<Box<dyn ToCode>>,
-	indent_level: Option<u32>,
+	children: Vec<Stmt>,
 }
 
-impl std::default::Default for Scope {
+impl Scope {
 	#[inline]
-	fn default() -> Self {
-		Self {
-			children: Vec::new(),
-			indent_level: None,
-		}
+	pub fn with(mut self, child: impl Into<Stmt>) -> Self {
+		self.children.push(child.into());
+		self
 	}
-}
 
-impl Scope {
 	#[inline]
-	pub fn with<T>(mut self, child: T) -> Self
-	where
-		T: ToCode + 'static,
-	{
-		self.children.push(Box::new(child));
-		self
+	pub fn children(&self) -> &[Stmt] {
+		&self.children
+	}
+
+	#[inline]
+	pub fn children_mut(&mut self) -> &mut Vec<Stmt> {
+		&mut self.children
 	}
 }
 
 impl ToCode for Scope {
-	fn to_code(&self, language: Language) -> String {
-		self.children
-			.iter()
-			.map(|itm| match language {
-				Language::Python {
-					indent_level,
-					indent_type,
-					..
-				} => {
-					let indent: String = indent_type.into();
-					format!(
-						"\n{}{}",
-						indent.repeat(indent_level.unwrap_or(0) as usize),
-						itm.to_code(language)
-					)
-				}
-				_ => itm.to_code(language),
-			})
-			.join("")
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.nested(|cx| cx.backend().scope(self, cx))
 	}
 }
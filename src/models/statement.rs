@@ -1,27 +1,33 @@
 pub use crate::{Language, ToCode};
 
-#[derive(Debug)]
+use crate::{
+	backends::Context,
+	ir::{Expr, Stmt, Type},
+	GenError, Ident,
+};
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct VariableInit {
-	pub name: Box<dyn ToCode>,
+	pub name: Ident,
 	pub mutable: Option<bool>,
-	pub typ: Option<Box<dyn ToCode>>,
-	pub value: Box<dyn ToCode>,
+	pub typ: Option<Type>,
+	pub value: Expr,
 }
 
 impl std::default::Default for VariableInit {
 	fn default() -> Self {
 		Self {
-			name: Box::new("foo"),
+			name: Ident::from("foo"),
 			mutable: None,
 			typ: None,
-			value: Box::new("bar"),
+			value: Expr::from("bar"),
 		}
 	}
 }
 
 impl VariableInit {
-	pub fn with_name(mut self, name: impl ToCode + 'static) -> Self {
-		self.name = Box::new(name);
+	pub fn with_name<T: AsRef<str>>(mut self, name: T) -> Self {
+		self.name = Ident::from(name.as_ref());
 		self
 	}
 
@@ -30,118 +36,78 @@ impl VariableInit {
 		self
 	}
 
-	pub fn with_type(mut self, typ: impl ToCode + 'static) -> Self {
-		self.typ = Some(Box::new(typ));
+	pub fn with_type(mut self, typ: impl Into<Type>) -> Self {
+		self.typ = Some(typ.into());
 		self
 	}
 
-	pub fn with_value(mut self, value: impl ToCode + 'static) -> Self {
-		self.value = Box::new(value);
+	pub fn with_value(mut self, value: impl Into<Expr>) -> Self {
+		self.value = value.into();
 		self
 	}
 }
 
 impl ToCode for VariableInit {
-	fn to_code(&self, language: Language) -> String {
-		let Self {
-			name,
-			mutable,
-			typ,
-			value,
-		} = self;
-
-		match language {
-			Language::Rust => format!(
-				"let {}{}{}={};",
-				match mutable {
-					Some(true) => {
-						String::from("mut ")
-					}
-					_ => String::new(),
-				},
-				name.to_code(language),
-				match typ {
-					Some(typ) => format!(":{}", typ.to_code(language)),
-					None => String::new(),
-				},
-				value.to_code(language)
-			),
-			Language::CPP => {
-				format!(
-					"{} {}={};",
-					typ.as_ref().unwrap().to_code(language),
-					name.to_code(language),
-					value.to_code(language)
-				)
-			}
-			Language::Python { include_types, .. } => {
-				format!(
-					"{}{}={}",
-					name.to_code(language),
-					if include_types && typ.is_some() {
-						format!(":{}", typ.as_ref().unwrap().to_code(language))
-					} else {
-						String::new()
-					},
-					value.to_code(language)
-				)
-			}
-			Language::TypeScript => {
-				format!(
-					"{} {}{}={};",
-					match mutable {
-						Some(true) => String::from("let"),
-						Some(false) | None => String::from("const"),
-					},
-					name.to_code(language),
-					match typ {
-						Some(typ) => format!(":{}", typ.to_code(language)),
-						None => String::new(),
-					},
-					value.to_code(language)
-				)
-			}
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.declare_variable(self.name.as_str());
+		if let Some(typ) = self.typ.clone().or_else(|| cx.type_of(&self.value)) {
+			cx.declare(self.name.as_str(), typ);
 		}
+		cx.within("variable", |cx| cx.backend().variable_init(self, cx))
 	}
 }
 
-#[derive(Debug)]
-pub struct AssignVariable<T: ToCode, F: ToCode>(T, F);
-
-impl<T: ToCode, F: ToCode> ToCode for AssignVariable<T, F> {
-	fn to_code(&self, language: Language) -> String {
-		let Self(name, value) = self;
-		match language {
-			Language::Rust | Language::TypeScript | Language::CPP => {
-				format!("{}={};", name.to_code(language), value.to_code(language))
-			}
-			Language::Python { .. } => {
-				format!("{}={}", name.to_code(language), value.to_code(language))
-			}
-		}
+impl From<VariableInit> for Stmt {
+	#[inline]
+	fn from(init: VariableInit) -> Self {
+		Self::VariableInit(init)
 	}
 }
 
-#[derive(Debug)]
-pub struct Return<T: ToCode>(pub Option<T>);
-
-impl<T: ToCode> ToCode for Return<T> {
-	fn to_code(&self, language: Language) -> String {
-		let Self(expr) = self;
-		match language {
-			Language::Rust | Language::TypeScript | Language::CPP => match expr {
-				Some(expr) => format!("return {};", expr.to_code(language)),
-				None => String::from("return;"),
-			},
-			Language::Python { .. } => match expr {
-				Some(expr) => format!("return {}", expr.to_code(language)),
-				None => String::from("return"),
-			},
-		}
+#[derive(Debug, Clone)]
+pub struct AssignVariable<T, F>(pub T, pub F);
+
+impl<T: Into<Expr>, F: Into<Expr>> From<AssignVariable<T, F>> for Stmt {
+	fn from(assign: AssignVariable<T, F>) -> Self {
+		let AssignVariable(name, value) = assign;
+		Self::Assign(name.into(), value.into())
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct Return<T>(pub Option<T>);
+
+impl<T: Into<Expr>> From<Return<T>> for Stmt {
+	fn from(ret: Return<T>) -> Self {
+		let Return(expr) = ret;
+		Self::Return(expr.map(Into::into))
+	}
+}
+
+/// Leaves the innermost loop, or the loop with the given label.
+#[derive(Debug, Clone, Default)]
+pub struct Break(pub Option<String>);
+
+impl From<Break> for Stmt {
+	#[inline]
+	fn from(stmt: Break) -> Self {
+		Self::Break(stmt.0)
+	}
+}
+
+/// Skips to the next iteration of the innermost loop, or of the loop with the
+/// given label.
+#[derive(Debug, Clone, Default)]
+pub struct Continue(pub Option<String>);
+
+impl From<Continue> for Stmt {
+	#[inline]
+	fn from(stmt: Continue) -> Self {
+		Self::Continue(stmt.0)
 	}
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Comment {
 	/// ## Notes
 	/// * inserts newline after comment if the language's comment spans rest of
@@ -152,22 +118,14 @@ pub enum Comment {
 }
 
 impl ToCode for Comment {
-	fn to_code(&self, language: Language) -> String {
-		match self {
-			Self::Regular(content) => match language {
-				Language::Rust | Language::TypeScript | Language::CPP => format!("//{}\n", content),
-				Language::Python { .. } => format!("#{}", content),
-			},
-			Self::MultiLine(content) => match language {
-				Language::Rust | Language::TypeScript | Language::CPP => format!("/*{}*/", content),
-				Language::Python { .. } => format!("\"\"\"{}\"\"\"", content),
-			},
-			Self::Doc(content) => match language {
-				Language::Rust => format!("///{}\n", content),
-				Language::TypeScript => format!("/**{}*/", content),
-				Language::CPP => Self::Regular(content.clone()).to_code(language),
-				Language::Python { .. } => Self::MultiLine(content.clone()).to_code(language),
-			},
-		}
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.backend().comment(self, cx)
+	}
+}
+
+impl From<Comment> for Stmt {
+	#[inline]
+	fn from(comment: Comment) -> Self {
+		Self::Comment(comment)
 	}
 }
@@ -0,0 +1,119 @@
+use crate::{
+	backends::Context,
+	ir::{Item, Stmt, Type},
+	GenError, Ident, ToCode, Visibility,
+};
+
+/// A named field of a [`Struct`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Field {
+	pub name: Ident,
+	pub typ: Type,
+	pub visibility: Visibility,
+}
+
+impl Field {
+	/// A public field, as fields of plain data types usually are.
+	pub fn new(name: impl Into<Ident>, typ: impl Into<Type>) -> Self {
+		Self {
+			name: name.into(),
+			typ: typ.into(),
+			visibility: Visibility::Public,
+		}
+	}
+
+	pub fn with_visibility(mut self, visibility: Visibility) -> Self {
+		self.visibility = visibility;
+		self
+	}
+}
+
+impl ToCode for Field {
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.within(format!("field `{}`", self.name.as_str()), |cx| {
+			cx.backend().field(self, cx)
+		})
+	}
+}
+
+/// A data type with named fields, built with [`StructBuilder`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Struct {
+	pub name: Ident,
+	pub visibility: Visibility,
+	pub fields: Vec<Field>,
+	/// Traits that Rust derives for the struct, e.g. `Debug`. Other languages
+	/// only use the ones they have an equivalent of.
+	pub derives: Vec<String>,
+}
+
+impl Struct {
+	/// The field called `name`.
+	pub fn field(&self, name: &str) -> Option<&Field> {
+		self.fields.iter().find(|itm| itm.name.as_str() == name)
+	}
+}
+
+#[derive(Debug, Default)]
+pub struct StructBuilder {
+	pub name: Option<String>,
+	pub visibility: Option<Visibility>,
+	pub fields: Vec<Field>,
+	pub derives: Vec<String>,
+}
+
+impl StructBuilder {
+	#[inline]
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	#[inline]
+	pub fn name<T: AsRef<str>>(mut self, name: T) -> Self {
+		self.name = Some(name.as_ref().to_owned());
+		self
+	}
+
+	#[inline]
+	pub fn with_visibility(mut self, visibility: Visibility) -> Self {
+		self.visibility = Some(visibility);
+		self
+	}
+
+	#[inline]
+	pub fn with_field(mut self, field: Field) -> Self {
+		self.fields.push(field);
+		self
+	}
+
+	#[inline]
+	pub fn with_derive<T: AsRef<str>>(mut self, derive: T) -> Self {
+		self.derives.push(derive.as_ref().to_owned());
+		self
+	}
+
+	pub fn build(self) -> Option<Struct> {
+		Some(Struct {
+			name: Ident::new(self.name?),
+			visibility: self.visibility.unwrap_or(Visibility::Private),
+			fields: self.fields,
+			derives: self.derives,
+		})
+	}
+}
+
+impl ToCode for Struct {
+	fn render(&self, cx: &mut Context) -> Result<String, GenError> {
+		cx.declare_struct(self.clone());
+		cx.within(format!("struct `{}`", self.name.as_str()), |cx| {
+			cx.backend().structure(self, cx)
+		})
+	}
+}
+
+impl From<Struct> for Stmt {
+	#[inline]
+	fn from(structure: Struct) -> Self {
+		Self::Item(Item::Struct(structure))
+	}
+}
@@ -1,91 +1,82 @@
-use crate::{Language, ToCode};
-use itertools::Itertools;
+use crate::ir::Type;
 
-#[derive(Debug)]
+/// The built-in types that every backend maps to a native type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Primitive {
+	Integer,
+	Number,
+	Float,
+	Boolean,
+}
+
+#[derive(Debug, Clone, Copy)]
 pub struct Number;
 
-impl ToCode for Number {
-	fn to_code(&self, language: Language) -> String {
-		String::from(match language {
-			Language::Rust => "f32",
-			Language::TypeScript => "number",
-			Language::CPP => "double",
-			Language::Python { .. } => "float",
-		})
+impl From<Number> for Type {
+	#[inline]
+	fn from(_: Number) -> Self {
+		Type::Primitive(Primitive::Number)
 	}
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct Integer;
 
-impl ToCode for Integer {
-	fn to_code(&self, language: Language) -> String {
-		String::from(match language {
-			Language::CPP => "int",
-			Language::Rust => "i32",
-			Language::TypeScript => "number",
-			Language::Python { .. } => "int",
-		})
+impl From<Integer> for Type {
+	#[inline]
+	fn from(_: Integer) -> Self {
+		Type::Primitive(Primitive::Integer)
 	}
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy)]
 pub struct Float;
 
-impl ToCode for Float {
-	fn to_code(&self, language: Language) -> String {
-		String::from(match language {
-			Language::CPP => "double",
-			Language::Rust => "f32",
-			Language::TypeScript => "number",
-			Language::Python { .. } => "float",
-		})
+impl From<Float> for Type {
+	#[inline]
+	fn from(_: Float) -> Self {
+		Type::Primitive(Primitive::Float)
 	}
 }
-#[derive(Debug)]
+
+#[derive(Debug, Clone, Copy)]
 pub struct Boolean;
 
-impl ToCode for Boolean {
-	fn to_code(&self, language: Language) -> String {
-		String::from(match language {
-			Language::Rust | Language::CPP | Language::Python { .. } => "bool",
-			Language::TypeScript => "boolean",
-		})
+impl From<Boolean> for Type {
+	#[inline]
+	fn from(_: Boolean) -> Self {
+		Type::Primitive(Primitive::Boolean)
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct Generic(pub String, pub Vec<Type>);
+
+impl From<Generic> for Type {
+	#[inline]
+	fn from(generic: Generic) -> Self {
+		let Generic(name, args) = generic;
+		Type::Generic(name, args)
 	}
 }
 
-#[derive(Debug)]
-pub struct Generic<'a>(pub String, pub Vec<&'a dyn ToCode>);
+#[derive(Debug, Clone)]
+pub struct Future<T>(pub T);
 
-impl<'a> ToCode for Generic<'a> {
-	fn to_code(&self, language: Language) -> String {
-		let Self(name, args) = self;
-		let args = args.iter().map(|itm| itm.to_code(language)).join(",");
-		match language {
-			Language::Rust | Language::TypeScript | Language::CPP => {
-				format!("{}<{}>", name, args)
-			}
-			Language::Python { .. } => {
-				format!("{}[{}]", name, args)
-			}
-		}
+impl<T: Into<Type>> From<Future<T>> for Type {
+	#[inline]
+	fn from(future: Future<T>) -> Self {
+		Type::Future(Box::new(future.0.into()))
 	}
 }
 
-#[derive(Debug)]
-pub struct Future<T: ToCode>(pub T);
+/// A type declared in the generated code, e.g. a [`crate::Struct`].
+#[derive(Debug, Clone)]
+pub struct Named<T>(pub T);
 
-impl<T: ToCode + 'static> ToCode for Future<T> {
-	fn to_code(&self, language: Language) -> String {
-		let Self(value) = self;
-		Generic(
-			String::from(match language {
-				Language::Rust => "Future",
-				Language::TypeScript => "Promise",
-				Language::CPP | Language::Python { .. } => unimplemented!(),
-			}),
-			vec![value as &dyn ToCode],
-		)
-		.to_code(language)
+impl<T: Into<String>> From<Named<T>> for Type {
+	#[inline]
+	fn from(named: Named<T>) -> Self {
+		Type::Named(named.0.into())
 	}
 }